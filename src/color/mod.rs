@@ -0,0 +1,288 @@
+//! Color glyphs.
+
+mod svg;
+
+pub use opentype::tables::color_palettes::{Color, ColorPalettes};
+
+use std::io::Result;
+
+use crate::glyph::{Glyph, GlyphID};
+use crate::{Number, Offset};
+
+/// A color glyph.
+#[derive(Clone, Debug)]
+pub struct ColorGlyph {
+    /// The layers from bottom to top, which are empty for paint-only glyphs.
+    pub layers: Vec<Layer>,
+    /// The paint.
+    pub paint: Paint,
+    /// The left, bottom, right, and top edges of the clip box.
+    pub clip_box: Option<(Number, Number, Number, Number)>,
+}
+
+/// A layer of a color glyph.
+#[derive(Clone, Debug)]
+pub struct Layer {
+    /// The glyph identifier.
+    pub glyph_id: GlyphID,
+    /// The outline.
+    pub glyph: Glyph,
+    /// The index of the color in a palette or `None` for the foreground color.
+    pub palette_index: Option<u16>,
+}
+
+/// A paint.
+#[derive(Clone, Debug)]
+pub enum Paint {
+    /// Paints composed from bottom to top.
+    Layers(Vec<Paint>),
+    /// A solid color.
+    Solid {
+        /// The index of the color in a palette or `None` for the foreground color.
+        palette_index: Option<u16>,
+        /// The alpha.
+        alpha: Number,
+    },
+    /// A linear gradient.
+    LinearGradient {
+        /// The color line.
+        color_line: ColorLine,
+        /// The start point, the end point, and the rotation point.
+        points: (Offset, Offset, Offset),
+    },
+    /// A radial gradient.
+    RadialGradient {
+        /// The color line.
+        color_line: ColorLine,
+        /// The center and radius of the start circle.
+        start: (Offset, Number),
+        /// The center and radius of the end circle.
+        end: (Offset, Number),
+    },
+    /// A sweep gradient.
+    SweepGradient {
+        /// The color line.
+        color_line: ColorLine,
+        /// The center.
+        center: Offset,
+        /// The start and end angles in degrees counterclockwise.
+        angles: (Number, Number),
+    },
+    /// A paint clipped by an outline.
+    Glyph {
+        /// The glyph identifier.
+        glyph_id: GlyphID,
+        /// The outline.
+        glyph: Glyph,
+        /// The paint.
+        paint: Box<Paint>,
+    },
+    /// A transformed paint.
+    Transform {
+        /// The affine transformation as `[xx, yx, xy, yy, dx, dy]`.
+        matrix: [Number; 6],
+        /// The paint.
+        paint: Box<Paint>,
+    },
+    /// A composition of two paints.
+    Composite {
+        /// The source.
+        source: Box<Paint>,
+        /// The mode.
+        mode: CompositeMode,
+        /// The backdrop.
+        backdrop: Box<Paint>,
+    },
+}
+
+/// A color line.
+#[derive(Clone, Debug)]
+pub struct ColorLine {
+    /// The extend mode.
+    pub extend: Extend,
+    /// The stops.
+    pub stops: Vec<ColorStop>,
+}
+
+/// A color stop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    /// The position on the color line.
+    pub offset: Number,
+    /// The index of the color in a palette or `None` for the foreground color.
+    pub palette_index: Option<u16>,
+    /// The alpha.
+    pub alpha: Number,
+}
+
+/// An extend mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Extend {
+    /// Use the colors at the ends.
+    #[default]
+    Pad,
+    /// Repeat the color line.
+    Repeat,
+    /// Repeat the color line reflecting every other time.
+    Reflect,
+}
+
+/// A composite mode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompositeMode {
+    /// Draw nothing.
+    Clear,
+    /// Draw the source only.
+    Source,
+    /// Draw the backdrop only.
+    Destination,
+    /// Draw the source over the backdrop.
+    SourceOver,
+    /// Draw the backdrop over the source.
+    DestinationOver,
+    /// Draw the source where the backdrop is.
+    SourceIn,
+    /// Draw the backdrop where the source is.
+    DestinationIn,
+    /// Draw the source where the backdrop is not.
+    SourceOut,
+    /// Draw the backdrop where the source is not.
+    DestinationOut,
+    /// Draw the source over the backdrop where the backdrop is.
+    SourceAtop,
+    /// Draw the backdrop over the source where the source is.
+    DestinationAtop,
+    /// Draw the source and the backdrop where they do not overlap.
+    Xor,
+    /// Add the source to the backdrop.
+    Plus,
+    /// Screen the source with the backdrop.
+    Screen,
+    /// Overlay the source on the backdrop.
+    Overlay,
+    /// Keep the darker of the source and the backdrop.
+    Darken,
+    /// Keep the lighter of the source and the backdrop.
+    Lighten,
+    /// Brighten the backdrop to reflect the source.
+    ColorDodge,
+    /// Darken the backdrop to reflect the source.
+    ColorBurn,
+    /// Multiply or screen depending on the source.
+    HardLight,
+    /// Darken or lighten depending on the source.
+    SoftLight,
+    /// Subtract the darker of the source and the backdrop from the lighter.
+    Difference,
+    /// Subtract with lower contrast than the difference.
+    Exclusion,
+    /// Multiply the source by the backdrop.
+    Multiply,
+    /// Take the hue of the source and the saturation and luminosity of the backdrop.
+    Hue,
+    /// Take the saturation of the source and the hue and luminosity of the backdrop.
+    Saturation,
+    /// Take the hue and saturation of the source and the luminosity of the backdrop.
+    Color,
+    /// Take the luminosity of the source and the hue and saturation of the backdrop.
+    Luminosity,
+}
+
+impl ColorGlyph {
+    /// Render the glyph as an SVG document with the y axis pointing down.
+    ///
+    /// Sweep gradients and the composite modes that SVG cannot express, which are the out and
+    /// atop modes and exclusive or, result in an error.
+    pub fn svg(
+        &self,
+        palettes: &ColorPalettes,
+        palette: usize,
+        foreground: Color,
+    ) -> Result<String> {
+        let colors = match palettes.iter().nth(palette) {
+            Some(colors) => colors.copied().collect(),
+            _ => Vec::new(),
+        };
+        svg::render(self, &colors, foreground)
+    }
+}
+
+impl Layer {
+    /// Return the color in a palette or `None` for the foreground color.
+    pub fn color(&self, palettes: &ColorPalettes, palette: usize) -> Option<Color> {
+        let index = self.palette_index? as usize;
+        palettes.iter().nth(palette)?.nth(index).copied()
+    }
+}
+
+impl Paint {
+    /// Visit the paint and its descendants in depth-first order.
+    pub fn walk<F: FnMut(&Paint)>(&self, callback: &mut F) {
+        callback(self);
+        match self {
+            Paint::Layers(paints) => paints.iter().for_each(|paint| paint.walk(callback)),
+            Paint::Glyph { paint, .. } | Paint::Transform { paint, .. } => paint.walk(callback),
+            Paint::Composite {
+                source, backdrop, ..
+            } => {
+                backdrop.walk(callback);
+                source.walk(callback);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl From<u8> for Extend {
+    #[inline]
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Extend::Repeat,
+            2 => Extend::Reflect,
+            _ => Extend::Pad,
+        }
+    }
+}
+
+impl TryFrom<u8> for CompositeMode {
+    type Error = std::io::Error;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        use CompositeMode::*;
+
+        const MODES: [CompositeMode; 28] = [
+            Clear,
+            Source,
+            Destination,
+            SourceOver,
+            DestinationOver,
+            SourceIn,
+            DestinationIn,
+            SourceOut,
+            DestinationOut,
+            SourceAtop,
+            DestinationAtop,
+            Xor,
+            Plus,
+            Screen,
+            Overlay,
+            Darken,
+            Lighten,
+            ColorDodge,
+            ColorBurn,
+            HardLight,
+            SoftLight,
+            Difference,
+            Exclusion,
+            Multiply,
+            Hue,
+            Saturation,
+            Color,
+            Luminosity,
+        ];
+        match MODES.get(value as usize) {
+            Some(mode) => Ok(*mode),
+            _ => error!("found an unknown composite mode ({value})"),
+        }
+    }
+}
@@ -0,0 +1,437 @@
+use std::fmt::Write;
+use std::io::Result;
+
+use crate::color::{Color, ColorGlyph, ColorLine, CompositeMode, Extend, Paint};
+use crate::{Number, Offset};
+
+// The extent of the area filled by gradients and solid colors.
+const EXTENT: Number = 32768.0;
+
+struct Renderer<'l> {
+    colors: &'l [Color],
+    foreground: Color,
+    definitions: String,
+    count: usize,
+}
+
+pub fn render(glyph: &ColorGlyph, colors: &[Color], foreground: Color) -> Result<String> {
+    let mut renderer = Renderer {
+        colors,
+        foreground,
+        definitions: String::new(),
+        count: 0,
+    };
+    let mut body = String::new();
+    renderer.paint(&mut body, &glyph.paint)?;
+    if let Some((left, bottom, right, top)) = glyph.clip_box {
+        let id = renderer.next("clip");
+        let _ = write!(
+            renderer.definitions,
+            r#"<clipPath id="{id}"><rect x="{left}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            flip(top),
+            right - left,
+            top - bottom,
+        );
+        body = format!(r#"<g clip-path="url(#{id})">{body}</g>"#);
+    }
+    let mut document = String::from(r#"<svg xmlns="http://www.w3.org/2000/svg">"#);
+    if !renderer.definitions.is_empty() {
+        let _ = write!(document, "<defs>{}</defs>", renderer.definitions);
+    }
+    document.push_str(&body);
+    document.push_str("</svg>");
+    Ok(document)
+}
+
+impl<'l> Renderer<'l> {
+    fn paint(&mut self, output: &mut String, paint: &Paint) -> Result<()> {
+        match paint {
+            Paint::Layers(paints) => {
+                output.push_str("<g>");
+                for paint in paints {
+                    self.paint(output, paint)?;
+                }
+                output.push_str("</g>");
+            }
+            Paint::Solid {
+                palette_index,
+                alpha,
+            } => {
+                let fill = self.color(*palette_index, *alpha);
+                fill_rectangle(output, &fill);
+            }
+            Paint::LinearGradient {
+                color_line,
+                points: (start, end, rotation),
+            } => {
+                let end = project(*start, *end, *rotation);
+                let id = self.next("gradient");
+                let _ = write!(
+                    self.definitions,
+                    r#"<linearGradient id="{id}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}"{}>"#,
+                    start.0,
+                    flip(start.1),
+                    end.0,
+                    flip(end.1),
+                    spread(color_line.extend),
+                );
+                self.stops(color_line);
+                self.definitions.push_str("</linearGradient>");
+                fill_rectangle(output, &format!(r#"fill="url(#{id})""#));
+            }
+            Paint::RadialGradient {
+                color_line,
+                start: (start, start_radius),
+                end: (end, end_radius),
+            } => {
+                let id = self.next("gradient");
+                let _ = write!(
+                    self.definitions,
+                    r#"<radialGradient id="{id}" gradientUnits="userSpaceOnUse" fx="{}" fy="{}" fr="{start_radius}" cx="{}" cy="{}" r="{end_radius}"{}>"#,
+                    start.0,
+                    flip(start.1),
+                    end.0,
+                    flip(end.1),
+                    spread(color_line.extend),
+                );
+                self.stops(color_line);
+                self.definitions.push_str("</radialGradient>");
+                fill_rectangle(output, &format!(r#"fill="url(#{id})""#));
+            }
+            Paint::SweepGradient { .. } => raise!("found an unsupported sweep gradient"),
+            Paint::Glyph { glyph, paint, .. } => {
+                let id = self.next("clip");
+                let _ = write!(
+                    self.definitions,
+                    r#"<clipPath id="{id}"><path d="{}"/></clipPath>"#,
+                    glyph.svg_path(),
+                );
+                let _ = write!(output, r#"<g clip-path="url(#{id})">"#);
+                self.paint(output, paint)?;
+                output.push_str("</g>");
+            }
+            Paint::Transform {
+                matrix: [xx, yx, xy, yy, dx, dy],
+                paint,
+            } => {
+                let _ = write!(
+                    output,
+                    r#"<g transform="matrix({xx} {} {} {yy} {dx} {})">"#,
+                    flip(*yx),
+                    flip(*xy),
+                    flip(*dy),
+                );
+                self.paint(output, paint)?;
+                output.push_str("</g>");
+            }
+            Paint::Composite {
+                source,
+                mode,
+                backdrop,
+            } => {
+                let blend = match mode {
+                    CompositeMode::Clear => return Ok(()),
+                    CompositeMode::Source => return self.paint(output, source),
+                    CompositeMode::Destination => return self.paint(output, backdrop),
+                    CompositeMode::SourceOver => {
+                        output.push_str("<g>");
+                        self.paint(output, backdrop)?;
+                        self.paint(output, source)?;
+                        output.push_str("</g>");
+                        return Ok(());
+                    }
+                    CompositeMode::DestinationOver => {
+                        output.push_str("<g>");
+                        self.paint(output, source)?;
+                        self.paint(output, backdrop)?;
+                        output.push_str("</g>");
+                        return Ok(());
+                    }
+                    CompositeMode::SourceIn => return self.mask(output, source, backdrop),
+                    CompositeMode::DestinationIn => return self.mask(output, backdrop, source),
+                    CompositeMode::Plus => "plus-lighter",
+                    CompositeMode::Screen => "screen",
+                    CompositeMode::Overlay => "overlay",
+                    CompositeMode::Darken => "darken",
+                    CompositeMode::Lighten => "lighten",
+                    CompositeMode::ColorDodge => "color-dodge",
+                    CompositeMode::ColorBurn => "color-burn",
+                    CompositeMode::HardLight => "hard-light",
+                    CompositeMode::SoftLight => "soft-light",
+                    CompositeMode::Difference => "difference",
+                    CompositeMode::Exclusion => "exclusion",
+                    CompositeMode::Multiply => "multiply",
+                    CompositeMode::Hue => "hue",
+                    CompositeMode::Saturation => "saturation",
+                    CompositeMode::Color => "color",
+                    CompositeMode::Luminosity => "luminosity",
+                    CompositeMode::SourceOut
+                    | CompositeMode::DestinationOut
+                    | CompositeMode::SourceAtop
+                    | CompositeMode::DestinationAtop
+                    | CompositeMode::Xor => {
+                        raise!("found an unsupported composite mode ({mode:?})")
+                    }
+                };
+                output.push_str(r#"<g style="isolation:isolate">"#);
+                self.paint(output, backdrop)?;
+                let _ = write!(output, r#"<g style="mix-blend-mode:{blend}">"#);
+                self.paint(output, source)?;
+                output.push_str("</g></g>");
+            }
+        }
+        Ok(())
+    }
+
+    // Draw a paint where another paint is opaque.
+    fn mask(&mut self, output: &mut String, paint: &Paint, mask: &Paint) -> Result<()> {
+        let mut content = String::new();
+        self.paint(&mut content, mask)?;
+        let id = self.next("mask");
+        let _ = write!(
+            self.definitions,
+            r#"<mask id="{id}" mask-type="alpha" maskUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}">{content}</mask>"#,
+            -EXTENT,
+            -EXTENT,
+            2.0 * EXTENT,
+            2.0 * EXTENT,
+        );
+        let _ = write!(output, r#"<g mask="url(#{id})">"#);
+        self.paint(output, paint)?;
+        output.push_str("</g>");
+        Ok(())
+    }
+
+    fn stops(&mut self, color_line: &ColorLine) {
+        for stop in color_line.stops.iter() {
+            let ([red, green, blue], opacity) = self.resolve(stop.palette_index, stop.alpha);
+            let _ = write!(
+                self.definitions,
+                r##"<stop offset="{}" stop-color="#{red:02x}{green:02x}{blue:02x}" stop-opacity="{opacity}"/>"##,
+                stop.offset,
+            );
+        }
+    }
+
+    fn color(&self, palette_index: Option<u16>, alpha: Number) -> String {
+        let ([red, green, blue], opacity) = self.resolve(palette_index, alpha);
+        format!(r##"fill="#{red:02x}{green:02x}{blue:02x}" fill-opacity="{opacity}""##)
+    }
+
+    fn resolve(&self, palette_index: Option<u16>, alpha: Number) -> ([u8; 3], Number) {
+        let color = palette_index
+            .and_then(|index| self.colors.get(index as usize))
+            .unwrap_or(&self.foreground);
+        (
+            [color.red, color.green, color.blue],
+            Number::from(color.alpha) / 255.0 * alpha,
+        )
+    }
+
+    fn next(&mut self, prefix: &str) -> String {
+        self.count += 1;
+        format!("{prefix}{}", self.count)
+    }
+}
+
+fn fill_rectangle(output: &mut String, fill: &str) {
+    let _ = write!(
+        output,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" {fill}/>"#,
+        -EXTENT,
+        -EXTENT,
+        2.0 * EXTENT,
+        2.0 * EXTENT,
+    );
+}
+
+// Negate a vertical coordinate without producing negative zeros.
+#[inline]
+fn flip(value: Number) -> Number {
+    0.0 - value
+}
+
+// Project the end point onto the line through the start point perpendicular to the rotation.
+fn project(start: Offset, end: Offset, rotation: Offset) -> Offset {
+    let normal = Offset(rotation.1 - start.1, start.0 - rotation.0);
+    let length = normal.0 * normal.0 + normal.1 * normal.1;
+    if length == 0.0 {
+        return end;
+    }
+    let delta = end - start;
+    start + normal * ((delta.0 * normal.0 + delta.1 * normal.1) / length)
+}
+
+fn spread(extend: Extend) -> &'static str {
+    match extend {
+        Extend::Pad => "",
+        Extend::Repeat => r#" spreadMethod="repeat""#,
+        Extend::Reflect => r#" spreadMethod="reflect""#,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentype::tables::color_palettes::{Header, Header0};
+
+    use crate::color::{
+        Color, ColorGlyph, ColorLine, ColorPalettes, ColorStop, CompositeMode, Extend, Paint,
+    };
+    use crate::glyph::Glyph;
+    use crate::Offset;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn render() {
+        let glyph = ColorGlyph {
+            layers: vec![],
+            paint: Paint::Layers(vec![
+                Paint::Glyph {
+                    glyph_id: 1,
+                    glyph: Glyph::default(),
+                    paint: Box::new(Paint::Solid {
+                        palette_index: Some(1),
+                        alpha: 0.5,
+                    }),
+                },
+                Paint::Composite {
+                    source: Box::new(Paint::Transform {
+                        matrix: [1.0, 0.0, 0.0, 1.0, 10.0, 20.0],
+                        paint: Box::new(Paint::LinearGradient {
+                            color_line: ColorLine {
+                                extend: Extend::Repeat,
+                                stops: vec![
+                                    ColorStop {
+                                        offset: 0.0,
+                                        palette_index: Some(0),
+                                        alpha: 1.0,
+                                    },
+                                    ColorStop {
+                                        offset: 1.0,
+                                        palette_index: None,
+                                        alpha: 1.0,
+                                    },
+                                ],
+                            },
+                            points: (Offset(0.0, 0.0), Offset(100.0, 100.0), Offset(0.0, 100.0)),
+                        }),
+                    }),
+                    mode: CompositeMode::Multiply,
+                    backdrop: Box::new(Paint::Solid {
+                        palette_index: None,
+                        alpha: 1.0,
+                    }),
+                },
+            ]),
+            clip_box: Some((0.0, -10.0, 100.0, 90.0)),
+        };
+        let palettes = ColorPalettes {
+            header: Header::Version0(Header0 {
+                version: 0,
+                entry_count: 2,
+                palette_count: 1,
+                color_count: 2,
+                color_offset: 0,
+                color_indices: vec![0],
+            }),
+            colors: vec![color(0xff, 0x00, 0x00, 0xff), color(0x00, 0x00, 0xff, 0xff)],
+        };
+        let foreground = color(0x00, 0x00, 0x00, 0xff);
+        assert_eq!(
+            ok!(glyph.svg(&palettes, 0, foreground)),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg"><defs>"#,
+                r#"<clipPath id="clip1"><path d=""/></clipPath>"#,
+                r#"<linearGradient id="gradient2" gradientUnits="userSpaceOnUse" "#,
+                r#"x1="0" y1="0" x2="100" y2="0" spreadMethod="repeat">"#,
+                r##"<stop offset="0" stop-color="#ff0000" stop-opacity="1"/>"##,
+                r##"<stop offset="1" stop-color="#000000" stop-opacity="1"/>"##,
+                r#"</linearGradient>"#,
+                r#"<clipPath id="clip3"><rect x="0" y="-90" width="100" height="100"/></clipPath>"#,
+                r#"</defs><g clip-path="url(#clip3)"><g><g clip-path="url(#clip1)">"#,
+                r##"<rect x="-32768" y="-32768" width="65536" height="65536" fill="#0000ff" fill-opacity="0.5"/>"##,
+                r#"</g><g style="isolation:isolate">"#,
+                r##"<rect x="-32768" y="-32768" width="65536" height="65536" fill="#000000" fill-opacity="1"/>"##,
+                r#"<g style="mix-blend-mode:multiply"><g transform="matrix(1 0 0 1 10 -20)">"#,
+                r#"<rect x="-32768" y="-32768" width="65536" height="65536" fill="url(#gradient2)"/>"#,
+                r#"</g></g></g></g></g></svg>"#,
+            ),
+        );
+    }
+
+    #[test]
+    fn composite() {
+        let solid = |palette_index| {
+            Box::new(Paint::Solid {
+                palette_index,
+                alpha: 1.0,
+            })
+        };
+        let glyph = |mode| ColorGlyph {
+            layers: vec![],
+            paint: Paint::Composite {
+                source: solid(Some(0)),
+                mode,
+                backdrop: solid(None),
+            },
+            clip_box: None,
+        };
+        let colors = [color(0xff, 0x00, 0x00, 0xff)];
+        let foreground = color(0x00, 0x00, 0x00, 0xff);
+        assert_eq!(
+            ok!(super::render(
+                &glyph(CompositeMode::SourceIn),
+                &colors,
+                foreground
+            )),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg"><defs>"#,
+                r#"<mask id="mask1" mask-type="alpha" maskUnits="userSpaceOnUse" "#,
+                r#"x="-32768" y="-32768" width="65536" height="65536">"#,
+                r##"<rect x="-32768" y="-32768" width="65536" height="65536" fill="#000000" fill-opacity="1"/>"##,
+                r#"</mask></defs><g mask="url(#mask1)">"#,
+                r##"<rect x="-32768" y="-32768" width="65536" height="65536" fill="#ff0000" fill-opacity="1"/>"##,
+                r#"</g></svg>"#,
+            ),
+        );
+        assert!(ok!(super::render(
+            &glyph(CompositeMode::Plus),
+            &colors,
+            foreground
+        ))
+        .contains("mix-blend-mode:plus-lighter"));
+        for mode in [
+            CompositeMode::SourceOut,
+            CompositeMode::DestinationOut,
+            CompositeMode::SourceAtop,
+            CompositeMode::DestinationAtop,
+            CompositeMode::Xor,
+        ] {
+            assert!(super::render(&glyph(mode), &colors, foreground).is_err());
+        }
+
+        let glyph = ColorGlyph {
+            layers: vec![],
+            paint: Paint::SweepGradient {
+                color_line: ColorLine {
+                    extend: Extend::Pad,
+                    stops: vec![],
+                },
+                center: Offset(0.0, 0.0),
+                angles: (0.0, 360.0),
+            },
+            clip_box: None,
+        };
+        assert!(super::render(&glyph, &colors, foreground).is_err());
+    }
+
+    fn color(red: u8, green: u8, blue: u8, alpha: u8) -> Color {
+        Color {
+            blue,
+            green,
+            red,
+            alpha,
+        }
+    }
+}
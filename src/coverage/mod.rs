@@ -0,0 +1,104 @@
+//! Unicode coverage.
+
+mod data;
+
+use crate::characters::Character;
+use crate::Number;
+
+/// A coverage report.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Coverage {
+    /// The scripts with at least one character.
+    pub scripts: Vec<Entry>,
+    /// The blocks with at least one character.
+    pub blocks: Vec<Entry>,
+}
+
+/// A script or block with the number of supported characters.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Entry {
+    /// The name.
+    pub name: &'static str,
+    /// The number of supported characters.
+    pub count: usize,
+    /// The number of assigned characters.
+    pub total: usize,
+}
+
+impl Coverage {
+    /// Create a coverage report for characters.
+    pub fn new(characters: &[Character]) -> Self {
+        let mut ranges = characters
+            .iter()
+            .map(|character| match character {
+                Character::Scalar(value) => (*value as u32, *value as u32),
+                Character::Range((start, end)) => (*start as u32, *end as u32),
+            })
+            .filter(|(start, end)| start <= end)
+            .collect::<Vec<_>>();
+        ranges.sort();
+        ranges.dedup_by(|next, last| {
+            let overlapping = next.0 <= last.1;
+            if overlapping {
+                last.1 = last.1.max(next.1);
+            }
+            overlapping
+        });
+
+        let mut scripts = vec![(0, 0); data::SCRIPT_NAMES.len()];
+        for (start, end, index) in data::SCRIPTS.iter() {
+            let entry = &mut scripts[*index as usize];
+            entry.0 += intersect(&ranges, *start, *end);
+            entry.1 += (end - start + 1) as usize;
+        }
+        let scripts = scripts
+            .into_iter()
+            .zip(data::SCRIPT_NAMES.iter())
+            .filter(|((count, _), _)| *count > 0)
+            .map(|((count, total), name)| Entry { name, count, total })
+            .collect();
+
+        let blocks = data::BLOCKS
+            .iter()
+            .filter_map(|(start, end, name)| {
+                let (mut count, mut total) = (0, 0);
+                let first = data::SCRIPTS.partition_point(|range| range.1 < *start);
+                for (first, last, _) in data::SCRIPTS[first..]
+                    .iter()
+                    .take_while(|range| range.0 <= *end)
+                {
+                    let (first, last) = ((*first).max(*start), (*last).min(*end));
+                    count += intersect(&ranges, first, last);
+                    total += (last - first + 1) as usize;
+                }
+                if total == 0 {
+                    count = intersect(&ranges, *start, *end);
+                    total = (end - start + 1) as usize;
+                }
+                if count == 0 {
+                    return None;
+                }
+                Some(Entry { name, count, total })
+            })
+            .collect();
+
+        Self { scripts, blocks }
+    }
+}
+
+impl Entry {
+    /// Return the percentage of assigned characters that are supported.
+    #[inline]
+    pub fn percentage(&self) -> Number {
+        100.0 * self.count as Number / self.total as Number
+    }
+}
+
+fn intersect(ranges: &[(u32, u32)], start: u32, end: u32) -> usize {
+    let first = ranges.partition_point(|range| range.1 < start);
+    ranges[first..]
+        .iter()
+        .take_while(|range| range.0 <= end)
+        .map(|range| (range.1.min(end).saturating_sub(range.0.max(start)) + 1) as usize)
+        .sum()
+}
@@ -1,6 +1,13 @@
 use std::io::Result;
 
-use crate::{Axes, Characters, Features, Glyph, Metrics, Names, Palettes, Tables, Timestamps};
+use opentype::truetype::tables::names::NameID;
+
+use crate::glyph::{Bitmap, Component, Document, GlyphID};
+use crate::languages::Support;
+use crate::{
+    Axes, Characters, ColorGlyph, Coverage, Encodings, Features, Glyph, Layout, LineMetrics,
+    LinePolicy, Metrics, Names, Number, Palettes, Sequences, Tables, Timestamps, VerticalMetrics,
+};
 
 /// A font.
 pub struct Font<T> {
@@ -17,14 +24,19 @@ macro_rules! implement {
     (
         $(
             $(#[$attribute:meta])*
-            fn $function:ident($($argument_name:ident: $argument_type:ty),*) -> $type:ty;
+            fn $function:ident$(<$generic:ident: $($bound:ident)::+ $(+ $($other:ident)::+)*>)?(
+                $($argument_name:ident: $argument_type:ty),*
+            ) -> $type:ty;
         )+
     ) => (
         /// A type that represents a font in a specific format.
         pub trait Case {
             $(
                 $(#[$attribute])*
-                fn $function(&mut self $(, $argument_name: $argument_type)*) -> Result<$type>;
+                fn $function$(<$generic: $($bound)::+ $(+ $($other)::+)*>)?(
+                    &mut self
+                    $(, $argument_name: $argument_type)*
+                ) -> Result<$type>;
             )+
         }
 
@@ -35,7 +47,10 @@ macro_rules! implement {
             $(
                 $(#[$attribute])*
                 #[inline]
-                pub fn $function(&mut self $(, $argument_name: $argument_type)*) -> Result<$type> {
+                pub fn $function$(<$generic: $($bound)::+ $(+ $($other)::+)*>)?(
+                    &mut self
+                    $(, $argument_name: $argument_type)*
+                ) -> Result<$type> {
                     match self.format {
                         Format::OpenType(ref mut font) => font.$function($($argument_name),*),
                         #[cfg(feature = "webtype")]
@@ -52,20 +67,67 @@ implement! {
     fn axes() -> Axes;
     /// Return the characters.
     fn characters() -> Characters;
+    /// Return the characters mapped to a glyph.
+    fn characters_of(glyph_id: GlyphID) -> Vec<char>;
+    /// Return the codes of the selected character-to-glyph encoding with their glyphs, which are
+    /// not Unicode scalars for multi-byte encodings.
+    fn codes() -> Vec<(u32, GlyphID)>;
+    /// Return the Unicode script and block coverage.
+    fn coverage() -> Coverage;
+    /// Return the character-to-glyph encodings.
+    fn encodings() -> Encodings;
     /// Return the features.
     fn features() -> Features;
     /// Return the metrics.
     fn metrics() -> Metrics;
+    /// Return the line-spacing metrics chosen according to a policy.
+    fn line_metrics(policy: LinePolicy) -> LineMetrics;
     /// Return the names.
     fn names() -> Names;
     /// Return the palettes.
-    fn palettes() -> Palettes;
+    fn palettes() -> Option<Palettes>;
+    /// Return the Unicode variation sequences.
+    fn sequences() -> Sequences;
     /// Return the tables.
     fn tables() -> Tables;
     /// Return the timestamps.
     fn timestamps() -> Timestamps;
+    /// Return the vertical metrics.
+    fn vertical_metrics() -> VerticalMetrics;
+    /// Use a specific character-to-glyph encoding instead of the preferred one.
+    fn select_encoding(platform_id: u16, encoding_id: u16) -> ();
+    /// Set the value of a name record in a language given as a BCP 47 tag.
+    fn set_name(name_id: NameID, language: &str, value: &str) -> ();
+    /// Remove the name records in a language given as a BCP 47 tag.
+    fn remove_name(name_id: NameID, language: &str) -> ();
+    /// Return the languages whose characters are supported.
+    fn supported_languages() -> Vec<Support>;
     /// Return the glyph of a character.
     fn glyph(character: char) -> Option<Glyph>;
+    /// Return the glyph of a character followed by a variation selector falling back to the glyph
+    /// of the character.
+    fn glyph_variant(character: char, selector: char) -> Option<Glyph>;
+    /// Return the glyph of a character grid-fitted to a size in pixels per em.
+    fn hinted_glyph(character: char, ppem: u16) -> Option<Glyph>;
+    /// Return the bitmap glyph of a character choosing the strike closest to a size in pixels per
+    /// em.
+    fn bitmap_glyph(character: char, ppem: u16) -> Option<Bitmap>;
+    /// Return the SVG document of a glyph.
+    fn svg_glyph(glyph_id: GlyphID) -> Option<Document>;
+    /// Return the layered color glyph of a character.
+    fn color_glyph(character: char) -> Option<ColorGlyph>;
+    /// Return the glyph with an identifier.
+    fn glyph_by_id(glyph_id: GlyphID) -> Glyph;
+    /// Return the glyph with an identifier without drawing its components.
+    fn shallow_glyph(glyph_id: GlyphID) -> Glyph;
+    /// Return the components of a glyph with an identifier.
+    fn components(glyph_id: GlyphID) -> Vec<Component>;
+    /// Lay out a line of text at a size and measure it.
+    fn measure(text: &str, size: Number) -> Layout;
+    /// Combine a line of text at a size and letter spacing into a single outline.
+    fn outline(text: &str, size: Number, spacing: Number) -> Glyph;
+    /// Write the font updating the names.
+    fn save<U: crate::Read + crate::Write>(tape: &mut U) -> ();
 }
 
 pub fn read<T: crate::Read>(mut tape: T) -> Result<Vec<Font<T>>> {
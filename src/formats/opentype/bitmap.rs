@@ -0,0 +1,670 @@
+use std::io::Result;
+use std::ops::DerefMut;
+
+use opentype::truetype::tables::MaximumProfile;
+use opentype::truetype::{GlyphID, Tag};
+
+use crate::formats::opentype::cache::Cache;
+use crate::glyph::{Bitmap, BitmapFormat};
+use crate::Number;
+
+// The maximum number of redirections followed for duplicate glyphs.
+const REDIRECTIONS: usize = 8;
+
+// The image format, the offset and size of the image, and the metrics if shared.
+type Location = (u16, u32, u32, Option<Metrics>);
+
+#[derive(Clone, Debug)]
+pub struct Strike {
+    pub ppem: u16,
+    pub bit_depth: u8,
+    pub subtables: Vec<Subtable>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Subtable {
+    pub first: GlyphID,
+    pub last: GlyphID,
+    pub image_format: u16,
+    pub image_offset: u32,
+    pub index: Index,
+}
+
+#[derive(Clone, Debug)]
+pub enum Index {
+    Offsets(Vec<u32>),
+    Pairs(Vec<(GlyphID, u16)>),
+    Constant {
+        size: u32,
+        metrics: Metrics,
+        glyph_ids: Option<Vec<GlyphID>>,
+    },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StandardBitmaps {
+    pub strikes: Vec<StandardStrike>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StandardStrike {
+    pub ppem: u16,
+    pub offset: u32,
+    pub offsets: Vec<u32>,
+}
+
+table! {
+    #[derive(Copy)]
+    pub Metrics { // BigGlyphMetrics
+        height               (u8), // height
+        width                (u8), // width
+        horizontal_bearing_x (i8), // horiBearingX
+        horizontal_bearing_y (i8), // horiBearingY
+        horizontal_advance   (u8), // horiAdvance
+        vertical_bearing_x   (i8), // vertBearingX
+        vertical_bearing_y   (i8), // vertBearingY
+        vertical_advance     (u8), // vertAdvance
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub SmallMetrics { // SmallGlyphMetrics
+        height    (u8), // height
+        width     (u8), // width
+        bearing_x (i8), // bearingX
+        bearing_y (i8), // bearingY
+        advance   (u8), // advance
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub LineMetrics { // SbitLineMetrics
+        ascender                 (i8), // ascender
+        descender                (i8), // descender
+        max_width                (u8), // widthMax
+        caret_slope_numerator    (i8), // caretSlopeNumerator
+        caret_slope_denominator  (i8), // caretSlopeDenominator
+        caret_offset             (i8), // caretOffset
+        min_origin_side_bearing  (i8), // minOriginSB
+        min_advance_side_bearing (i8), // minAdvanceSB
+        max_before_baseline      (i8), // maxBeforeBL
+        min_after_baseline       (i8), // minAfterBL
+        padding1                 (i8), // pad1
+        padding2                 (i8), // pad2
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub Size { // BitmapSize
+        subtable_offset (u32), // indexSubtableListOffset
+        subtable_size   (u32), // indexSubtableListSize
+        subtable_count  (u32), // numberOfIndexSubtables
+        color_reference (u32), // colorRef
+        horizontal      (LineMetrics), // hori
+        vertical        (LineMetrics), // vert
+        start_glyph_id  (GlyphID), // startGlyphIndex
+        end_glyph_id    (GlyphID), // endGlyphIndex
+        ppem_x          (u8), // ppemX
+        ppem_y          (u8), // ppemY
+        bit_depth       (u8), // bitDepth
+        flags           (i8), // flags
+    }
+}
+
+macro_rules! locations(
+    ($($name:ident => $tag:literal,)+) => ($(
+        #[derive(Clone, Debug, Default)]
+        pub struct $name(pub Vec<Strike>);
+
+        impl opentype::Table for $name {
+            #[inline]
+            fn tag() -> Tag {
+                Tag(*$tag)
+            }
+        }
+
+        impl opentype::value::Read for $name {
+            #[inline]
+            fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+                Ok(Self(read_strikes(tape)?))
+            }
+        }
+    )+);
+);
+
+locations! {
+    BitmapLocations => b"EBLC",
+    ColorBitmapLocations => b"CBLC",
+}
+
+impl Strike {
+    pub fn locate(&self, glyph_id: GlyphID) -> Result<Option<Location>> {
+        let subtable = match self
+            .subtables
+            .iter()
+            .find(|subtable| subtable.first <= glyph_id && glyph_id <= subtable.last)
+        {
+            Some(subtable) => subtable,
+            _ => return Ok(None),
+        };
+        let index = (glyph_id - subtable.first) as usize;
+        let (start, end, metrics) = match &subtable.index {
+            Index::Offsets(offsets) => match (offsets.get(index), offsets.get(index + 1)) {
+                (Some(start), Some(end)) => (*start, *end, None),
+                _ => return Ok(None),
+            },
+            Index::Pairs(pairs) => {
+                // The last pair only marks the end of the data of the one before it.
+                let count = pairs.len().saturating_sub(1);
+                let position = match pairs[..count]
+                    .binary_search_by_key(&glyph_id, |(glyph_id, _)| *glyph_id)
+                {
+                    Ok(position) => position,
+                    _ => return Ok(None),
+                };
+                (pairs[position].1 as u32, pairs[position + 1].1 as u32, None)
+            }
+            Index::Constant {
+                size,
+                metrics,
+                glyph_ids,
+            } => {
+                let index = match glyph_ids {
+                    Some(glyph_ids) => match glyph_ids.binary_search(&glyph_id) {
+                        Ok(index) => index,
+                        _ => return Ok(None),
+                    },
+                    _ => index,
+                } as u32;
+                match (index.checked_mul(*size), (index + 1).checked_mul(*size)) {
+                    (Some(start), Some(end)) => (start, end, Some(*metrics)),
+                    _ => raise!("found a malformed bitmap-location table"),
+                }
+            }
+        };
+        if end <= start {
+            return Ok(None);
+        }
+        match subtable.image_offset.checked_add(start) {
+            Some(offset) => Ok(Some((subtable.image_format, offset, end - start, metrics))),
+            _ => raise!("found a malformed bitmap-location table"),
+        }
+    }
+}
+
+impl StandardStrike {
+    // The offset and size of the glyph data.
+    pub fn locate(&self, glyph_id: GlyphID) -> Result<Option<(u32, u32)>> {
+        let (start, end) = match (
+            self.offsets.get(glyph_id as usize),
+            self.offsets.get(glyph_id as usize + 1),
+        ) {
+            (Some(start), Some(end)) => (*start, *end),
+            _ => return Ok(None),
+        };
+        match (start.checked_add(8), self.offset.checked_add(start)) {
+            (Some(value), _) if end <= value => Ok(None),
+            (Some(_), Some(offset)) => Ok(Some((offset, end - start))),
+            _ => raise!("found a malformed standard-bitmap table"),
+        }
+    }
+}
+
+impl opentype::Table for StandardBitmaps {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"sbix")
+    }
+}
+
+impl<'l> opentype::walue::Read<'l> for StandardBitmaps {
+    type Parameter = &'l MaximumProfile;
+
+    fn read<T: opentype::tape::Read>(tape: &mut T, profile: Self::Parameter) -> Result<Self> {
+        let position = tape.position()?;
+        let _ = tape.take::<u16>()?; // version
+        let _ = tape.take::<u16>()?; // flags
+        let count = tape.take::<u32>()? as usize;
+        let offsets: Vec<u32> = tape.take_given(count)?;
+        let mut strikes = Vec::with_capacity(count);
+        for offset in offsets {
+            tape.jump(position + offset as u64)?;
+            let ppem = tape.take::<u16>()?;
+            let _ = tape.take::<u16>()?; // ppi
+            strikes.push(StandardStrike {
+                ppem,
+                offset,
+                offsets: tape.take_given(profile.glyph_count() + 1)?,
+            });
+        }
+        Ok(Self { strikes })
+    }
+}
+
+pub fn read<T: crate::Read>(
+    cache: &mut Cache<T>,
+    glyph_id: GlyphID,
+    ppem: u16,
+) -> Result<Option<Bitmap>> {
+    if let Some(table) = cache.try_color_bitmap_locations()? {
+        let table = table.clone();
+        let table = table.borrow();
+        if let Some(value) = read_data(cache, Tag(*b"CBDT"), &table.0, glyph_id, ppem)? {
+            return Ok(Some(value));
+        }
+    }
+    if let Some(table) = cache.try_standard_bitmaps()? {
+        let table = table.clone();
+        let table = table.borrow();
+        if let Some(value) = read_standard(cache, &table, glyph_id, ppem)? {
+            return Ok(Some(value));
+        }
+    }
+    if let Some(table) = cache.try_bitmap_locations()? {
+        let table = table.clone();
+        let table = table.borrow();
+        if let Some(value) = read_data(cache, Tag(*b"EBDT"), &table.0, glyph_id, ppem)? {
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+fn read_data<T: crate::Read>(
+    cache: &mut Cache<T>,
+    tag: Tag,
+    strikes: &[Strike],
+    glyph_id: GlyphID,
+    ppem: u16,
+) -> Result<Option<Bitmap>> {
+    let ppems = strikes.iter().map(|strike| strike.ppem).collect::<Vec<_>>();
+    for index in choose(&ppems, ppem) {
+        let strike = &strikes[index];
+        let (image_format, offset, size, metrics) = match strike.locate(glyph_id)? {
+            Some(value) => value,
+            _ => continue,
+        };
+        let mut tape = cache.tape.borrow_mut();
+        let tape = tape.deref_mut();
+        match cache.position(tag) {
+            Some(position) => tape.jump(position + u64::from(offset))?,
+            _ => raise!("cannot find the bitmap-data table"),
+        };
+        let metrics = match image_format {
+            1 | 2 | 8 | 17 => {
+                let value = tape.take::<SmallMetrics>()?;
+                Metrics {
+                    height: value.height,
+                    width: value.width,
+                    horizontal_bearing_x: value.bearing_x,
+                    horizontal_bearing_y: value.bearing_y,
+                    horizontal_advance: value.advance,
+                    ..Default::default()
+                }
+            }
+            6 | 7 | 9 | 18 => tape.take::<Metrics>()?,
+            5 | 19 => match metrics {
+                Some(metrics) => metrics,
+                _ => raise!("found a malformed bitmap-location table"),
+            },
+            value => raise!("found an unknown format of bitmap data ({value})"),
+        };
+        let (format, data) = match image_format {
+            17..=19 => {
+                let consumed = match image_format {
+                    17 => 5 + 4,
+                    18 => 8 + 4,
+                    _ => 4,
+                };
+                let length = tape.take::<u32>()?;
+                if u64::from(length) + consumed > u64::from(size) {
+                    raise!("found a bitmap exceeding its size in the bitmap-location table");
+                }
+                (BitmapFormat::Png, tape.take_bytes(length as usize)?)
+            }
+            8 | 9 => raise!("found an unsupported format of bitmap data ({image_format})"),
+            _ => {
+                let consumed = match image_format {
+                    1 | 2 => 5,
+                    6 | 7 => 8,
+                    _ => 0,
+                };
+                let data = tape.take_bytes(size.saturating_sub(consumed) as usize)?;
+                let format = match strike.bit_depth {
+                    32 => BitmapFormat::Bgra,
+                    value => BitmapFormat::Gray(value),
+                };
+                let data = match image_format {
+                    2 | 5 | 7 => align(
+                        &data,
+                        metrics.width as usize,
+                        metrics.height as usize,
+                        strike.bit_depth as usize,
+                    ),
+                    _ => data,
+                };
+                (format, data)
+            }
+        };
+        return Ok(Some(Bitmap {
+            format,
+            data,
+            ppem: strike.ppem,
+            size: (metrics.width as u16, metrics.height as u16),
+            bearing: (
+                metrics.horizontal_bearing_x as i16,
+                metrics.horizontal_bearing_y as i16,
+            ),
+            advance_width: metrics.horizontal_advance as u16,
+        }));
+    }
+    Ok(None)
+}
+
+fn read_standard<T: crate::Read>(
+    cache: &mut Cache<T>,
+    table: &StandardBitmaps,
+    glyph_id: GlyphID,
+    ppem: u16,
+) -> Result<Option<Bitmap>> {
+    let ppems = table
+        .strikes
+        .iter()
+        .map(|strike| strike.ppem)
+        .collect::<Vec<_>>();
+    for index in choose(&ppems, ppem) {
+        let strike = &table.strikes[index];
+        let mut other = glyph_id;
+        for _ in 0..REDIRECTIONS {
+            let (offset, size) = match strike.locate(other)? {
+                Some(value) => value,
+                _ => break,
+            };
+            let position = match cache.position(Tag(*b"sbix")) {
+                Some(position) => position,
+                _ => raise!("cannot find the standard-bitmap table"),
+            };
+            let (x, y, kind, data) = {
+                let mut tape = cache.tape.borrow_mut();
+                let tape = tape.deref_mut();
+                tape.jump(position + u64::from(offset))?;
+                let x = tape.take::<i16>()?;
+                let y = tape.take::<i16>()?;
+                let kind = tape.take::<Tag>()?;
+                (x, y, kind, tape.take_bytes(size as usize - 8)?)
+            };
+            let format = match &kind.0 {
+                b"png " => BitmapFormat::Png,
+                b"jpg " => BitmapFormat::Jpeg,
+                b"tiff" => BitmapFormat::Tiff,
+                b"dupe" if data.len() >= 2 => {
+                    other = GlyphID::from_be_bytes([data[0], data[1]]);
+                    continue;
+                }
+                _ => break,
+            };
+            // Images whose headers cannot be understood are assumed to fill the em square.
+            let (width, height) = match format {
+                BitmapFormat::Png => dimensions(&data),
+                BitmapFormat::Jpeg => dimensions_jpeg(&data),
+                _ => dimensions_tiff(&data),
+            }
+            .unwrap_or((strike.ppem as u32, strike.ppem as u32));
+            let units_per_em = Number::from(cache.font_header()?.borrow().units_per_em);
+            let (advance_width, _) = cache.metrics()?.get(glyph_id);
+            return Ok(Some(Bitmap {
+                format,
+                data,
+                ppem: strike.ppem,
+                size: (width as u16, height as u16),
+                bearing: (x, y.saturating_add(height as i16)),
+                advance_width: (advance_width * Number::from(strike.ppem) / units_per_em).round()
+                    as u16,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+fn read_strikes<T: opentype::tape::Read>(tape: &mut T) -> Result<Vec<Strike>> {
+    let position = tape.position()?;
+    let _ = tape.take::<u16>()?; // majorVersion
+    let _ = tape.take::<u16>()?; // minorVersion
+    let count = tape.take::<u32>()? as usize;
+    let sizes: Vec<Size> = tape.take_given(count)?;
+    let mut strikes = Vec::with_capacity(count);
+    for size in sizes {
+        let offset = position + size.subtable_offset as u64;
+        tape.jump(offset)?;
+        let mut records = Vec::with_capacity(size.subtable_count as usize);
+        for _ in 0..size.subtable_count {
+            records.push((
+                tape.take::<GlyphID>()?,
+                tape.take::<GlyphID>()?,
+                tape.take::<u32>()?,
+            ));
+        }
+        let mut subtables = Vec::with_capacity(records.len());
+        for (first, last, subtable_offset) in records {
+            if last < first {
+                raise!("found a malformed bitmap-location table");
+            }
+            tape.jump(offset + subtable_offset as u64)?;
+            let index_format = tape.take::<u16>()?;
+            let image_format = tape.take::<u16>()?;
+            let image_offset = tape.take::<u32>()?;
+            let count = (last - first) as usize + 1;
+            let index = match index_format {
+                1 => Index::Offsets(tape.take_given(count + 1)?),
+                2 => Index::Constant {
+                    size: tape.take()?,
+                    metrics: tape.take()?,
+                    glyph_ids: None,
+                },
+                3 => {
+                    let offsets: Vec<u16> = tape.take_given(count + 1)?;
+                    Index::Offsets(offsets.into_iter().map(u32::from).collect())
+                }
+                4 => {
+                    let count = tape.take::<u32>()? as usize;
+                    Index::Pairs(tape.take_given(count + 1)?)
+                }
+                5 => {
+                    let size = tape.take()?;
+                    let metrics = tape.take()?;
+                    let count = tape.take::<u32>()? as usize;
+                    Index::Constant {
+                        size,
+                        metrics,
+                        glyph_ids: Some(tape.take_given(count)?),
+                    }
+                }
+                value => raise!("found an unknown format of the bitmap index ({value})"),
+            };
+            subtables.push(Subtable {
+                first,
+                last,
+                image_format,
+                image_offset,
+                index,
+            });
+        }
+        strikes.push(Strike {
+            ppem: size.ppem_y as u16,
+            bit_depth: size.bit_depth,
+            subtables,
+        });
+    }
+    Ok(strikes)
+}
+
+// Order strikes preferring the exact size, then larger sizes, and then smaller sizes.
+fn choose(ppems: &[u16], ppem: u16) -> Vec<usize> {
+    let mut indices = (0..ppems.len()).collect::<Vec<_>>();
+    indices.sort_by_key(|index| {
+        let other = ppems[*index];
+        if other >= ppem {
+            (false, other - ppem)
+        } else {
+            (true, ppem - other)
+        }
+    });
+    indices
+}
+
+// Pad the rows of bit-aligned data to whole bytes.
+fn align(data: &[u8], width: usize, height: usize, bit_depth: usize) -> Vec<u8> {
+    let row_bits = width * bit_depth;
+    let stride = row_bits.div_ceil(8);
+    let mut values = vec![0; stride * height];
+    for row in 0..height {
+        for bit in 0..row_bits {
+            let source = row * row_bits + bit;
+            let byte = match data.get(source / 8) {
+                Some(byte) => *byte,
+                _ => return values,
+            };
+            if byte & (0x80 >> (source % 8)) != 0 {
+                values[row * stride + bit / 8] |= 0x80 >> (bit % 8);
+            }
+        }
+    }
+    values
+}
+
+// The width and height of a PNG image.
+fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    if data.len() < 24 || &data[12..16] != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+    let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    Some((width, height))
+}
+
+// The width and height of a JPEG image.
+fn dimensions_jpeg(data: &[u8]) -> Option<(u32, u32)> {
+    if data.get(..2)? != [0xFF, 0xD8] {
+        return None;
+    }
+    let mut position = 2;
+    loop {
+        let [0xFF, marker, high, low] = *data.get(position..(position + 4))? else {
+            return None;
+        };
+        match marker {
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let value = data.get((position + 5)..(position + 9))?;
+                let height = u16::from_be_bytes([value[0], value[1]]);
+                let width = u16::from_be_bytes([value[2], value[3]]);
+                return Some((width as u32, height as u32));
+            }
+            _ => position += 2 + u16::from_be_bytes([high, low]) as usize,
+        }
+    }
+}
+
+// The width and height of a TIFF image from its first image file directory.
+fn dimensions_tiff(data: &[u8]) -> Option<(u32, u32)> {
+    let little = match data.get(..4)? {
+        b"II*\0" => true,
+        b"MM\0*" => false,
+        _ => return None,
+    };
+    let read = |position: usize, size: usize| -> Option<u32> {
+        let value = data.get(position..(position + size))?;
+        Some(value.iter().enumerate().fold(0, |sum, (index, byte)| {
+            let shift = if little { index } else { size - 1 - index };
+            sum | (*byte as u32) << (8 * shift)
+        }))
+    };
+    let position = read(4, 4)? as usize;
+    let (mut width, mut height) = (None, None);
+    for index in 0..read(position, 2)? as usize {
+        let position = position + 2 + 12 * index;
+        let value = match read(position + 2, 2)? {
+            3 => read(position + 8, 2)?,
+            4 => read(position + 8, 4)?,
+            _ => continue,
+        };
+        match read(position, 2)? {
+            256 => width = Some(value),
+            257 => height = Some(value),
+            _ => {}
+        }
+    }
+    Some((width?, height?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::ColorBitmapLocations;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn align() {
+        assert_eq!(
+            super::align(&[0b1011_0110, 0b1100_0000], 5, 2, 1),
+            [0b1011_0000, 0b1101_1000],
+        );
+        assert_eq!(super::align(&[0xAB, 0xCD], 1, 2, 8), [0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn choose() {
+        assert_eq!(super::choose(&[20, 64, 136, 109], 64), [1, 3, 2, 0]);
+        assert_eq!(super::choose(&[20, 64, 136, 109], 200), [2, 3, 1, 0]);
+        assert_eq!(super::choose(&[20, 64, 136, 109], 10), [0, 1, 3, 2]);
+    }
+
+    #[test]
+    fn read() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x03, 0x00, 0x00, // majorVersion, minorVersion
+            0x00, 0x00, 0x00, 0x01, // numSizes
+            0x00, 0x00, 0x00, 0x38, // indexSubtableListOffset
+            0x00, 0x00, 0x00, 0x44, // indexSubtableListSize
+            0x00, 0x00, 0x00, 0x02, // numberOfIndexSubtables
+            0x00, 0x00, 0x00, 0x00, // colorRef
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // hori
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // vert
+            0x00, 0x01, 0x00, 0x08, // startGlyphIndex, endGlyphIndex
+            0x6D, 0x6D, 0x20, 0x01, // ppemX, ppemY, bitDepth, flags
+            // IndexSubtableList
+            0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x10, // firstGlyphIndex, lastGlyphIndex, indexSubtableOffset
+            0x00, 0x05, 0x00, 0x08, 0x00, 0x00, 0x00, 0x24, // firstGlyphIndex, lastGlyphIndex, indexSubtableOffset
+            // IndexSubtable1
+            0x00, 0x01, 0x00, 0x11, 0x00, 0x00, 0x00, 0x04, // indexFormat, imageFormat, imageDataOffset
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x64, // sbitOffsets
+            0x00, 0x00, 0x00, 0x96, // sbitOffsets
+            // IndexSubtable5
+            0x00, 0x05, 0x00, 0x13, 0x00, 0x00, 0x01, 0x00, // indexFormat, imageFormat, imageDataOffset
+            0x00, 0x00, 0x00, 0x20, // imageSize
+            0x10, 0x12, 0x01, 0x0E, 0x14, 0x00, 0x00, 0x00, // bigMetrics
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x05, 0x00, 0x08, // numGlyphs, glyphIdArray
+        ];
+        let table = ok!(ColorBitmapLocations::read(&mut Cursor::new(data)));
+        assert_eq!(table.0.len(), 1);
+        let strike = &table.0[0];
+        assert_eq!((strike.ppem, strike.bit_depth), (109, 32));
+        let (format, offset, size, metrics) = ok!(ok!(strike.locate(1)));
+        assert_eq!((format, offset, size), (17, 4, 100));
+        assert!(metrics.is_none());
+        let (_, offset, size, _) = ok!(ok!(strike.locate(2)));
+        assert_eq!((offset, size), (104, 50));
+        let (format, offset, size, metrics) = ok!(ok!(strike.locate(8)));
+        assert_eq!((format, offset, size), (19, 288, 32));
+        assert_eq!(ok!(metrics).width, 18);
+        assert!(ok!(strike.locate(3)).is_none());
+        assert!(ok!(strike.locate(6)).is_none());
+    }
+}
@@ -1,12 +1,23 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::io::Result;
 use std::ops::DerefMut;
 use std::rc::Rc;
 
 use opentype;
+use opentype::truetype::Tag;
 
+use crate::formats::opentype::bitmap::{BitmapLocations, ColorBitmapLocations, StandardBitmaps};
+use crate::formats::opentype::color::ColorGlyphs;
+use crate::formats::opentype::documents::Documents;
+use crate::formats::opentype::encoding::CharacterMapping;
+use crate::formats::opentype::hinting::Hinter;
+use crate::formats::opentype::kerning::{self, Pairs};
 use crate::formats::opentype::mapping;
-use crate::formats::opentype::metrics::Metrics;
+use crate::formats::opentype::metrics::{self, Metrics};
+use crate::formats::opentype::palettes::Labels;
+use crate::formats::opentype::variants::Variants;
+use crate::formats::opentype::vertical::{VerticalHeader, VerticalMetrics, VerticalOrigin};
 
 pub type Reference<T> = Rc<RefCell<T>>;
 
@@ -21,9 +32,12 @@ macro_rules! cache(
                     tape,
                     backend,
 
+                    selection: Default::default(),
                     forward_mapping: Default::default(),
                     reverse_mapping: Default::default(),
                     metrics: Default::default(),
+                    pairs: Default::default(),
+                    hinters: Default::default(),
 
                     $($field: Default::default(),)+
                 }
@@ -37,9 +51,12 @@ macro_rules! cache(
             pub tape: Reference<T>,
             pub backend: opentype::Font,
 
+            pub selection: Option<usize>,
             forward_mapping: Option<Rc<mapping::Forward>>,
             reverse_mapping: Option<Rc<mapping::Reverse>>,
             metrics: Option<Rc<Metrics>>,
+            pairs: Option<Rc<Pairs>>,
+            hinters: HashMap<u16, Rc<Hinter>>,
 
             $(pub $field: Option<Reference<$type>>,)+
         }
@@ -102,14 +119,39 @@ dereference! { Cache<T>::backend => opentype::Font }
 cache! {
     (
         character_mapping -> try_character_mapping(),
-        opentype::truetype::tables::CharacterMapping,
+        CharacterMapping,
         "the character-to-glyph mapping",
     ),
+    (
+        bitmap_locations -> try_bitmap_locations(),
+        BitmapLocations,
+        "the bitmap-location table",
+    ),
+    (
+        color_bitmap_locations -> try_color_bitmap_locations(),
+        ColorBitmapLocations,
+        "the color-bitmap-location table",
+    ),
+    (
+        color_glyphs -> try_color_glyphs(),
+        ColorGlyphs,
+        "the color table",
+    ),
+    (
+        color_palette_labels -> try_color_palette_labels(),
+        Labels,
+        "the color-palette table",
+    ),
     (
         color_palettes -> try_color_palettes(),
         opentype::tables::ColorPalettes,
         "the color-palette table",
     ),
+    (
+        documents -> try_documents(),
+        Documents,
+        "the SVG table",
+    ),
     (
         font_header -> try_font_header(),
         opentype::truetype::tables::FontHeader,
@@ -155,6 +197,11 @@ cache! {
         opentype::truetype::tables::HorizontalMetrics,
         "the horizontal metrics",
     ),
+    (
+        kerning -> try_kerning(),
+        kerning::Kerning,
+        "the kerning table",
+    ),
     (
         maximum_profile -> try_maximum_profile(),
         opentype::truetype::tables::MaximumProfile,
@@ -170,6 +217,31 @@ cache! {
         opentype::truetype::tables::PostScript,
         "the PostScript table",
     ),
+    (
+        standard_bitmaps -> try_standard_bitmaps(try_maximum_profile),
+        StandardBitmaps,
+        "the standard-bitmap table",
+    ),
+    (
+        variants -> try_variants(),
+        Variants,
+        "the Unicode variation sequences",
+    ),
+    (
+        vertical_header -> try_vertical_header(),
+        VerticalHeader,
+        "the vertical header",
+    ),
+    (
+        vertical_metrics -> try_vertical_metrics(try_vertical_header, try_maximum_profile),
+        VerticalMetrics,
+        "the vertical metrics",
+    ),
+    (
+        vertical_origin -> try_vertical_origin(),
+        VerticalOrigin,
+        "the vertical origins",
+    ),
     (
         windows_metrics -> try_windows_metrics(),
         opentype::truetype::tables::WindowsMetrics,
@@ -180,12 +252,57 @@ cache! {
 impl<T: crate::Read> Cache<T> {
     pub fn forward_mapping(&mut self) -> Result<&Rc<mapping::Forward>> {
         if self.forward_mapping.is_none() {
-            let value = mapping::Forward::new(&self.character_mapping()?.borrow())?;
+            let selection = self.selection;
+            let value = mapping::Forward::new(&self.character_mapping()?.borrow(), selection)?;
             self.forward_mapping = Some(Rc::new(value));
         }
         Ok(self.forward_mapping.as_ref().unwrap())
     }
 
+    #[inline]
+    pub fn position(&self, tag: Tag) -> Option<u64> {
+        self.record(tag).map(|record| record.offset as u64)
+    }
+
+    #[inline]
+    pub fn size(&self, tag: Tag) -> Option<u64> {
+        self.record(tag).map(|record| record.size as u64)
+    }
+
+    pub fn data(&self, tag: Tag) -> Result<Option<Vec<u8>>> {
+        let record = match self.record(tag) {
+            Some(record) => record,
+            _ => return Ok(None),
+        };
+        let mut tape = self.tape.borrow_mut();
+        let tape = tape.deref_mut();
+        tape.jump(record.offset as u64)?;
+        Ok(Some(tape.take_bytes(record.size as usize)?))
+    }
+
+    fn record(&self, tag: Tag) -> Option<&opentype::truetype::tables::offsets::Record> {
+        self.backend
+            .offsets
+            .records
+            .iter()
+            .find(|record| record.tag == tag)
+    }
+
+    // The hinter with the font and control-value programs run for a size in pixels per em.
+    pub fn hinter(&mut self, ppem: u16) -> Result<&Rc<Hinter>> {
+        if !self.hinters.contains_key(&ppem) {
+            let value = Hinter::new(self, ppem)?;
+            self.hinters.insert(ppem, Rc::new(value));
+        }
+        Ok(&self.hinters[&ppem])
+    }
+
+    pub fn select(&mut self, selection: Option<usize>) {
+        self.selection = selection;
+        self.forward_mapping = None;
+        self.reverse_mapping = None;
+    }
+
     pub fn reverse_mapping(&mut self) -> Result<&Rc<mapping::Reverse>> {
         if self.reverse_mapping.is_none() {
             let value = mapping::Reverse::new(&self.forward_mapping()?.clone());
@@ -196,9 +313,18 @@ impl<T: crate::Read> Cache<T> {
 
     pub fn metrics(&mut self) -> Result<&Rc<Metrics>> {
         if self.metrics.is_none() {
-            let value = Metrics::new(self.horizontal_metrics()?.clone());
+            let horizontal_metrics = self.horizontal_metrics()?.clone();
+            let value = Metrics::new(horizontal_metrics, metrics::vertical(self)?);
             self.metrics = Some(Rc::new(value));
         }
         Ok(self.metrics.as_ref().unwrap())
     }
+
+    pub fn pairs(&mut self) -> Result<&Rc<Pairs>> {
+        if self.pairs.is_none() {
+            let value = kerning::read(self)?;
+            self.pairs = Some(Rc::new(value));
+        }
+        Ok(self.pairs.as_ref().unwrap())
+    }
 }
@@ -2,7 +2,7 @@
 
 use std::io::Result;
 
-use opentype::truetype::tables::character_mapping::Encoding;
+use opentype::truetype::GlyphID;
 
 use crate::formats::opentype::cache::Cache;
 
@@ -13,24 +13,328 @@ pub enum Character {
     Range((char, char)),
 }
 
-/// Unicode characters.
-pub type Characters = Vec<Character>;
+/// A set of Unicode characters.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Characters {
+    values: Vec<Character>,
+}
+
+/// A character-to-glyph encoding.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Encoding {
+    /// The platform identifier.
+    pub platform_id: u16,
+    /// The platform-specific encoding identifier.
+    pub encoding_id: u16,
+    /// The format of the subtable.
+    pub format: u16,
+    /// The flag indicating that the encoding is the one in use.
+    pub selected: bool,
+}
+
+/// Character-to-glyph encodings.
+pub type Encodings = Vec<Encoding>;
+
+/// A Unicode variation sequence.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Sequence {
+    /// The base character.
+    pub character: char,
+    /// The variation selector.
+    pub selector: char,
+    /// The glyph identifier.
+    pub glyph_id: GlyphID,
+    /// The flag indicating that the glyph is the one of the base character.
+    pub default: bool,
+}
+
+/// Unicode variation sequences.
+pub type Sequences = Vec<Sequence>;
+
+impl Characters {
+    /// Check if a character is present.
+    pub fn contains(&self, character: char) -> bool {
+        let index = self
+            .values
+            .partition_point(|value| bounds(value).1 < character);
+        self.values
+            .get(index)
+            .is_some_and(|value| bounds(value).0 <= character)
+    }
+
+    /// Check if all characters of a text are present.
+    #[inline]
+    pub fn covers(&self, text: &str) -> bool {
+        text.chars().all(|character| self.contains(character))
+    }
+
+    /// Return the number of characters.
+    pub fn count(&self) -> usize {
+        self.values
+            .iter()
+            .map(|value| {
+                let (start, end) = bounds(value);
+                let (start, end) = (start as usize, end as usize);
+                let surrogates = (end.min(0xDFFF) + 1).saturating_sub(start.max(0xD800));
+                end - start + 1 - surrogates
+            })
+            .sum()
+    }
+
+    /// Return the characters present in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = self.ranges();
+        ranges.extend(other.ranges());
+        ranges.sort();
+        Self::from_ranges(ranges)
+    }
+
+    /// Return the characters present in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (this, other) = (self.ranges(), other.ranges());
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+        while i < this.len() && j < other.len() {
+            let start = this[i].0.max(other[j].0);
+            let end = this[i].1.min(other[j].1);
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if this[i].1 < other[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self::from_ranges(ranges)
+    }
+
+    /// Return the characters present in this set but not in the other.
+    pub fn difference(&self, other: &Self) -> Self {
+        let other = other.ranges();
+        let mut ranges = vec![];
+        let mut j = 0;
+        for (mut start, end) in self.ranges() {
+            while j < other.len() && other[j].1 < start {
+                j += 1;
+            }
+            let mut k = j;
+            while k < other.len() && other[k].0 <= end {
+                if other[k].0 > start {
+                    ranges.push((start, other[k].0 - 1));
+                }
+                start = start.max(other[k].1.saturating_add(1));
+                k += 1;
+            }
+            if start <= end {
+                ranges.push((start, end));
+            }
+        }
+        Self::from_ranges(ranges)
+    }
+
+    /// Iterate over the characters.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.values.iter().flat_map(|value| {
+            let (start, end) = bounds(value);
+            start..=end
+        })
+    }
+
+    fn from_ranges(mut ranges: Vec<(u32, u32)>) -> Self {
+        let mut values: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges.drain(..) {
+            // Malformed encodings produce inverted ranges and ones with surrogates or beyond Unicode.
+            let start = if (0xD800..=0xDFFF).contains(&start) {
+                0xE000
+            } else {
+                start
+            };
+            let end = if (0xD800..=0xDFFF).contains(&end) {
+                0xD7FF
+            } else {
+                end.min(0x10FFFF)
+            };
+            if start > end {
+                continue;
+            }
+            match values.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => values.push((start, end)),
+            }
+        }
+        Self {
+            values: compress(values),
+        }
+    }
+
+    fn ranges(&self) -> Vec<(u32, u32)> {
+        self.values
+            .iter()
+            .map(|value| {
+                let (start, end) = bounds(value);
+                (start as u32, end as u32)
+            })
+            .collect()
+    }
+}
+
+impl std::ops::Deref for Characters {
+    type Target = [Character];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl FromIterator<char> for Characters {
+    fn from_iter<T: IntoIterator<Item = char>>(iterator: T) -> Self {
+        let mut ranges = iterator
+            .into_iter()
+            .map(|character| (character as u32, character as u32))
+            .collect::<Vec<_>>();
+        ranges.sort();
+        Self::from_ranges(ranges)
+    }
+}
+
+impl IntoIterator for Characters {
+    type Item = Character;
+    type IntoIter = std::vec::IntoIter<Character>;
 
-pub(crate) fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Vec<Character>> {
-    for encoding in cache.character_mapping()?.borrow().encodings.iter() {
-        let ranges = match encoding {
-            Encoding::Format0(encoding) => encoding.characters(),
-            Encoding::Format4(encoding) => encoding.characters(),
-            Encoding::Format6(encoding) => encoding.characters(),
-            Encoding::Format12(encoding) => encoding.characters(),
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+pub(crate) fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Characters> {
+    let selection = cache.selection;
+    let character_mapping = cache.character_mapping()?.borrow();
+    if let Some(index) = character_mapping.choose(selection) {
+        let mut ranges = character_mapping.characters(index);
+        ranges.sort();
+        return Ok(Characters::from_ranges(ranges));
+    }
+    raise!("found no known character-to-glyph encoding")
+}
+
+pub(crate) fn characters_of<T: crate::Read>(
+    cache: &mut Cache<T>,
+    glyph_id: GlyphID,
+) -> Result<Vec<char>> {
+    Ok(cache.reverse_mapping()?.get_all(glyph_id).collect())
+}
+
+pub(crate) fn codes<T: crate::Read>(cache: &mut Cache<T>) -> Result<Vec<(u32, GlyphID)>> {
+    let selection = cache.selection;
+    let character_mapping = cache.character_mapping()?.borrow();
+    if let Some(index) = character_mapping.choose(selection) {
+        let mut values = character_mapping.encodings[index]
+            .codes()
+            .into_iter()
+            .collect::<Vec<_>>();
+        values.sort();
+        return Ok(values);
+    }
+    raise!("found no known character-to-glyph encoding")
+}
+
+pub(crate) fn encodings<T: crate::Read>(cache: &mut Cache<T>) -> Result<Vec<Encoding>> {
+    let selection = cache.selection;
+    let character_mapping = cache.character_mapping()?.borrow();
+    let chosen = character_mapping.choose(selection);
+    Ok(character_mapping
+        .records
+        .iter()
+        .zip(character_mapping.encodings.iter())
+        .enumerate()
+        .map(|(index, (record, encoding))| Encoding {
+            platform_id: record.platform_id,
+            encoding_id: record.encoding_id,
+            format: encoding.format(),
+            selected: chosen == Some(index),
+        })
+        .collect())
+}
+
+pub(crate) fn select<T: crate::Read>(
+    cache: &mut Cache<T>,
+    platform_id: u16,
+    encoding_id: u16,
+) -> Result<()> {
+    use crate::formats::opentype::encoding::Encoding;
+
+    let index = cache
+        .character_mapping()?
+        .borrow()
+        .records
+        .iter()
+        .position(|record| (record.platform_id, record.encoding_id) == (platform_id, encoding_id));
+    let index = match index {
+        Some(index) => index,
+        _ => raise!(
+            "found no character-to-glyph encoding with platform {} and encoding {}",
+            platform_id,
+            encoding_id,
+        ),
+    };
+    if let Encoding::Unknown(format) = cache.character_mapping()?.borrow().encodings[index] {
+        raise!(
+            "found an unsupported character-to-glyph encoding format ({})",
+            format
+        );
+    }
+    cache.select(Some(index));
+    Ok(())
+}
+
+pub(crate) fn sequences<T: crate::Read>(cache: &mut Cache<T>) -> Result<Vec<Sequence>> {
+    let mapping = cache.forward_mapping()?.clone();
+    let mut values = vec![];
+    for record in cache.variants()?.borrow().records.iter() {
+        let selector = match char::from_u32(record.character) {
+            Some(selector) => selector,
             _ => continue,
         };
-        return compress(ranges);
+        let mut sequences = vec![];
+        for (start, end) in record.default.iter() {
+            for character in (*start..=*end).filter_map(char::from_u32) {
+                if let Some(glyph_id) = mapping.get(character) {
+                    sequences.push(Sequence {
+                        character,
+                        selector,
+                        glyph_id,
+                        default: true,
+                    });
+                }
+            }
+        }
+        for (character, glyph_id) in record.non_default.iter() {
+            if let Some(character) = char::from_u32(*character) {
+                sequences.push(Sequence {
+                    character,
+                    selector,
+                    glyph_id: *glyph_id,
+                    default: false,
+                });
+            }
+        }
+        sequences.sort_by_key(|sequence| sequence.character);
+        values.extend(sequences);
+    }
+    Ok(values)
+}
+
+fn bounds(value: &Character) -> (char, char) {
+    match value {
+        Character::Scalar(value) => (*value, *value),
+        Character::Range((start, end)) => (*start, *end),
     }
-    raise!("found no known character-to-glyph encoding")
 }
 
-fn compress(ranges: Vec<(u32, u32)>) -> Result<Vec<Character>> {
+fn compress(ranges: Vec<(u32, u32)>) -> Vec<Character> {
     let mut values = Vec::with_capacity(ranges.len());
     for range in ranges {
         if let (Some(start), Some(end)) = (char::from_u32(range.0), char::from_u32(range.1)) {
@@ -51,12 +355,12 @@ fn compress(ranges: Vec<(u32, u32)>) -> Result<Vec<Character>> {
             }
         }
     }
-    Ok(values)
+    values
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Character;
+    use super::{Character, Characters};
 
     macro_rules! ok(($result:expr) => ($result.unwrap()));
 
@@ -140,7 +444,7 @@ mod tests {
             (64257, 64258),
         ];
         assert_eq!(
-            ok!(super::compress(ranges)),
+            super::compress(ranges),
             characters![
                 Scalar(0),
                 Scalar(13),
@@ -198,4 +502,59 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn operations() {
+        let one = "abcdefxyz".chars().collect::<Characters>();
+        let other = "cdeXYZz".chars().collect::<Characters>();
+        assert_eq!(one.len(), 2);
+        assert_eq!(one.count(), 9);
+        assert!(one.contains('d'));
+        assert!(!one.contains('g'));
+        assert!(one.covers("fax"));
+        assert!(!one.covers("fox"));
+        assert_eq!(
+            one.union(&other).chars().collect::<String>(),
+            "XYZabcdefxyz"
+        );
+        assert_eq!(one.intersection(&other).chars().collect::<String>(), "cdez");
+        assert_eq!(one.difference(&other).chars().collect::<String>(), "abfxy");
+        assert_eq!(other.difference(&one).chars().collect::<String>(), "XYZ");
+        assert_eq!(
+            *one.difference(&other),
+            characters![Range((97, 98)), Scalar(102), Range((120, 121)),],
+        );
+    }
+
+    #[test]
+    fn count() {
+        let characters = Characters {
+            values: characters![Range((0xD000, 0xE000)),],
+        };
+        assert_eq!(characters.count(), 0x1001 - 0x800);
+    }
+
+    #[test]
+    fn from_ranges() {
+        let ranges = vec![
+            (0x41, 0x5A),
+            (0x50, 0x20),
+            (0xD800, 0xDBFF),
+            (0xD900, 0xE001),
+            (0x10FFF0, 0xFFFFFFFF),
+            (0xFFFFFFF0, 0xFFFFFFFF),
+        ];
+        let characters = Characters::from_ranges(ranges);
+        assert_eq!(
+            *characters,
+            characters![
+                Range((0x41, 0x5A)),
+                Range((0xE000, 0xE001)),
+                Range((0x10FFF0, 0x10FFFF)),
+            ],
+        );
+        assert_eq!(characters.count(), 26 + 2 + 16);
+        assert!(characters.contains('\u{E001}'));
+        assert!(!characters.contains('\u{30}'));
+    }
 }
@@ -0,0 +1,663 @@
+use std::collections::HashMap;
+use std::io::Result;
+
+use opentype::truetype::{GlyphID, Tag};
+
+use crate::color::{ColorLine, ColorStop, CompositeMode};
+use crate::glyph::Glyph;
+use crate::{Number, Offset};
+
+// The maximum depth of a paint graph.
+const DEPTH: usize = 64;
+
+// The maximum number of paints in an expanded paint graph.
+const SIZE: usize = 1 << 16;
+
+#[derive(Clone, Debug, Default)]
+pub struct ColorGlyphs {
+    pub base_glyphs: Vec<BaseGlyph>,
+    pub layers: Vec<Layer>,
+    pub base_paints: Vec<(GlyphID, usize)>,
+    pub layer_paints: Vec<usize>,
+    pub clips: Vec<Clip>,
+    pub paints: Vec<Paint>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Clip {
+    pub start: GlyphID,
+    pub end: GlyphID,
+    pub bounding_box: (i16, i16, i16, i16),
+}
+
+#[derive(Clone, Debug)]
+pub enum Paint {
+    Layers {
+        first: usize,
+        count: usize,
+    },
+    Solid {
+        palette_index: u16,
+        alpha: Number,
+    },
+    LinearGradient {
+        color_line: ColorLine,
+        points: (Offset, Offset, Offset),
+    },
+    RadialGradient {
+        color_line: ColorLine,
+        start: (Offset, Number),
+        end: (Offset, Number),
+    },
+    SweepGradient {
+        color_line: ColorLine,
+        center: Offset,
+        angles: (Number, Number),
+    },
+    Glyph {
+        glyph_id: GlyphID,
+        paint: usize,
+    },
+    ColorGlyph {
+        glyph_id: GlyphID,
+    },
+    Transform {
+        matrix: [Number; 6],
+        paint: usize,
+    },
+    Composite {
+        source: usize,
+        mode: CompositeMode,
+        backdrop: usize,
+    },
+}
+
+table! {
+    #[derive(Copy)]
+    pub Header {
+        version           (u16), // version
+        base_glyph_count  (u16), // numBaseGlyphRecords
+        base_glyph_offset (u32), // baseGlyphRecordsOffset
+        layer_offset      (u32), // layerRecordsOffset
+        layer_count       (u16), // numLayerRecords
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub Extension {
+        base_paint_offset      (u32), // baseGlyphListOffset
+        layer_paint_offset     (u32), // layerListOffset
+        clip_offset            (u32), // clipListOffset
+        variation_index_offset (u32), // varIndexMapOffset
+        variation_store_offset (u32), // itemVariationStoreOffset
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub BaseGlyph {
+        glyph_id    (GlyphID), // glyphID
+        first_layer (u16), // firstLayerIndex
+        layer_count (u16), // numLayers
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub Layer {
+        glyph_id      (GlyphID), // glyphID
+        palette_index (u16), // paletteIndex
+    }
+}
+
+impl ColorGlyphs {
+    pub fn get(&self, glyph_id: GlyphID) -> Option<&[Layer]> {
+        let index = self
+            .base_glyphs
+            .binary_search_by_key(&glyph_id, |record| record.glyph_id)
+            .ok()?;
+        let record = &self.base_glyphs[index];
+        let start = record.first_layer as usize;
+        self.layers
+            .get(start..(start + record.layer_count as usize))
+    }
+
+    pub fn clip(&self, glyph_id: GlyphID) -> Option<(i16, i16, i16, i16)> {
+        self.clips
+            .iter()
+            .find(|clip| clip.start <= glyph_id && glyph_id <= clip.end)
+            .map(|clip| clip.bounding_box)
+    }
+
+    pub fn paint<F>(&self, glyph_id: GlyphID, mut draw: F) -> Result<Option<crate::color::Paint>>
+    where
+        F: FnMut(GlyphID) -> Result<Glyph>,
+    {
+        let index = match self.find(glyph_id) {
+            Some(index) => index,
+            _ => return Ok(None),
+        };
+        let mut stack = Vec::new();
+        let mut size = 0;
+        Ok(Some(self.expand(index, &mut draw, &mut stack, &mut size)?))
+    }
+
+    fn expand<F>(
+        &self,
+        index: usize,
+        draw: &mut F,
+        stack: &mut Vec<usize>,
+        size: &mut usize,
+    ) -> Result<crate::color::Paint>
+    where
+        F: FnMut(GlyphID) -> Result<Glyph>,
+    {
+        use crate::color::Paint as Value;
+
+        if stack.contains(&index) {
+            raise!("found a cyclic paint graph");
+        }
+        if stack.len() >= DEPTH {
+            raise!("found a paint graph that is too deep");
+        }
+        *size += 1;
+        if *size > SIZE {
+            raise!("found a paint graph that is too large");
+        }
+        stack.push(index);
+        let value = match &self.paints[index] {
+            Paint::Layers { first, count } => {
+                let mut values = Vec::with_capacity(*count);
+                for position in *first..(*first + *count) {
+                    let index = match self.layer_paints.get(position) {
+                        Some(index) => *index,
+                        _ => raise!("found a malformed paint layer ({position})"),
+                    };
+                    values.push(self.expand(index, draw, stack, size)?);
+                }
+                Value::Layers(values)
+            }
+            &Paint::Solid {
+                palette_index,
+                alpha,
+            } => Value::Solid {
+                palette_index: palette(palette_index),
+                alpha,
+            },
+            Paint::LinearGradient { color_line, points } => Value::LinearGradient {
+                color_line: color_line.clone(),
+                points: *points,
+            },
+            Paint::RadialGradient {
+                color_line,
+                start,
+                end,
+            } => Value::RadialGradient {
+                color_line: color_line.clone(),
+                start: *start,
+                end: *end,
+            },
+            Paint::SweepGradient {
+                color_line,
+                center,
+                angles,
+            } => Value::SweepGradient {
+                color_line: color_line.clone(),
+                center: *center,
+                angles: *angles,
+            },
+            &Paint::Glyph { glyph_id, paint } => Value::Glyph {
+                glyph_id,
+                glyph: draw(glyph_id)?,
+                paint: Box::new(self.expand(paint, draw, stack, size)?),
+            },
+            &Paint::ColorGlyph { glyph_id } => match self.find(glyph_id) {
+                Some(index) => self.expand(index, draw, stack, size)?,
+                _ => raise!("found a missing color glyph ({glyph_id})"),
+            },
+            &Paint::Transform { matrix, paint } => Value::Transform {
+                matrix,
+                paint: Box::new(self.expand(paint, draw, stack, size)?),
+            },
+            &Paint::Composite {
+                source,
+                mode,
+                backdrop,
+            } => Value::Composite {
+                source: Box::new(self.expand(source, draw, stack, size)?),
+                mode,
+                backdrop: Box::new(self.expand(backdrop, draw, stack, size)?),
+            },
+        };
+        stack.pop();
+        Ok(value)
+    }
+
+    fn find(&self, glyph_id: GlyphID) -> Option<usize> {
+        let index = self
+            .base_paints
+            .binary_search_by_key(&glyph_id, |(glyph_id, _)| *glyph_id)
+            .ok()?;
+        Some(self.base_paints[index].1)
+    }
+}
+
+impl opentype::Table for ColorGlyphs {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"COLR")
+    }
+}
+
+impl opentype::value::Read for ColorGlyphs {
+    fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let header = tape.take::<Header>()?;
+        let mut table = ColorGlyphs::default();
+        if header.base_glyph_count > 0 {
+            tape.jump(position + header.base_glyph_offset as u64)?;
+            table.base_glyphs = tape.take_given(header.base_glyph_count as usize)?;
+        }
+        if header.layer_count > 0 {
+            tape.jump(position + header.layer_offset as u64)?;
+            table.layers = tape.take_given(header.layer_count as usize)?;
+        }
+        if header.version == 0 {
+            return Ok(table);
+        }
+        tape.jump(position + 14)?;
+        let extension = tape.take::<Extension>()?;
+        let mut reader = Reader {
+            paints: Vec::new(),
+            positions: HashMap::new(),
+        };
+        if extension.base_paint_offset > 0 {
+            let offset = position + extension.base_paint_offset as u64;
+            tape.jump(offset)?;
+            let count = tape.take::<u32>()? as usize;
+            let mut records = Vec::with_capacity(count);
+            for _ in 0..count {
+                records.push((tape.take::<GlyphID>()?, tape.take::<u32>()?));
+            }
+            for (glyph_id, paint_offset) in records {
+                let index = reader.read(tape, offset + paint_offset as u64, 0)?;
+                table.base_paints.push((glyph_id, index));
+            }
+        }
+        if extension.layer_paint_offset > 0 {
+            let offset = position + extension.layer_paint_offset as u64;
+            tape.jump(offset)?;
+            let count = tape.take::<u32>()? as usize;
+            let offsets: Vec<u32> = tape.take_given(count)?;
+            for paint_offset in offsets {
+                let index = reader.read(tape, offset + paint_offset as u64, 0)?;
+                table.layer_paints.push(index);
+            }
+        }
+        if extension.clip_offset > 0 {
+            let offset = position + extension.clip_offset as u64;
+            tape.jump(offset)?;
+            match tape.take::<u8>()? {
+                1 => {}
+                value => raise!("found an unknown format of the clip list ({value})"),
+            }
+            let count = tape.take::<u32>()? as usize;
+            let mut records = Vec::with_capacity(count);
+            for _ in 0..count {
+                let start = tape.take::<GlyphID>()?;
+                let end = tape.take::<GlyphID>()?;
+                records.push((start, end, take_offset(tape)?));
+            }
+            for (start, end, box_offset) in records {
+                tape.jump(offset + box_offset as u64)?;
+                match tape.take::<u8>()? {
+                    1 | 2 => {}
+                    value => raise!("found an unknown format of the clip box ({value})"),
+                }
+                let bounding_box = (tape.take()?, tape.take()?, tape.take()?, tape.take()?);
+                table.clips.push(Clip {
+                    start,
+                    end,
+                    bounding_box,
+                });
+            }
+        }
+        table.paints = reader.paints;
+        Ok(table)
+    }
+}
+
+struct Reader {
+    paints: Vec<Paint>,
+    positions: HashMap<u64, usize>,
+}
+
+impl Reader {
+    fn read<T: opentype::tape::Read>(
+        &mut self,
+        tape: &mut T,
+        position: u64,
+        depth: usize,
+    ) -> Result<usize> {
+        if let Some(index) = self.positions.get(&position) {
+            return Ok(*index);
+        }
+        if depth >= DEPTH {
+            raise!("found a paint graph that is too deep");
+        }
+        macro_rules! child(
+            ($offset:expr) => (
+                match $offset {
+                    0 => raise!("found a malformed paint"),
+                    offset => self.read(tape, position + offset as u64, depth + 1)?,
+                }
+            );
+        );
+        tape.jump(position)?;
+        let format = tape.take::<u8>()?;
+        let paint = match format {
+            1 => {
+                let count = tape.take::<u8>()? as usize;
+                let first = tape.take::<u32>()? as usize;
+                Paint::Layers { first, count }
+            }
+            2 | 3 => Paint::Solid {
+                palette_index: tape.take()?,
+                alpha: take_f2dot14(tape)?,
+            },
+            4 | 5 => {
+                let offset = take_offset(tape)?;
+                let points = (take_point(tape)?, take_point(tape)?, take_point(tape)?);
+                Paint::LinearGradient {
+                    color_line: read_color_line(tape, position + offset as u64, format == 5)?,
+                    points,
+                }
+            }
+            6 | 7 => {
+                let offset = take_offset(tape)?;
+                let start = (take_point(tape)?, Number::from(tape.take::<u16>()?));
+                let end = (take_point(tape)?, Number::from(tape.take::<u16>()?));
+                Paint::RadialGradient {
+                    color_line: read_color_line(tape, position + offset as u64, format == 7)?,
+                    start,
+                    end,
+                }
+            }
+            8 | 9 => {
+                let offset = take_offset(tape)?;
+                let center = take_point(tape)?;
+                let angles = (180.0 * take_f2dot14(tape)?, 180.0 * take_f2dot14(tape)?);
+                Paint::SweepGradient {
+                    color_line: read_color_line(tape, position + offset as u64, format == 9)?,
+                    center,
+                    angles,
+                }
+            }
+            10 => {
+                let offset = take_offset(tape)?;
+                let glyph_id = tape.take()?;
+                Paint::Glyph {
+                    glyph_id,
+                    paint: child!(offset),
+                }
+            }
+            11 => Paint::ColorGlyph {
+                glyph_id: tape.take()?,
+            },
+            12..=31 => {
+                let offset = take_offset(tape)?;
+                let matrix = read_matrix(tape, position, format)?;
+                Paint::Transform {
+                    matrix,
+                    paint: child!(offset),
+                }
+            }
+            32 => {
+                let source = take_offset(tape)?;
+                let mode = tape.take::<u8>()?.try_into()?;
+                let backdrop = take_offset(tape)?;
+                Paint::Composite {
+                    source: child!(source),
+                    mode,
+                    backdrop: child!(backdrop),
+                }
+            }
+            value => raise!("found an unknown format of the paint ({value})"),
+        };
+        let index = self.paints.len();
+        self.paints.push(paint);
+        self.positions.insert(position, index);
+        Ok(index)
+    }
+}
+
+fn read_color_line<T: opentype::tape::Read>(
+    tape: &mut T,
+    position: u64,
+    variable: bool,
+) -> Result<ColorLine> {
+    tape.jump(position)?;
+    let extend = tape.take::<u8>()?.into();
+    let count = tape.take::<u16>()? as usize;
+    let mut stops = Vec::with_capacity(count);
+    for _ in 0..count {
+        stops.push(ColorStop {
+            offset: take_f2dot14(tape)?,
+            palette_index: palette(tape.take()?),
+            alpha: take_f2dot14(tape)?,
+        });
+        if variable {
+            tape.take::<u32>()?;
+        }
+    }
+    Ok(ColorLine { extend, stops })
+}
+
+fn read_matrix<T: opentype::tape::Read>(
+    tape: &mut T,
+    position: u64,
+    format: u8,
+) -> Result<[Number; 6]> {
+    let matrix = match format {
+        12 | 13 => {
+            let offset = take_offset(tape)?;
+            tape.jump(position + offset as u64)?;
+            let mut matrix = [0.0; 6];
+            for value in matrix.iter_mut() {
+                *value = tape.take::<i32>()? as Number / 65536.0;
+            }
+            return Ok(matrix);
+        }
+        14 | 15 => {
+            let Offset(x, y) = take_point(tape)?;
+            return Ok([1.0, 0.0, 0.0, 1.0, x, y]);
+        }
+        16..=19 => {
+            let (x, y) = (take_f2dot14(tape)?, take_f2dot14(tape)?);
+            [x, 0.0, 0.0, y, 0.0, 0.0]
+        }
+        20..=23 => {
+            let scale = take_f2dot14(tape)?;
+            [scale, 0.0, 0.0, scale, 0.0, 0.0]
+        }
+        24..=27 => {
+            let (sin, cos) = (std::f32::consts::PI * take_f2dot14(tape)?).sin_cos();
+            [cos, sin, -sin, cos, 0.0, 0.0]
+        }
+        _ => {
+            let x = (std::f32::consts::PI * take_f2dot14(tape)?).tan();
+            let y = (std::f32::consts::PI * take_f2dot14(tape)?).tan();
+            [1.0, y, -x, 1.0, 0.0, 0.0]
+        }
+    };
+    if format % 4 < 2 {
+        return Ok(matrix);
+    }
+    let Offset(x, y) = take_point(tape)?;
+    let [xx, yx, xy, yy, _, _] = matrix;
+    Ok([xx, yx, xy, yy, x - xx * x - xy * y, y - yx * x - yy * y])
+}
+
+#[inline]
+fn palette(index: u16) -> Option<u16> {
+    match index {
+        0xFFFF => None,
+        value => Some(value),
+    }
+}
+
+#[inline]
+fn take_f2dot14<T: opentype::tape::Read>(tape: &mut T) -> Result<Number> {
+    Ok(tape.take::<i16>()? as Number / 16384.0)
+}
+
+#[inline]
+fn take_offset<T: opentype::tape::Read>(tape: &mut T) -> Result<u32> {
+    let [a, b, c] = [tape.take::<u8>()?, tape.take::<u8>()?, tape.take::<u8>()?];
+    Ok(u32::from_be_bytes([0, a, b, c]))
+}
+
+#[inline]
+fn take_point<T: opentype::tape::Read>(tape: &mut T) -> Result<Offset> {
+    Ok(Offset(
+        tape.take::<i16>()?.into(),
+        tape.take::<i16>()?.into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::ColorGlyphs;
+    use crate::color::{Extend, Paint};
+    use crate::glyph::Glyph;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn read() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x02, // version, numBaseGlyphRecords
+            0x00, 0x00, 0x00, 0x0E, // baseGlyphRecordsOffset
+            0x00, 0x00, 0x00, 0x1A, // layerRecordsOffset
+            0x00, 0x03, // numLayerRecords
+            0x00, 0x05, 0x00, 0x00, 0x00, 0x02, // glyphID, firstLayerIndex, numLayers
+            0x00, 0x07, 0x00, 0x02, 0x00, 0x01, // glyphID, firstLayerIndex, numLayers
+            0x00, 0x0A, 0x00, 0x00, // glyphID, paletteIndex
+            0x00, 0x0B, 0xFF, 0xFF, // glyphID, paletteIndex
+            0x00, 0x0C, 0x00, 0x01, // glyphID, paletteIndex
+        ];
+        let table = ok!(ColorGlyphs::read(&mut Cursor::new(data)));
+        let layers = ok!(table.get(5))
+            .iter()
+            .map(|layer| (layer.glyph_id, layer.palette_index))
+            .collect::<Vec<_>>();
+        assert_eq!(layers, [(10, 0), (11, 0xFFFF)]);
+        assert_eq!(ok!(table.get(7)).len(), 1);
+        assert!(table.get(6).is_none());
+    }
+
+    #[test]
+    fn paint() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x01, 0x00, 0x00, // version, numBaseGlyphRecords
+            0x00, 0x00, 0x00, 0x00, // baseGlyphRecordsOffset
+            0x00, 0x00, 0x00, 0x00, // layerRecordsOffset
+            0x00, 0x00, // numLayerRecords
+            0x00, 0x00, 0x00, 0x22, // baseGlyphListOffset
+            0x00, 0x00, 0x00, 0x3B, // layerListOffset
+            0x00, 0x00, 0x00, 0x81, // clipListOffset
+            0x00, 0x00, 0x00, 0x00, // varIndexMapOffset
+            0x00, 0x00, 0x00, 0x00, // itemVariationStoreOffset
+            // BaseGlyphList
+            0x00, 0x00, 0x00, 0x02, // numBaseGlyphPaintRecords
+            0x00, 0x05, 0x00, 0x00, 0x00, 0x10, // glyphID, paintOffset
+            0x00, 0x06, 0x00, 0x00, 0x00, 0x16, // glyphID, paintOffset
+            0x01, 0x02, 0x00, 0x00, 0x00, 0x00, // PaintColrLayers
+            0x0B, 0x00, 0x06, // PaintColrGlyph
+            // LayerList
+            0x00, 0x00, 0x00, 0x02, // numLayers
+            0x00, 0x00, 0x00, 0x0C, // paintOffsets[0]
+            0x00, 0x00, 0x00, 0x17, // paintOffsets[1]
+            0x0A, 0x00, 0x00, 0x06, 0x00, 0x01, // PaintGlyph
+            0x02, 0x00, 0x02, 0x40, 0x00, // PaintSolid
+            0x0A, 0x00, 0x00, 0x06, 0x00, 0x02, // PaintGlyph
+            0x1A, 0x00, 0x00, 0x0A, 0x20, 0x00, // PaintRotateAroundCenter
+            0x00, 0x64, 0x00, 0x00, // centerX, centerY
+            0x04, 0x00, 0x00, 0x10, // PaintLinearGradient
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x64, // x0, y0, x1
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x64, // y1, x2, y2
+            0x01, 0x00, 0x02, // ColorLine
+            0x00, 0x00, 0x00, 0x00, 0x40, 0x00, // ColorStop
+            0x40, 0x00, 0xFF, 0xFF, 0x20, 0x00, // ColorStop
+            // ClipList
+            0x01, 0x00, 0x00, 0x00, 0x01, // format, numClips
+            0x00, 0x05, 0x00, 0x05, 0x00, 0x00, 0x0C, // startGlyphID, endGlyphID, clipBoxOffset
+            0x01, 0x00, 0x00, 0xFF, 0x9C, // format, xMin, yMin
+            0x00, 0xC8, 0x03, 0x20, // xMax, yMax
+        ];
+        let table = ok!(ColorGlyphs::read(&mut Cursor::new(data)));
+        assert_eq!(table.clip(5), Some((0, -100, 200, 800)));
+        assert_eq!(table.clip(6), None);
+        assert!(ok!(table.paint(7, |_| Ok(Glyph::default()))).is_none());
+        assert!(table.paint(6, |_| Ok(Glyph::default())).is_err());
+
+        let mut glyph_ids = vec![];
+        let paint = ok!(ok!(table.paint(5, |glyph_id| {
+            glyph_ids.push(glyph_id);
+            Ok(Glyph::default())
+        })));
+        assert_eq!(glyph_ids, [1, 2]);
+        let layers = match paint {
+            Paint::Layers(layers) => layers,
+            _ => unreachable!(),
+        };
+        match &layers[0] {
+            Paint::Glyph {
+                glyph_id, paint, ..
+            } => {
+                assert_eq!(*glyph_id, 1);
+                match **paint {
+                    Paint::Solid {
+                        palette_index,
+                        alpha,
+                    } => assert_eq!((palette_index, alpha), (Some(2), 1.0)),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+        let (matrix, paint) = match &layers[1] {
+            Paint::Glyph { paint, .. } => match &**paint {
+                Paint::Transform { matrix, paint } => (matrix, paint),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        let expected = [0.0, 1.0, -1.0, 0.0, 100.0, -100.0];
+        assert!(matrix
+            .iter()
+            .zip(expected)
+            .all(|(value, expected)| (value - expected).abs() < 1e-4));
+        match &**paint {
+            Paint::LinearGradient { color_line, points } => {
+                assert_eq!(color_line.extend, Extend::Repeat);
+                let stops = color_line
+                    .stops
+                    .iter()
+                    .map(|stop| (stop.offset, stop.palette_index, stop.alpha))
+                    .collect::<Vec<_>>();
+                assert_eq!(stops, [(0.0, Some(0), 1.0), (1.0, None, 0.5)]);
+                assert_eq!(points.1 .0, 100.0);
+                assert_eq!(points.2 .1, 100.0);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
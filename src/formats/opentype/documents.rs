@@ -0,0 +1,136 @@
+use std::io::Result;
+use std::ops::DerefMut;
+
+use opentype::truetype::{GlyphID, Tag};
+
+use crate::formats::opentype::cache::Cache;
+use crate::formats::opentype::gzip;
+use crate::glyph::Document;
+
+#[derive(Clone, Debug, Default)]
+pub struct Documents {
+    pub records: Vec<Record>,
+}
+
+table! {
+    #[derive(Copy)]
+    pub Header {
+        version     (u16) = { 0 }, // version
+        list_offset (u32), // svgDocumentListOffset
+        reserved    (u32), // reserved
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub Record { // SVGDocumentRecord
+        start_glyph_id (GlyphID), // startGlyphID
+        end_glyph_id   (GlyphID), // endGlyphID
+        offset         (u32), // svgDocOffset
+        size           (u32), // svgDocLength
+    }
+}
+
+impl Documents {
+    pub fn get(&self, glyph_id: GlyphID) -> Option<&Record> {
+        let index = self
+            .records
+            .partition_point(|record| record.end_glyph_id < glyph_id);
+        self.records
+            .get(index)
+            .filter(|record| record.start_glyph_id <= glyph_id)
+    }
+}
+
+impl opentype::Table for Documents {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"SVG ")
+    }
+}
+
+impl opentype::value::Read for Documents {
+    fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let header = tape.take::<Header>()?;
+        let position = position + header.list_offset as u64;
+        tape.jump(position)?;
+        let count = tape.take::<u16>()?;
+        let mut records: Vec<Record> = tape.take_given(count as usize)?;
+        for record in records.iter_mut() {
+            if record.end_glyph_id < record.start_glyph_id {
+                raise!("found a malformed SVG document record");
+            }
+            record.offset = match record.offset.checked_add(header.list_offset) {
+                Some(value) => value,
+                _ => raise!("found a malformed SVG document record"),
+            };
+        }
+        Ok(Self { records })
+    }
+}
+
+pub fn read<T: crate::Read>(cache: &mut Cache<T>, glyph_id: GlyphID) -> Result<Option<Document>> {
+    let record = match cache.try_documents()? {
+        Some(table) => match table.borrow().get(glyph_id) {
+            Some(record) => *record,
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let (position, size) = match (cache.position(Tag(*b"SVG ")), cache.size(Tag(*b"SVG "))) {
+        (Some(position), Some(size)) => (position, size),
+        _ => raise!("cannot find the SVG table"),
+    };
+    if record.offset as u64 + record.size as u64 > size {
+        raise!("found an SVG document beyond the SVG table");
+    }
+    let data = {
+        let mut tape = cache.tape.borrow_mut();
+        let tape = tape.deref_mut();
+        tape.jump(position + record.offset as u64)?;
+        tape.take_bytes(record.size as usize)?
+    };
+    let data = if gzip::is(&data) {
+        gzip::decompress(&data)?
+    } else {
+        data
+    };
+    match String::from_utf8(data) {
+        Ok(content) => Ok(Some(Document {
+            glyph_ids: (record.start_glyph_id, record.end_glyph_id),
+            content,
+        })),
+        _ => raise!("found a malformed SVG document"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::Documents;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn read() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, // version, svgDocumentListOffset
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x02, // numEntries
+            0x00, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x1A, 0x00, 0x00, 0x00, 0x10, // record
+            0x00, 0x07, 0x00, 0x07, 0x00, 0x00, 0x00, 0x1A, 0x00, 0x00, 0x00, 0x10, // record
+        ];
+        let table = ok!(Documents::read(&mut Cursor::new(data)));
+        assert!(table.get(1).is_none());
+        assert_eq!(ok!(table.get(2)).offset, 0x24);
+        assert_eq!(ok!(table.get(4)).end_glyph_id, 4);
+        assert!(table.get(5).is_none());
+        assert_eq!(ok!(table.get(7)).start_glyph_id, 7);
+        assert!(table.get(8).is_none());
+    }
+}
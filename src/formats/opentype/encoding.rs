@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::io::Result;
+
+use opentype::truetype::tables::character_mapping::{
+    Encoding0, Encoding12, Encoding4, Encoding6, Header, Record,
+};
+use opentype::truetype::{GlyphID, Tag};
+
+// The last Unicode code point.
+const LIMIT: u32 = 0x10FFFF;
+
+pub struct CharacterMapping {
+    pub records: Vec<Record>,
+    pub encodings: Vec<Encoding>,
+}
+
+pub enum Encoding {
+    Format0(Encoding0),
+    Format2(Encoding2),
+    Format4(Encoding4),
+    Format6(Encoding6),
+    Format10(Encoding10),
+    Format12(Encoding12),
+    Format13(Encoding13),
+    Unknown(u16),
+}
+
+table! {
+    pub Encoding2 {
+        format   (u16) = { 2 }, // format
+        size     (u16), // length
+        language (u16), // language
+
+        keys (Vec<u16>) |_, tape| { // subHeaderKeys
+            tape.take_given(256)
+        },
+
+        headers (Vec<SubHeader>) |this, tape| { // subHeaders
+            let count = this.keys.iter().map(|key| key / 8).max().unwrap_or(0);
+            tape.take_given(count as usize + 1)
+        },
+
+        glyph_ids (Vec<GlyphID>) |this, tape| { // glyphIdArray
+            let size = 6 + 2 * 256 + 8 * this.headers.len();
+            tape.take_given((this.size as usize).saturating_sub(size) / 2)
+        },
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub SubHeader {
+        first_code      (u16), // firstCode
+        entry_count     (u16), // entryCount
+        id_delta        (i16), // idDelta
+        id_range_offset (u16), // idRangeOffset
+    }
+}
+
+table! {
+    pub Encoding10 {
+        format      (u16) = { 10 }, // format
+        reserved    (u16) = { 0 }, // reserved
+        size        (u32), // length
+        language    (u32), // language
+        start_code  (u32), // startCharCode
+        entry_count (u32), // numChars
+
+        glyph_ids (Vec<GlyphID>) |this, tape| { // glyphIdArray
+            tape.take_given(this.entry_count as usize)
+        },
+    }
+}
+
+table! {
+    pub Encoding13 {
+        format      (u16) = { 13 }, // format
+        reserved    (u16) = { 0 }, // reserved
+        size        (u32), // length
+        language    (u32), // language
+        group_count (u32), // numGroups
+
+        groups (Vec<Group>) |this, tape| { // groups
+            tape.take_given(this.group_count as usize)
+        },
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub Group {
+        start_code (u32), // startCharCode
+        end_code   (u32), // endCharCode
+        glyph_id   (u32), // glyphID
+    }
+}
+
+impl CharacterMapping {
+    pub fn choose(&self, selection: Option<usize>) -> Option<usize> {
+        if selection.is_some() {
+            return selection;
+        }
+        (0..self.records.len())
+            .filter(|index| !matches!(self.encodings[*index], Encoding::Unknown(_)))
+            .min_by_key(|index| rank(&self.records[*index]))
+    }
+
+    pub fn characters(&self, index: usize) -> Vec<(u32, u32)> {
+        let mut ranges = self.encodings[index].characters();
+        if symbolic(&self.records[index]) {
+            let symbols = ranges
+                .iter()
+                .map(|(start, end)| ((*start).max(0xF000), (*end).min(0xF0FF)))
+                .filter(|(start, end)| start <= end)
+                .map(|(start, end)| (start - 0xF000, end - 0xF000))
+                .collect::<Vec<_>>();
+            ranges.extend(symbols);
+        }
+        ranges
+    }
+
+    pub fn mapping(&self, index: usize) -> HashMap<u32, GlyphID> {
+        let mut values = self.encodings[index].mapping();
+        if symbolic(&self.records[index]) {
+            let symbols = values
+                .iter()
+                .filter(|(character, _)| (0xF000..=0xF0FF).contains(*character))
+                .map(|(character, glyph_id)| (character - 0xF000, *glyph_id))
+                .collect::<Vec<_>>();
+            for (character, glyph_id) in symbols {
+                values.entry(character).or_insert(glyph_id);
+            }
+        }
+        values
+    }
+
+    pub fn ranges(&self, index: usize) -> Vec<(u32, u32, GlyphID)> {
+        let mut ranges = self.encodings[index].ranges();
+        if symbolic(&self.records[index]) {
+            let symbols = ranges
+                .iter()
+                .map(|(start, end, glyph_id)| ((*start).max(0xF000), (*end).min(0xF0FF), *glyph_id))
+                .filter(|(start, end, _)| start <= end)
+                .map(|(start, end, glyph_id)| (start - 0xF000, end - 0xF000, glyph_id))
+                .collect::<Vec<_>>();
+            ranges.extend(symbols);
+            ranges = disjoin(ranges);
+        }
+        ranges
+    }
+}
+
+impl Encoding {
+    pub fn format(&self) -> u16 {
+        match self {
+            Encoding::Format0(_) => 0,
+            Encoding::Format2(_) => 2,
+            Encoding::Format4(_) => 4,
+            Encoding::Format6(_) => 6,
+            Encoding::Format10(_) => 10,
+            Encoding::Format12(_) => 12,
+            Encoding::Format13(_) => 13,
+            Encoding::Unknown(format) => *format,
+        }
+    }
+
+    pub fn characters(&self) -> Vec<(u32, u32)> {
+        match self {
+            Encoding::Format0(encoding) => encoding.characters(),
+            Encoding::Format4(encoding) => encoding.characters(),
+            Encoding::Format6(encoding) => encoding.characters(),
+            Encoding::Format2(encoding) => {
+                let mut codes = encoding
+                    .mapping()
+                    .into_keys()
+                    .filter(|code| *code < 0x100)
+                    .collect::<Vec<_>>();
+                codes.sort();
+                let mut ranges: Vec<(u32, u32)> = vec![];
+                for code in codes {
+                    match ranges.last_mut() {
+                        Some((_, end)) if *end + 1 == code => *end = code,
+                        _ => ranges.push((code, code)),
+                    }
+                }
+                ranges
+            }
+            Encoding::Format10(encoding) => {
+                let end = encoding
+                    .start_code
+                    .saturating_add(encoding.entry_count)
+                    .saturating_sub(1)
+                    .min(LIMIT);
+                if encoding.entry_count > 0 && encoding.start_code <= end {
+                    vec![(encoding.start_code, end)]
+                } else {
+                    vec![]
+                }
+            }
+            Encoding::Format12(encoding) => encoding
+                .groups
+                .iter()
+                .map(|group| (group.start_code, group.end_code))
+                .collect(),
+            Encoding::Format13(_) => self
+                .ranges()
+                .into_iter()
+                .map(|(start, end, _)| (start, end))
+                .collect(),
+            _ => Default::default(),
+        }
+    }
+
+    // Multi-byte codes of format 2 are not Unicode scalars and are available only via `codes`, and
+    // ranges of format 13 are available only via `ranges`.
+    pub fn mapping(&self) -> HashMap<u32, GlyphID> {
+        match self {
+            Encoding::Format0(encoding) => encoding.mapping(),
+            Encoding::Format2(encoding) => encoding
+                .mapping()
+                .into_iter()
+                .filter(|(code, _)| *code < 0x100)
+                .collect(),
+            Encoding::Format4(encoding) => encoding.mapping(),
+            Encoding::Format6(encoding) => encoding.mapping(),
+            Encoding::Format10(encoding) => (encoding.start_code..=LIMIT)
+                .zip(encoding.glyph_ids.iter().cloned())
+                .collect(),
+            Encoding::Format12(encoding) => encoding.mapping(),
+            _ => Default::default(),
+        }
+    }
+
+    // Return disjoint ranges of characters mapped to a single glyph sorted by the first character.
+    pub fn ranges(&self) -> Vec<(u32, u32, GlyphID)> {
+        match self {
+            Encoding::Format13(encoding) => disjoin(
+                encoding
+                    .groups
+                    .iter()
+                    .map(|group| {
+                        (
+                            group.start_code,
+                            group.end_code.min(LIMIT),
+                            group.glyph_id as _,
+                        )
+                    })
+                    .filter(|(start, end, _)| start <= end)
+                    .collect(),
+            ),
+            _ => Default::default(),
+        }
+    }
+
+    pub fn codes(&self) -> HashMap<u32, GlyphID> {
+        match self {
+            Encoding::Format2(encoding) => encoding.mapping(),
+            _ => {
+                let mut values = self.mapping();
+                for (start, end, glyph_id) in self.ranges() {
+                    values.extend((start..=end).map(|code| (code, glyph_id)));
+                }
+                values
+            }
+        }
+    }
+}
+
+impl Encoding2 {
+    pub fn mapping(&self) -> HashMap<u32, GlyphID> {
+        let start = 6 + 2 * 256 + 8 * self.headers.len();
+        let mut values = HashMap::new();
+        for (high, key) in self.keys.iter().enumerate() {
+            let index = (key / 8) as usize;
+            let header = match self.headers.get(index) {
+                Some(header) => header,
+                _ => continue,
+            };
+            let position = 6 + 2 * 256 + 8 * index + 6 + header.id_range_offset as usize;
+            let codes = header.first_code..header.first_code.saturating_add(header.entry_count);
+            for (offset, low) in codes.enumerate() {
+                let code = if index == 0 {
+                    if low as usize != high {
+                        continue;
+                    }
+                    high as u32
+                } else {
+                    (high as u32) << 8 | low as u32
+                };
+                let glyph_id = match (position + 2 * offset)
+                    .checked_sub(start)
+                    .and_then(|offset| self.glyph_ids.get(offset / 2))
+                {
+                    Some(0) | None => continue,
+                    Some(glyph_id) => glyph_id.wrapping_add(header.id_delta as u16),
+                };
+                values.insert(code, glyph_id);
+            }
+        }
+        values
+    }
+}
+
+impl opentype::Table for CharacterMapping {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"cmap")
+    }
+}
+
+impl opentype::value::Read for CharacterMapping {
+    fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let header = tape.take::<Header>()?;
+        let mut records = Vec::with_capacity(header.table_count as usize);
+        for _ in 0..header.table_count {
+            records.push(tape.take::<Record>()?);
+        }
+        let mut encodings = Vec::with_capacity(records.len());
+        for record in records.iter() {
+            tape.jump(position + record.offset as u64)?;
+            encodings.push(match tape.peek::<u16>()? {
+                0 => Encoding::Format0(tape.take()?),
+                2 => Encoding::Format2(tape.take()?),
+                4 => Encoding::Format4(tape.take()?),
+                6 => Encoding::Format6(tape.take()?),
+                10 => Encoding::Format10(tape.take()?),
+                12 => Encoding::Format12(tape.take()?),
+                13 => Encoding::Format13(tape.take()?),
+                format => Encoding::Unknown(format),
+            });
+        }
+        Ok(Self { records, encodings })
+    }
+}
+
+// Sort ranges and trim the ones overlapping the preceding ones.
+fn disjoin(mut ranges: Vec<(u32, u32, GlyphID)>) -> Vec<(u32, u32, GlyphID)> {
+    ranges.sort_by_key(|(start, _, _)| *start);
+    let mut values: Vec<(u32, u32, GlyphID)> = Vec::with_capacity(ranges.len());
+    for (start, end, glyph_id) in ranges {
+        let start = match values.last() {
+            Some((_, last, _)) if *last >= end => continue,
+            Some((_, last, _)) => start.max(last + 1),
+            _ => start,
+        };
+        values.push((start, end, glyph_id));
+    }
+    values
+}
+
+// Symbol encodings place characters at U+F000..U+F0FF, which are also reachable without the offset.
+#[inline]
+fn symbolic(record: &Record) -> bool {
+    (record.platform_id, record.encoding_id) == (3, 0)
+}
+
+// Unicode full repertoire, Unicode BMP, Windows symbol, and Mac Roman in this order.
+fn rank(record: &Record) -> usize {
+    match (record.platform_id, record.encoding_id) {
+        (0, 4) | (0, 6) | (3, 10) => 0,
+        (0, _) | (3, 1) => 1,
+        (3, 0) => 2,
+        (1, 0) => 3,
+        _ => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::{CharacterMapping, Encoding};
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn format2() {
+        let mut data = vec![0x00, 0x02, 0x00, 0x00, 0x00, 0x00];
+        for high in 0..256 {
+            data.extend([0x00, if high == 0x81 { 0x08 } else { 0x00 }]);
+        }
+        data.extend([0x00, 0x41, 0x00, 0x02, 0x00, 0x00, 0x00, 0x0A]);
+        data.extend([0x00, 0x40, 0x00, 0x02, 0x00, 0x0A, 0x00, 0x06]);
+        data.extend([0x00, 0x03, 0x00, 0x04, 0x00, 0x05, 0x00, 0x06]);
+        let size = data.len() as u16;
+        data[2..4].copy_from_slice(&size.to_be_bytes());
+        let encoding = ok!(super::Encoding2::read(&mut Cursor::new(data)));
+        let mut values = encoding.mapping().into_iter().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, [(0x41, 3), (0x42, 4), (0x8140, 15), (0x8141, 16)],);
+        let encoding = Encoding::Format2(encoding);
+        assert_eq!(encoding.characters(), [(0x41, 0x42)]);
+        assert_eq!(encoding.mapping().len(), 2);
+    }
+
+    #[test]
+    fn format13() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x01, // version, numTables
+            0x00, 0x03, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x0C, // platformID, encodingID, offset
+            0x00, 0x0D, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, // format, reserved, length
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, // language, numGroups
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7F, 0x00, 0x00, 0x00, 0x01,
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x10, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x02,
+        ];
+        let table = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+        let encoding = &table.encodings[ok!(table.choose(None))];
+        assert!(matches!(encoding, Encoding::Format13(_)));
+        assert_eq!(encoding.characters(), [(0, 0x7F), (0x10000, 0x10FFFF)]);
+        assert!(encoding.mapping().is_empty());
+        assert_eq!(encoding.ranges(), [(0, 0x7F, 1), (0x10000, 0x10FFFF, 2)]);
+        assert_eq!(encoding.codes().len(), 0x80 + 0x100000);
+    }
+
+    #[test]
+    fn format13_malformed() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x0D, 0x00, 0x00, 0x00, 0x00, 0x00, 0x34, // format, reserved, length
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, // language, numGroups
+            0x00, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x7F, 0x00, 0x00, 0x00, 0x01,
+            0x00, 0x10, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x02,
+            0x00, 0x10, 0xFF, 0x00, 0x00, 0x10, 0xFF, 0x0F, 0x00, 0x00, 0x00, 0x03,
+        ];
+        let encoding = Encoding::Format13(ok!(super::Encoding13::read(&mut Cursor::new(data))));
+        assert_eq!(encoding.ranges(), [(0x100000, 0x10FFFF, 2)]);
+    }
+
+    #[test]
+    fn format10_malformed() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x18, // format, reserved, length
+            0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, // language, startCharCode
+            0x00, 0x00, 0x00, 0x02, 0x00, 0x05, 0x00, 0x06, // numChars, glyphIdArray
+        ];
+        let encoding = Encoding::Format10(ok!(super::Encoding10::read(&mut Cursor::new(data))));
+        assert!(encoding.characters().is_empty());
+        assert!(encoding.mapping().is_empty());
+    }
+}
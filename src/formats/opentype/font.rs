@@ -3,13 +3,16 @@ use std::io::Result;
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
+use opentype::truetype::tables::names::NameID;
 use opentype::truetype::tables::FontHeader;
-use opentype::truetype::Tag;
+use opentype::truetype::{GlyphID, Tag};
 
 use crate::formats::opentype::cache::{Cache, Reference};
 use crate::formats::opentype::{
-    axes, characters, features, metrics, names, palettes, tables, timestamps,
+    axes, bitmap, characters, documents, features, hinting, layout, metrics, names, palettes,
+    tables, timestamps,
 };
+use crate::Number;
 
 /// A font.
 pub struct Font<T> {
@@ -35,6 +38,27 @@ impl<T: crate::Read> crate::font::Case for Font<T> {
         characters::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn characters_of(&mut self, glyph_id: GlyphID) -> Result<Vec<char>> {
+        characters::characters_of(&mut self.cache.borrow_mut(), glyph_id)
+    }
+
+    #[inline]
+    fn codes(&mut self) -> Result<Vec<(u32, GlyphID)>> {
+        characters::codes(&mut self.cache.borrow_mut())
+    }
+
+    #[inline]
+    fn coverage(&mut self) -> Result<crate::Coverage> {
+        let characters = characters::read(&mut self.cache.borrow_mut())?;
+        Ok(crate::Coverage::new(&characters))
+    }
+
+    #[inline]
+    fn encodings(&mut self) -> Result<crate::Encodings> {
+        characters::encodings(&mut self.cache.borrow_mut())
+    }
+
     #[inline]
     fn features(&mut self) -> Result<crate::Features> {
         features::read(&mut self.cache.borrow_mut())
@@ -45,16 +69,48 @@ impl<T: crate::Read> crate::font::Case for Font<T> {
         metrics::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn line_metrics(&mut self, policy: crate::LinePolicy) -> Result<crate::LineMetrics> {
+        metrics::read_line(&mut self.cache.borrow_mut(), policy)
+    }
+
     #[inline]
     fn names(&mut self) -> Result<crate::Names> {
         names::read(&mut self.cache.borrow_mut())
     }
 
     #[inline]
-    fn palettes(&mut self) -> Result<crate::Palettes> {
+    fn palettes(&mut self) -> Result<Option<crate::Palettes>> {
         palettes::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn sequences(&mut self) -> Result<crate::Sequences> {
+        characters::sequences(&mut self.cache.borrow_mut())
+    }
+
+    #[inline]
+    fn select_encoding(&mut self, platform_id: u16, encoding_id: u16) -> Result<()> {
+        characters::select(&mut self.cache.borrow_mut(), platform_id, encoding_id)
+    }
+
+    #[inline]
+    fn set_name(&mut self, name_id: NameID, language: &str, value: &str) -> Result<()> {
+        names::set(&mut self.cache.borrow_mut(), name_id, language, value)
+    }
+
+    #[inline]
+    fn remove_name(&mut self, name_id: NameID, language: &str) -> Result<()> {
+        names::remove(&mut self.cache.borrow_mut(), name_id, language)
+    }
+
+    fn supported_languages(&mut self) -> Result<Vec<crate::languages::Support>> {
+        let mut cache = self.cache.borrow_mut();
+        let characters = characters::read(&mut cache)?;
+        let features = features::read(&mut cache)?;
+        Ok(crate::languages::supported(&characters, &features))
+    }
+
     #[inline]
     fn tables(&mut self) -> Result<crate::Tables> {
         tables::read(&mut self.cache.borrow_mut())
@@ -65,23 +121,163 @@ impl<T: crate::Read> crate::font::Case for Font<T> {
         timestamps::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn vertical_metrics(&mut self) -> Result<crate::VerticalMetrics> {
+        metrics::read_vertical(&mut self.cache.borrow_mut())
+    }
+
     fn glyph(&mut self, character: char) -> Result<Option<crate::Glyph>> {
         let mut cache = self.cache.borrow_mut();
-        let mapping = cache.forward_mapping()?.clone();
+        let glyph_id = match cache.forward_mapping()?.get(character) {
+            Some(glyph_id) => glyph_id,
+            _ => return Ok(None),
+        };
+        draw(&mut cache, self.index, glyph_id).map(Some)
+    }
+
+    fn glyph_variant(&mut self, character: char, selector: char) -> Result<Option<crate::Glyph>> {
+        let mut cache = self.cache.borrow_mut();
+        let base = cache.forward_mapping()?.get(character);
+        let glyph_id = cache
+            .variants()?
+            .borrow()
+            .resolve(character as u32, selector as u32, base);
+        match glyph_id {
+            Some(glyph_id) => draw(&mut cache, self.index, glyph_id).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    fn hinted_glyph(&mut self, character: char, ppem: u16) -> Result<Option<crate::Glyph>> {
+        let mut cache = self.cache.borrow_mut();
+        let glyph_id = match cache.forward_mapping()?.get(character) {
+            Some(glyph_id) => glyph_id,
+            _ => return Ok(None),
+        };
+        match self.index {
+            (true, _) => hinting::draw(&mut cache, glyph_id, ppem).map(Some),
+            _ => raise!("found PostScript outlines, which cannot be hinted yet"),
+        }
+    }
+
+    fn bitmap_glyph(&mut self, character: char, ppem: u16) -> Result<Option<crate::glyph::Bitmap>> {
+        let mut cache = self.cache.borrow_mut();
+        match cache.forward_mapping()?.get(character) {
+            Some(glyph_id) => bitmap::read(&mut cache, glyph_id, ppem),
+            _ => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn svg_glyph(&mut self, glyph_id: GlyphID) -> Result<Option<crate::glyph::Document>> {
+        documents::read(&mut self.cache.borrow_mut(), glyph_id)
+    }
+
+    fn color_glyph(&mut self, character: char) -> Result<Option<crate::ColorGlyph>> {
+        use crate::color::{Layer, Paint};
+
+        let mut cache = self.cache.borrow_mut();
+        let glyph_id = match cache.forward_mapping()?.get(character) {
+            Some(glyph_id) => glyph_id,
+            _ => return Ok(None),
+        };
+        let table = match cache.try_color_glyphs()? {
+            Some(table) => table.clone(),
+            _ => return Ok(None),
+        };
+        let table = table.borrow();
+        let mut layers = Vec::new();
+        for layer in table.get(glyph_id).unwrap_or_default() {
+            layers.push(Layer {
+                glyph_id: layer.glyph_id,
+                glyph: draw(&mut cache, self.index, layer.glyph_id)?,
+                palette_index: match layer.palette_index {
+                    0xFFFF => None,
+                    value => Some(value),
+                },
+            });
+        }
+        let paint =
+            match table.paint(glyph_id, |glyph_id| draw(&mut cache, self.index, glyph_id))? {
+                Some(paint) => paint,
+                _ if layers.is_empty() => return Ok(None),
+                _ => Paint::Layers(
+                    layers
+                        .iter()
+                        .map(|layer| Paint::Glyph {
+                            glyph_id: layer.glyph_id,
+                            glyph: layer.glyph.clone(),
+                            paint: Box::new(Paint::Solid {
+                                palette_index: layer.palette_index,
+                                alpha: 1.0,
+                            }),
+                        })
+                        .collect(),
+                ),
+            };
+        let clip_box = table.clip(glyph_id).map(|(left, bottom, right, top)| {
+            (left.into(), bottom.into(), right.into(), top.into())
+        });
+        Ok(Some(crate::ColorGlyph {
+            layers,
+            paint,
+            clip_box,
+        }))
+    }
+
+    #[inline]
+    fn glyph_by_id(&mut self, glyph_id: GlyphID) -> Result<crate::Glyph> {
+        draw(&mut self.cache.borrow_mut(), self.index, glyph_id)
+    }
+
+    fn shallow_glyph(&mut self, glyph_id: GlyphID) -> Result<crate::Glyph> {
+        let mut cache = self.cache.borrow_mut();
         let metrics = cache.metrics()?.clone();
         match self.index {
             (true, _) => {
-                super::truetype::draw(&cache.glyph_data()?.borrow(), &mapping, &metrics, character)
+                super::truetype::draw_shallow(&cache.glyph_data()?.borrow(), &metrics, glyph_id)
+            }
+            (false, id) => {
+                super::postscript::draw_shallow(&cache.font_set()?.borrow(), &metrics, id, glyph_id)
             }
-            (false, id) => super::postscript::draw(
-                &cache.font_set()?.borrow(),
-                &mapping,
-                &metrics,
-                id,
-                character,
-            ),
         }
     }
+
+    fn components(&mut self, glyph_id: GlyphID) -> Result<Vec<crate::glyph::Component>> {
+        let mut cache = self.cache.borrow_mut();
+        match self.index {
+            (true, _) => super::truetype::components(&cache.glyph_data()?.borrow(), glyph_id),
+            // Glyphs composed by endchar have a base and an accent.
+            (false, id) => super::postscript::components(&cache.font_set()?.borrow(), id, glyph_id),
+        }
+    }
+
+    fn measure(&mut self, text: &str, size: Number) -> Result<crate::Layout> {
+        let index = self.index;
+        layout::measure(
+            &mut self.cache.borrow_mut(),
+            text,
+            size,
+            0.0,
+            |cache, glyph_id| draw(cache, index, glyph_id),
+        )
+    }
+
+    fn outline(&mut self, text: &str, size: Number, spacing: Number) -> Result<crate::Glyph> {
+        let index = self.index;
+        layout::outline(
+            &mut self.cache.borrow_mut(),
+            text,
+            size,
+            spacing,
+            |cache, glyph_id| draw(cache, index, glyph_id),
+        )
+    }
+
+    #[inline]
+    fn save<U: crate::Read + crate::Write>(&mut self, tape: &mut U) -> Result<()> {
+        update(self, tape)
+    }
 }
 
 pub fn read<T: crate::Read>(tape: Reference<T>, backend: opentype::Font) -> Result<Vec<Font<T>>> {
@@ -115,10 +311,45 @@ pub fn read<T: crate::Read>(tape: Reference<T>, backend: opentype::Font) -> Resu
     Ok(fonts)
 }
 
+/// Write a font updating the names if they have been read.
+#[inline]
+pub fn save<T, U>(font: Font<T>, tape: &mut U) -> Result<()>
+where
+    T: crate::Read,
+    U: crate::Read + crate::Write,
+{
+    update(&font, tape)
+}
+
 /// Write a font.
+#[inline]
 pub fn write<T, U, F>(font: Font<T>, tape: &mut U, dispose: F) -> Result<()>
 where
-    T: crate::Read + 'static,
+    T: crate::Read,
+    U: crate::Read + crate::Write,
+    F: Fn(&Tag) -> Disposition,
+{
+    dump(&font, tape, dispose)
+}
+
+fn update<T, U>(font: &Font<T>, tape: &mut U) -> Result<()>
+where
+    T: crate::Read,
+    U: crate::Read + crate::Write,
+{
+    let names = font.cache.borrow().names.is_some();
+    dump(font, tape, |tag| {
+        if names && tag == b"name" {
+            Disposition::Update
+        } else {
+            Disposition::Retain
+        }
+    })
+}
+
+fn dump<T, U, F>(font: &Font<T>, tape: &mut U, dispose: F) -> Result<()>
+where
+    T: crate::Read,
     U: crate::Read + crate::Write,
     F: Fn(&Tag) -> Disposition,
 {
@@ -183,9 +414,21 @@ where
     Ok(())
 }
 
+fn draw<T: crate::Read>(
+    cache: &mut Cache<T>,
+    index: (bool, usize),
+    glyph_id: GlyphID,
+) -> Result<crate::Glyph> {
+    let metrics = cache.metrics()?.clone();
+    match index {
+        (true, _) => super::truetype::draw(&cache.glyph_data()?.borrow(), &metrics, glyph_id),
+        (false, id) => super::postscript::draw(&cache.font_set()?.borrow(), &metrics, id, glyph_id),
+    }
+}
+
 fn copy<T, U>(source: &mut T, destination: &mut U, size: u64) -> Result<()>
 where
-    T: crate::Read + 'static,
+    T: crate::Read,
     U: crate::Read + crate::Write,
 {
     let mut source = std::io::Read::take(source.by_ref(), size);
@@ -0,0 +1,335 @@
+use std::io::Result;
+
+// The maximum size of a decompressed stream.
+const LIMIT: usize = 1 << 26;
+
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRAS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRAS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+struct Bits<'l> {
+    data: &'l [u8],
+    position: usize,
+    buffer: u32,
+    count: u32,
+}
+
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl<'l> Bits<'l> {
+    fn take(&mut self, count: u32) -> Result<u32> {
+        while self.count < count {
+            let byte = match self.data.get(self.position) {
+                Some(byte) => *byte as u32,
+                _ => raise!("found a truncated compressed stream"),
+            };
+            self.position += 1;
+            self.buffer |= byte << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1u64 << count) - 1) as u32;
+        self.buffer = self.buffer.checked_shr(count).unwrap_or(0);
+        self.count -= count;
+        Ok(value)
+    }
+
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for length in lengths {
+            counts[*length as usize] += 1;
+        }
+        let mut offsets = [0u16; 16];
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length > 0 {
+                let offset = &mut offsets[*length as usize];
+                symbols[*offset as usize] = symbol as u16;
+                *offset += 1;
+            }
+        }
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for length in 1..16 {
+            code |= bits.take(1)? as i32;
+            let count = self.counts[length] as i32;
+            if code - count < first {
+                return match self.symbols.get((index + code - first) as usize) {
+                    Some(symbol) => Ok(*symbol),
+                    _ => raise!("found a malformed compressed stream"),
+                };
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        raise!("found a malformed compressed stream")
+    }
+}
+
+pub fn is(data: &[u8]) -> bool {
+    data.starts_with(&[0x1F, 0x8B])
+}
+
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < 18 || !is(data) || data[2] != 8 {
+        raise!("found a malformed compressed stream");
+    }
+    let flags = data[3];
+    let mut position = 10;
+    if flags & 0x04 != 0 {
+        position += 2 + u16::from_le_bytes([data[position], data[position + 1]]) as usize;
+    }
+    for flag in [0x08, 0x10] {
+        if flags & flag != 0 {
+            while data.get(position).is_some_and(|byte| *byte != 0) {
+                position += 1;
+            }
+            position += 1;
+        }
+    }
+    if flags & 0x02 != 0 {
+        position += 2;
+    }
+    if position > data.len() - 8 {
+        raise!("found a malformed compressed stream");
+    }
+    let tail = &data[data.len() - 8..];
+    let checksum = u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]);
+    let size = u32::from_le_bytes([tail[4], tail[5], tail[6], tail[7]]);
+    if size as usize > LIMIT {
+        raise!("found a compressed stream exceeding the size limit");
+    }
+    let value = inflate(&data[position..], size as usize)?;
+    if size != value.len() as u32 || checksum != crc32(&value) {
+        raise!("found a corrupted compressed stream");
+    }
+    Ok(value)
+}
+
+fn inflate(data: &[u8], limit: usize) -> Result<Vec<u8>> {
+    let mut bits = Bits {
+        data,
+        position: 0,
+        buffer: 0,
+        count: 0,
+    };
+    let mut value = Vec::new();
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => {
+                bits.align();
+                let start = bits.position;
+                if start + 4 > data.len() {
+                    raise!("found a truncated compressed stream");
+                }
+                let size = u16::from_le_bytes([data[start], data[start + 1]]) as usize;
+                let check = u16::from_le_bytes([data[start + 2], data[start + 3]]) as usize;
+                if size != !check & 0xFFFF || start + 4 + size > data.len() {
+                    raise!("found a malformed compressed stream");
+                }
+                if value.len() + size > limit {
+                    raise!("found a compressed stream exceeding its size");
+                }
+                value.extend_from_slice(&data[(start + 4)..(start + 4 + size)]);
+                bits.position = start + 4 + size;
+            }
+            1 => {
+                let mut lengths = [0u8; 288 + 30];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..288].fill(8);
+                lengths[288..].fill(5);
+                let literals = Huffman::new(&lengths[..288]);
+                let distances = Huffman::new(&lengths[288..]);
+                decode(&mut bits, &mut value, limit, &literals, &distances)?;
+            }
+            2 => {
+                let literal_count = bits.take(5)? as usize + 257;
+                let distance_count = bits.take(5)? as usize + 1;
+                let code_count = bits.take(4)? as usize + 4;
+                let mut lengths = [0u8; 19];
+                for index in CODE_LENGTH_ORDER.iter().take(code_count) {
+                    lengths[*index] = bits.take(3)? as u8;
+                }
+                let codes = Huffman::new(&lengths);
+                let mut lengths = vec![0u8; literal_count + distance_count];
+                let mut index = 0;
+                while index < lengths.len() {
+                    let (length, count) = match codes.decode(&mut bits)? {
+                        symbol @ 0..=15 => (symbol as u8, 1),
+                        16 if index > 0 => (lengths[index - 1], 3 + bits.take(2)? as usize),
+                        17 => (0, 3 + bits.take(3)? as usize),
+                        18 => (0, 11 + bits.take(7)? as usize),
+                        _ => raise!("found a malformed compressed stream"),
+                    };
+                    if index + count > lengths.len() {
+                        raise!("found a malformed compressed stream");
+                    }
+                    lengths[index..(index + count)].fill(length);
+                    index += count;
+                }
+                let literals = Huffman::new(&lengths[..literal_count]);
+                let distances = Huffman::new(&lengths[literal_count..]);
+                decode(&mut bits, &mut value, limit, &literals, &distances)?;
+            }
+            _ => raise!("found a malformed compressed stream"),
+        }
+        if last {
+            break;
+        }
+    }
+    Ok(value)
+}
+
+fn decode(
+    bits: &mut Bits,
+    value: &mut Vec<u8>,
+    limit: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<()> {
+    loop {
+        let symbol = literals.decode(bits)? as usize;
+        if symbol < 256 {
+            if value.len() >= limit {
+                raise!("found a compressed stream exceeding its size");
+            }
+            value.push(symbol as u8);
+            continue;
+        }
+        if symbol == 256 {
+            return Ok(());
+        }
+        let index = symbol - 257;
+        if index >= LENGTH_BASES.len() {
+            raise!("found a malformed compressed stream");
+        }
+        let length =
+            LENGTH_BASES[index] as usize + bits.take(LENGTH_EXTRAS[index] as u32)? as usize;
+        let index = distances.decode(bits)? as usize;
+        if index >= DISTANCE_BASES.len() {
+            raise!("found a malformed compressed stream");
+        }
+        let distance =
+            DISTANCE_BASES[index] as usize + bits.take(DISTANCE_EXTRAS[index] as u32)? as usize;
+        if distance > value.len() {
+            raise!("found a malformed compressed stream");
+        }
+        if value.len() + length > limit {
+            raise!("found a compressed stream exceeding its size");
+        }
+        let start = value.len() - distance;
+        for offset in 0..length {
+            value.push(value[start + offset]);
+        }
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut value = !0u32;
+    for byte in data {
+        value ^= *byte as u32;
+        for _ in 0..8 {
+            value = (value >> 1) ^ (0xEDB8_8320 & 0u32.wrapping_sub(value & 1));
+        }
+    }
+    !value
+}
+
+#[cfg(test)]
+mod tests {
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn decompress() {
+        let expected = r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#;
+
+        #[rustfmt::skip]
+        let data = [
+            0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x03, 0x01, 0x29, 0x00, 0xD6,
+            0xFF, 0x3C, 0x73, 0x76, 0x67, 0x20, 0x78, 0x6D, 0x6C, 0x6E, 0x73, 0x3D, 0x22, 0x68,
+            0x74, 0x74, 0x70, 0x3A, 0x2F, 0x2F, 0x77, 0x77, 0x77, 0x2E, 0x77, 0x33, 0x2E, 0x6F,
+            0x72, 0x67, 0x2F, 0x32, 0x30, 0x30, 0x30, 0x2F, 0x73, 0x76, 0x67, 0x22, 0x2F, 0x3E,
+            0xC4, 0xF7, 0x91, 0x93, 0x29, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(ok!(super::decompress(&data)), expected.as_bytes());
+
+        #[rustfmt::skip]
+        let data = [
+            0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xB3, 0x29, 0x2E, 0x4B,
+            0x57, 0xA8, 0xC8, 0xCD, 0xC9, 0x2B, 0xB6, 0x55, 0xCA, 0x28, 0x29, 0x29, 0xB0, 0xD2,
+            0xD7, 0x2F, 0x2F, 0x2F, 0xD7, 0x2B, 0x37, 0xD6, 0xCB, 0x2F, 0x4A, 0xD7, 0x37, 0x32,
+            0x30, 0x30, 0xD0, 0x07, 0xAA, 0x50, 0xD2, 0xB7, 0x03, 0x00, 0xC4, 0xF7, 0x91, 0x93,
+            0x29, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(ok!(super::decompress(&data)), expected.as_bytes());
+
+        let mut expected = String::from(r#"<svg xmlns="http://www.w3.org/2000/svg">"#);
+        for index in 0..12 {
+            let x = index * 10;
+            expected.push_str(&format!(r#"<path id="glyph{index}" d="M0 0L{x} 100Z"/>"#));
+        }
+        expected.push_str("</svg>");
+
+        #[rustfmt::skip]
+        let data = [
+            0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x75, 0xD1, 0x4D, 0x0A,
+            0xC2, 0x30, 0x10, 0x86, 0xE1, 0xAB, 0x0C, 0x73, 0x80, 0xCE, 0x24, 0xE9, 0xAF, 0xB4,
+            0x3D, 0x81, 0x5E, 0xC0, 0x9D, 0x20, 0x24, 0x42, 0xD5, 0xD0, 0x06, 0xA3, 0xB7, 0xB7,
+            0xAE, 0x26, 0x08, 0xB3, 0xFB, 0x16, 0xCF, 0xEA, 0xFD, 0xC6, 0xED, 0xE5, 0xE1, 0x7D,
+            0x5F, 0x1E, 0xDB, 0x84, 0x21, 0xA5, 0x78, 0x20, 0xCA, 0x39, 0x57, 0xD9, 0x55, 0xCF,
+            0xD5, 0x93, 0x65, 0x66, 0xDA, 0x05, 0xCE, 0x63, 0xBC, 0xA4, 0x00, 0xB7, 0xEB, 0x84,
+            0x7E, 0xF9, 0xC4, 0xC0, 0x08, 0xFB, 0x3C, 0x31, 0xF0, 0x91, 0xC1, 0x30, 0x9F, 0x91,
+            0xFE, 0x89, 0x11, 0x62, 0x34, 0x63, 0xC5, 0x58, 0xCD, 0x38, 0x31, 0x4E, 0x33, 0xB5,
+            0x98, 0x5A, 0x33, 0x8D, 0x98, 0x46, 0x33, 0xAD, 0x98, 0x56, 0x33, 0x9D, 0x98, 0x4E,
+            0x33, 0xBD, 0x98, 0x5E, 0x33, 0x83, 0x98, 0x41, 0x6D, 0xC8, 0x65, 0x44, 0x55, 0x95,
+            0xA9, 0x8B, 0xD6, 0xBF, 0xE7, 0xE6, 0x2F, 0x3E, 0xF2, 0xFF, 0xC2, 0xE1, 0x01, 0x00,
+            0x00,
+        ];
+        assert_eq!(ok!(super::decompress(&data)), expected.as_bytes());
+
+        let mut other = data.to_vec();
+        other[20] ^= 0xFF;
+        assert!(super::decompress(&other).is_err());
+
+        let mut other = data.to_vec();
+        let size = other.len() - 4;
+        other[size..].copy_from_slice(&100u32.to_le_bytes());
+        assert!(super::decompress(&other).is_err());
+        other[size..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(super::decompress(&other).is_err());
+    }
+}
@@ -0,0 +1,2039 @@
+// The virtual machine follows the reference rasterizer in interpreter version 40, including
+// its undocumented behavior, so that grid-fitted outlines agree bit for bit.
+
+use std::io::Result;
+
+use super::math;
+
+pub const ON_CURVE: u8 = 0x01;
+pub const TOUCHED_X: u8 = 0x08;
+pub const TOUCHED_Y: u8 = 0x10;
+
+const MAXIMUM_CALL_DEPTH: usize = 32;
+const MAXIMUM_INSTRUCTION_COUNT: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Zone {
+    pub unscaled: Vec<Point>,
+    pub original: Vec<Point>,
+    pub current: Vec<Point>,
+    pub flags: Vec<u8>,
+    pub end_points: Vec<usize>,
+    pub limit: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct State {
+    pub reference_points: [u16; 3],
+    pub dual_vector: (i32, i32),
+    pub projection_vector: (i32, i32),
+    pub freedom_vector: (i32, i32),
+    pub loop_count: i32,
+    pub minimum_distance: i32,
+    pub round_state: u8,
+    pub auto_flip: bool,
+    pub control_value_cutin: i32,
+    pub single_width_cutin: i32,
+    pub single_width_value: i32,
+    pub delta_base: u16,
+    pub delta_shift: u16,
+    pub instruct_control: u8,
+    pub scan_control: bool,
+    pub scan_type: i32,
+    pub zone_pointers: [u8; 3],
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Range {
+    Font = 0,
+    ControlValue = 1,
+    Glyph = 2,
+}
+
+#[derive(Clone)]
+pub struct Machine {
+    pub state: State,
+    pub control_values: Vec<i32>,
+    pub storage: Vec<i32>,
+    pub twilight: Zone,
+    pub glyph: Zone,
+    pub programs: [Vec<u8>; 3],
+    pub ppem: i32,
+    pub point_size: i32,
+    pub scale: i32,
+    pub unscaled_scale: i32,
+    pub backward_compatibility: bool,
+    pub subpixel: bool,
+    pub is_composite: bool,
+
+    period: i32,
+    phase: i32,
+    threshold: i32,
+
+    functions: Vec<Definition>,
+    function_limit: usize,
+    maximum_function: u32,
+    instructions: Vec<Definition>,
+    instruction_limit: usize,
+
+    stack: Vec<i32>,
+    top: usize,
+    calls: Vec<Call>,
+
+    range: Range,
+    initial_range: Range,
+    ip: usize,
+    opcode: u8,
+    length: usize,
+    args: usize,
+    new_top: usize,
+    step: bool,
+    iup_called: (bool, bool),
+    f_dot_p: i32,
+    loop_calls: (usize, usize),
+    negative_jumps: (usize, usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Definition {
+    number: u32,
+    range: Range,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Call {
+    range: Range,
+    ip: usize,
+    count: i32,
+    definition: Definition,
+}
+
+impl Zone {
+    pub fn new(count: usize) -> Self {
+        Self {
+            unscaled: vec![Point::default(); count],
+            original: vec![Point::default(); count],
+            current: vec![Point::default(); count],
+            flags: vec![0; count],
+            end_points: vec![],
+            limit: count,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.current.len().min(self.limit)
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            reference_points: [0; 3],
+            dual_vector: (0x4000, 0),
+            projection_vector: (0x4000, 0),
+            freedom_vector: (0x4000, 0),
+            loop_count: 1,
+            minimum_distance: 64,
+            round_state: 1,
+            auto_flip: true,
+            control_value_cutin: 68,
+            single_width_cutin: 0,
+            single_width_value: 0,
+            delta_base: 9,
+            delta_shift: 3,
+            instruct_control: 0,
+            scan_control: false,
+            scan_type: 0,
+            zone_pointers: [1; 3],
+        }
+    }
+}
+
+impl Machine {
+    pub fn new(
+        stack_size: usize,
+        storage_size: usize,
+        twilight_size: usize,
+        function_limit: usize,
+        instruction_limit: usize,
+    ) -> Self {
+        Self {
+            state: State::default(),
+            control_values: vec![],
+            storage: vec![0; storage_size],
+            twilight: Zone::new(twilight_size),
+            glyph: Zone::default(),
+            programs: Default::default(),
+            ppem: 0,
+            point_size: 0,
+            scale: 0,
+            unscaled_scale: 0,
+            backward_compatibility: false,
+            subpixel: true,
+            is_composite: false,
+
+            period: 64,
+            phase: 0,
+            threshold: 0,
+
+            functions: vec![],
+            function_limit,
+            maximum_function: 0,
+            instructions: vec![],
+            instruction_limit,
+
+            stack: vec![0; stack_size],
+            top: 0,
+            calls: vec![],
+
+            range: Range::Font,
+            initial_range: Range::Font,
+            ip: 0,
+            opcode: 0,
+            length: 0,
+            args: 0,
+            new_top: 0,
+            step: false,
+            iup_called: (false, false),
+            f_dot_p: 0x4000,
+            loop_calls: (0, 0),
+            negative_jumps: (0, 0),
+        }
+    }
+
+    pub fn run(&mut self, range: Range) -> Result<()> {
+        if self.programs[range as usize].is_empty() {
+            return Ok(());
+        }
+        self.range = range;
+        self.initial_range = range;
+        self.ip = 0;
+        self.top = 0;
+        self.calls.clear();
+        self.iup_called = (false, false);
+        self.update_vectors();
+        let point_count = self.glyph.len();
+        let control_value_count = self.control_values.len();
+        let limit = 30.max(2 * (point_count + control_value_count));
+        self.twilight.limit = self.twilight.limit.min(limit);
+        let limit = match point_count {
+            0 => 300 + 22 * control_value_count,
+            _ => 50.max(10 * point_count) + 50.max(control_value_count / 10),
+        };
+        self.loop_calls = (0, limit);
+        self.negative_jumps = (0, limit);
+        let mut count = 0;
+        loop {
+            let size = self.programs[self.range as usize].len();
+            if self.ip >= size {
+                if !self.calls.is_empty() {
+                    raise!("found a function without an end");
+                }
+                return Ok(());
+            }
+            self.opcode = self.programs[self.range as usize][self.ip];
+            self.length = self.measure(self.ip)?;
+            let (pop, push) = count_arguments(self.opcode);
+            if self.top < pop {
+                self.stack[..pop].fill(0);
+                self.args = 0;
+            } else {
+                self.args = self.top - pop;
+            }
+            self.new_top = self.args + push;
+            if self.new_top > self.stack.len() {
+                raise!("found a stack overflow in hinting instructions");
+            }
+            self.step = true;
+            self.execute()?;
+            self.top = self.new_top;
+            if self.step {
+                self.ip += self.length;
+            }
+            count += 1;
+            if count > MAXIMUM_INSTRUCTION_COUNT {
+                raise!("found too many hinting instructions to execute");
+            }
+        }
+    }
+
+    fn measure(&self, ip: usize) -> Result<usize> {
+        let code = &self.programs[self.range as usize];
+        let opcode = code[ip];
+        let length = match opcode {
+            0x40 | 0x41 => match code.get(ip + 1) {
+                Some(&count) => 2 + count as usize * (opcode as usize - 0x3F),
+                _ => raise!("found a code overflow in hinting instructions"),
+            },
+            0xB0..=0xB7 => 2 + (opcode - 0xB0) as usize,
+            0xB8..=0xBF => 3 + 2 * (opcode - 0xB8) as usize,
+            _ => 1,
+        };
+        if ip + length > code.len() {
+            raise!("found a code overflow in hinting instructions");
+        }
+        Ok(length)
+    }
+
+    fn skip(&mut self) -> Result<()> {
+        self.ip += self.length;
+        if self.ip >= self.programs[self.range as usize].len() {
+            raise!("found a code overflow in hinting instructions");
+        }
+        self.opcode = self.programs[self.range as usize][self.ip];
+        self.length = self.measure(self.ip)?;
+        Ok(())
+    }
+
+    fn execute(&mut self) -> Result<()> {
+        let opcode = self.opcode;
+        match opcode {
+            // SVTCA, SPVTCA, SFVTCA
+            0x00..=0x05 => {
+                let vector = if opcode & 1 == 0 {
+                    (0, 0x4000)
+                } else {
+                    (0x4000, 0)
+                };
+                if opcode < 0x04 {
+                    self.state.projection_vector = vector;
+                    self.state.dual_vector = vector;
+                }
+                if opcode & 0x06 != 0x02 {
+                    self.state.freedom_vector = vector;
+                }
+                self.update_vectors();
+            }
+            // SPVTL, SFVTL
+            0x06..=0x09 => {
+                let (first, second) = (self.argument(1) as u16, self.argument(0) as u16);
+                if let Some(vector) = self.vector_to_line(first as usize, second as usize) {
+                    if opcode < 0x08 {
+                        self.state.projection_vector = vector;
+                        self.state.dual_vector = vector;
+                    } else {
+                        self.state.freedom_vector = vector;
+                    }
+                }
+                self.update_vectors();
+            }
+            // SPVFS, SFVFS
+            0x0A | 0x0B => {
+                let x = self.argument(0) as i16 as i32;
+                let y = self.argument(1) as i16 as i32;
+                if let Some(vector) = math::normalize(x, y) {
+                    if opcode == 0x0A {
+                        self.state.projection_vector = vector;
+                        self.state.dual_vector = vector;
+                    } else {
+                        self.state.freedom_vector = vector;
+                    }
+                }
+                self.update_vectors();
+            }
+            // GPV, GFV
+            0x0C | 0x0D => {
+                let vector = match opcode {
+                    0x0C => self.state.projection_vector,
+                    _ => self.state.freedom_vector,
+                };
+                self.set(0, vector.0);
+                self.set(1, vector.1);
+            }
+            // SFVTPV
+            0x0E => {
+                self.state.freedom_vector = self.state.projection_vector;
+                self.update_vectors();
+            }
+            // ISECT
+            0x0F => self.intersect(),
+            // SRP0, SRP1, SRP2
+            0x10..=0x12 => {
+                self.state.reference_points[opcode as usize - 0x10] = self.argument(0) as u16
+            }
+            // SZP0, SZP1, SZP2, SZPS
+            0x13..=0x16 => {
+                let value = match self.argument(0) {
+                    value @ (0 | 1) => value as u8,
+                    _ => return Ok(()),
+                };
+                match opcode {
+                    0x16 => self.state.zone_pointers = [value; 3],
+                    _ => self.state.zone_pointers[opcode as usize - 0x13] = value,
+                }
+            }
+            // SLOOP
+            0x17 => match self.argument(0) {
+                value if value < 0 => raise!("found a negative loop count in hinting instructions"),
+                value => self.state.loop_count = value.min(0xFFFF),
+            },
+            // RTG, RTHG
+            0x18 => self.state.round_state = 1,
+            0x19 => self.state.round_state = 0,
+            // SMD
+            0x1A => self.state.minimum_distance = self.argument(0),
+            // ELSE
+            0x1B => {
+                let mut depth = 1;
+                while depth > 0 {
+                    self.skip()?;
+                    match self.opcode {
+                        0x58 => depth += 1,
+                        0x59 => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            // JMPR
+            0x1C => self.jump(self.argument(0))?,
+            // SCVTCI, SSWCI, SSW
+            0x1D => self.state.control_value_cutin = self.argument(0),
+            0x1E => self.state.single_width_cutin = self.argument(0),
+            0x1F => {
+                self.state.single_width_value = math::multiply_fixed(self.argument(0), self.scale)
+            }
+            // DUP
+            0x20 => self.set(1, self.argument(0)),
+            // POP
+            0x21 => {}
+            // CLEAR
+            0x22 => self.new_top = 0,
+            // SWAP
+            0x23 => {
+                let (a, b) = (self.argument(0), self.argument(1));
+                self.set(0, b);
+                self.set(1, a);
+            }
+            // DEPTH
+            0x24 => self.set(0, self.top as i32),
+            // CINDEX
+            0x25 => {
+                let index = self.argument(0);
+                let value = match index {
+                    index if index <= 0 || index as usize > self.args => 0,
+                    index => self.stack[self.args - index as usize],
+                };
+                self.set(0, value);
+            }
+            // MINDEX
+            0x26 => {
+                let index = self.argument(0);
+                if index > 0 && index as usize <= self.args {
+                    let index = self.args - index as usize;
+                    self.stack[index..self.args].rotate_left(1);
+                }
+            }
+            // ALIGNPTS
+            0x27 => {
+                let first = self.argument(0) as u16 as usize;
+                let second = self.argument(1) as u16 as usize;
+                if first >= self.zone(1).len() || second >= self.zone(0).len() {
+                    return Ok(());
+                }
+                let distance =
+                    self.project(self.zone(0).current[second], self.zone(1).current[first]) / 2;
+                self.move_point(1, first, distance);
+                self.move_point(0, second, distance.wrapping_neg());
+            }
+            // UTP
+            0x29 => {
+                let point = self.argument(0) as u16 as usize;
+                if point >= self.zone(0).len() {
+                    return Ok(());
+                }
+                let mut mask = 0xFF;
+                if self.state.freedom_vector.0 != 0 {
+                    mask &= !TOUCHED_X;
+                }
+                if self.state.freedom_vector.1 != 0 {
+                    mask &= !TOUCHED_Y;
+                }
+                self.zone_mut(0).flags[point] &= mask;
+            }
+            // LOOPCALL, CALL
+            0x2A | 0x2B => {
+                let (number, count) = match opcode {
+                    0x2A => (self.argument(1), self.argument(0)),
+                    _ => (self.argument(0), 1),
+                };
+                let definition = match self.find_function(number) {
+                    Some(definition) => definition,
+                    _ => raise!("found an undefined function in hinting instructions"),
+                };
+                if self.calls.len() >= MAXIMUM_CALL_DEPTH {
+                    raise!("found too deeply nested functions in hinting instructions");
+                }
+                if count > 0 {
+                    self.calls.push(Call {
+                        range: self.range,
+                        ip: self.ip + 1,
+                        count,
+                        definition,
+                    });
+                    self.range = definition.range;
+                    self.ip = definition.start;
+                    self.step = false;
+                    if opcode == 0x2A {
+                        self.loop_calls.0 += count as usize;
+                        if self.loop_calls.0 > self.loop_calls.1 {
+                            raise!("found too many function calls in hinting instructions");
+                        }
+                    }
+                }
+            }
+            // FDEF
+            0x2C => self.define_function()?,
+            // ENDF
+            0x2D => {
+                let mut call = match self.calls.pop() {
+                    Some(call) => call,
+                    _ => raise!("found an unexpected end of a function in hinting instructions"),
+                };
+                call.count -= 1;
+                self.step = false;
+                if call.count > 0 {
+                    self.ip = call.definition.start;
+                    self.calls.push(call);
+                } else {
+                    self.range = call.range;
+                    self.ip = call.ip;
+                }
+            }
+            // MDAP
+            0x2E | 0x2F => {
+                let point = self.argument(0) as u16;
+                if point as usize >= self.zone(0).len() {
+                    raise!("found an invalid point in hinting instructions");
+                }
+                let distance = if opcode & 1 != 0 {
+                    let current = self.project_point(self.zone(0).current[point as usize]);
+                    self.round(current).wrapping_sub(current)
+                } else {
+                    0
+                };
+                self.move_point(0, point as usize, distance);
+                self.state.reference_points[0] = point;
+                self.state.reference_points[1] = point;
+            }
+            // IUP
+            0x30 | 0x31 => self.interpolate_untouched(opcode & 1 != 0),
+            // SHP
+            0x32 | 0x33 => {
+                if (self.top as i32) < self.state.loop_count {
+                    self.state.loop_count = 1;
+                    return Ok(());
+                }
+                let (dx, dy, _, _) = match self.displacement() {
+                    Some(value) => value,
+                    _ => return Ok(()),
+                };
+                while self.state.loop_count > 0 {
+                    self.args -= 1;
+                    let point = self.stack[self.args] as u16 as usize;
+                    if point < self.zone(2).len() {
+                        self.shift_point(point, dx, dy, true);
+                    }
+                    self.state.loop_count -= 1;
+                }
+                self.state.loop_count = 1;
+                self.new_top = self.args;
+            }
+            // SHC
+            0x34 | 0x35 => {
+                let contour = self.argument(0) as i16;
+                let zone = self.zone(2);
+                let twilight = self.state.zone_pointers[2] == 0;
+                let count = if twilight { 1 } else { zone.end_points.len() };
+                if contour < 0 || contour as usize >= count {
+                    return Ok(());
+                }
+                let contour = contour as usize;
+                let start = match contour {
+                    0 => 0,
+                    _ => zone.end_points[contour - 1] + 1,
+                };
+                let end = if twilight {
+                    zone.len()
+                } else {
+                    zone.end_points[contour] + 1
+                };
+                let (dx, dy, pointer, reference) = match self.displacement() {
+                    Some(value) => value,
+                    _ => return Ok(()),
+                };
+                let same = self.state.zone_pointers[pointer] == self.state.zone_pointers[2];
+                for point in start..end {
+                    if !same || reference != point {
+                        self.shift_point(point, dx, dy, true);
+                    }
+                }
+            }
+            // SHZ
+            0x36 | 0x37 => {
+                if !(0..2).contains(&self.argument(0)) {
+                    return Ok(());
+                }
+                let (dx, dy, pointer, reference) = match self.displacement() {
+                    Some(value) => value,
+                    _ => return Ok(()),
+                };
+                let zone = self.zone(2);
+                let end = match self.state.zone_pointers[2] {
+                    0 => zone.len(),
+                    _ => zone.end_points.last().map_or(0, |&point| point + 1),
+                };
+                let same = self.state.zone_pointers[pointer] == self.state.zone_pointers[2];
+                for point in 0..end {
+                    if !same || reference != point {
+                        self.shift_point(point, dx, dy, false);
+                    }
+                }
+            }
+            // SHPIX
+            0x38 => {
+                if (self.top as i32) < self.state.loop_count + 1 {
+                    self.state.loop_count = 1;
+                    self.new_top = self.args;
+                    return Ok(());
+                }
+                let distance = self.argument(0);
+                let dx = math::multiply_14(distance, self.state.freedom_vector.0);
+                let dy = math::multiply_14(distance, self.state.freedom_vector.1);
+                let twilight = self.state.zone_pointers.contains(&0);
+                while self.state.loop_count > 0 {
+                    self.args -= 1;
+                    let point = self.stack[self.args] as u16 as usize;
+                    if point < self.zone(2).len() {
+                        if self.backward_compatibility {
+                            let touched = self.zone(2).flags[point] & TOUCHED_Y != 0;
+                            if twilight
+                                || (!self.post_iup()
+                                    && ((self.is_composite && self.state.freedom_vector.1 != 0)
+                                        || touched))
+                            {
+                                self.shift_point(point, 0, dy, true);
+                            }
+                        } else {
+                            self.shift_point(point, dx, dy, true);
+                        }
+                    }
+                    self.state.loop_count -= 1;
+                }
+                self.state.loop_count = 1;
+                self.new_top = self.args;
+            }
+            // IP
+            0x39 => self.interpolate_point(),
+            // MSIRP
+            0x3A | 0x3B => {
+                let point = self.argument(0) as u16 as usize;
+                let reference = self.state.reference_points[0] as usize;
+                if point >= self.zone(1).len() || reference >= self.zone(0).len() {
+                    return Ok(());
+                }
+                let distance = self.argument(1);
+                if self.state.zone_pointers[1] == 0 {
+                    let original = self.zone(0).original[reference];
+                    self.zone_mut(1).original[point] = original;
+                    self.move_original(1, point, distance);
+                    let original = self.zone(1).original[point];
+                    self.zone_mut(1).current[point] = original;
+                }
+                let current =
+                    self.project(self.zone(1).current[point], self.zone(0).current[reference]);
+                self.move_point(1, point, distance.wrapping_sub(current));
+                self.state.reference_points[1] = self.state.reference_points[0];
+                self.state.reference_points[2] = point as u16;
+                if opcode & 1 != 0 {
+                    self.state.reference_points[0] = point as u16;
+                }
+            }
+            // ALIGNRP
+            0x3C => {
+                let reference = self.state.reference_points[0] as usize;
+                if (self.top as i32) < self.state.loop_count || reference >= self.zone(0).len() {
+                    self.state.loop_count = 1;
+                    self.new_top = self.args;
+                    return Ok(());
+                }
+                while self.state.loop_count > 0 {
+                    self.args -= 1;
+                    let point = self.stack[self.args] as u16 as usize;
+                    if point < self.zone(1).len() {
+                        let distance = self
+                            .project(self.zone(1).current[point], self.zone(0).current[reference]);
+                        self.move_point(1, point, distance.wrapping_neg());
+                    }
+                    self.state.loop_count -= 1;
+                }
+                self.state.loop_count = 1;
+                self.new_top = self.args;
+            }
+            // RTDG
+            0x3D => self.state.round_state = 2,
+            // MIAP
+            0x3E | 0x3F => {
+                let point = self.argument(0) as u16;
+                let entry = self.argument(1) as u32 as usize;
+                if point as usize >= self.zone(0).len() || entry >= self.control_values.len() {
+                    self.state.reference_points[0] = point;
+                    self.state.reference_points[1] = point;
+                    return Ok(());
+                }
+                let mut distance = self.control_values[entry];
+                if self.state.zone_pointers[0] == 0 {
+                    let value = Point {
+                        x: math::multiply_14(distance, self.state.freedom_vector.0),
+                        y: math::multiply_14(distance, self.state.freedom_vector.1),
+                    };
+                    let zone = self.zone_mut(0);
+                    zone.original[point as usize] = value;
+                    zone.current[point as usize] = value;
+                }
+                let original = self.project_point(self.zone(0).current[point as usize]);
+                if opcode & 1 != 0 {
+                    if distance.wrapping_sub(original).wrapping_abs()
+                        > self.state.control_value_cutin
+                    {
+                        distance = original;
+                    }
+                    distance = self.round(distance);
+                }
+                self.move_point(0, point as usize, distance.wrapping_sub(original));
+                self.state.reference_points[0] = point;
+                self.state.reference_points[1] = point;
+            }
+            // NPUSHB, NPUSHW, PUSHB, PUSHW
+            0x40 | 0x41 | 0xB0..=0xBF => {
+                let code = &self.programs[self.range as usize];
+                let (count, start, words) = match opcode {
+                    0x40 | 0x41 => (code[self.ip + 1] as usize, self.ip + 2, opcode == 0x41),
+                    0xB0..=0xB7 => ((opcode - 0xAF) as usize, self.ip + 1, false),
+                    _ => ((opcode - 0xB7) as usize, self.ip + 1, true),
+                };
+                if opcode < 0xB0 {
+                    if self.args + count > self.stack.len() {
+                        raise!("found a stack overflow in hinting instructions");
+                    }
+                    self.new_top += count;
+                }
+                for index in 0..count {
+                    self.stack[self.args + index] = if words {
+                        i16::from_be_bytes([code[start + 2 * index], code[start + 2 * index + 1]])
+                            as i32
+                    } else {
+                        code[start + index] as i32
+                    };
+                }
+            }
+            // WS
+            0x42 => {
+                let index = self.argument(0) as u32 as usize;
+                if index < self.storage.len() {
+                    self.storage[index] = self.argument(1);
+                }
+            }
+            // RS
+            0x43 => {
+                let index = self.argument(0) as u32 as usize;
+                self.set(0, self.storage.get(index).copied().unwrap_or(0));
+            }
+            // WCVTP
+            0x44 => {
+                let index = self.argument(0) as u32 as usize;
+                if index < self.control_values.len() {
+                    self.control_values[index] = self.argument(1);
+                }
+            }
+            // RCVT
+            0x45 => {
+                let index = self.argument(0) as u32 as usize;
+                self.set(0, self.control_values.get(index).copied().unwrap_or(0));
+            }
+            // GC
+            0x46 | 0x47 => {
+                let point = self.argument(0) as u32 as usize;
+                let value = if point >= self.zone(2).len() {
+                    0
+                } else if opcode & 1 != 0 {
+                    self.project_dual_point(self.zone(2).original[point])
+                } else {
+                    self.project_point(self.zone(2).current[point])
+                };
+                self.set(0, value);
+            }
+            // SCFS
+            0x48 => {
+                let point = self.argument(0) as u16 as usize;
+                if point >= self.zone(2).len() {
+                    return Ok(());
+                }
+                let current = self.project_point(self.zone(2).current[point]);
+                self.move_point(2, point, self.argument(1).wrapping_sub(current));
+                if self.state.zone_pointers[2] == 0 {
+                    let zone = self.zone_mut(2);
+                    zone.original[point] = zone.current[point];
+                }
+            }
+            // MD
+            0x49 | 0x4A => {
+                let second = self.argument(1) as u16 as usize;
+                let first = self.argument(0) as u16 as usize;
+                let value = if first >= self.zone(0).len() || second >= self.zone(1).len() {
+                    0
+                } else if opcode & 1 != 0 {
+                    self.project(self.zone(0).current[first], self.zone(1).current[second])
+                } else if self.state.zone_pointers[0] == 0 || self.state.zone_pointers[1] == 0 {
+                    self.project_dual(self.zone(0).original[first], self.zone(1).original[second])
+                } else {
+                    let value = self
+                        .project_dual(self.zone(0).unscaled[first], self.zone(1).unscaled[second]);
+                    math::multiply_fixed(value, self.unscaled_scale)
+                };
+                self.set(0, value);
+            }
+            // MPPEM
+            0x4B => self.set(0, self.ppem),
+            // MPS
+            0x4C => self.set(0, self.point_size),
+            // FLIPON, FLIPOFF
+            0x4D => self.state.auto_flip = true,
+            0x4E => self.state.auto_flip = false,
+            // LT, LTEQ, GT, GTEQ, EQ, NEQ
+            0x50..=0x55 => {
+                let (a, b) = (self.argument(0), self.argument(1));
+                let value = match opcode {
+                    0x50 => a < b,
+                    0x51 => a <= b,
+                    0x52 => a > b,
+                    0x53 => a >= b,
+                    0x54 => a == b,
+                    _ => a != b,
+                };
+                self.set(0, value as i32);
+            }
+            // ODD, EVEN
+            0x56 | 0x57 => {
+                let value = self.round(self.argument(0)) & 127;
+                self.set(0, (value == if opcode == 0x56 { 64 } else { 0 }) as i32);
+            }
+            // IF
+            0x58 => {
+                if self.argument(0) != 0 {
+                    return Ok(());
+                }
+                let mut depth = 1;
+                loop {
+                    self.skip()?;
+                    match self.opcode {
+                        0x58 => depth += 1,
+                        0x1B if depth == 1 => break,
+                        0x59 => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // EIF
+            0x59 => {}
+            // AND, OR, NOT
+            0x5A => self.set(0, (self.argument(0) != 0 && self.argument(1) != 0) as i32),
+            0x5B => self.set(0, (self.argument(0) != 0 || self.argument(1) != 0) as i32),
+            0x5C => self.set(0, (self.argument(0) == 0) as i32),
+            // DELTAP1, DELTAP2, DELTAP3
+            0x5D | 0x71 | 0x72 => self.delta_point(),
+            // SDB, SDS
+            0x5E => self.state.delta_base = self.argument(0) as u16,
+            0x5F => match self.argument(0) as u32 {
+                value if value > 6 => {
+                    raise!("found an invalid delta shift in hinting instructions")
+                }
+                value => self.state.delta_shift = value as u16,
+            },
+            // ADD, SUB, DIV, MUL
+            0x60 => self.set(0, self.argument(0).wrapping_add(self.argument(1))),
+            0x61 => self.set(0, self.argument(0).wrapping_sub(self.argument(1))),
+            0x62 => match self.argument(1) {
+                0 => raise!("found a division by zero in hinting instructions"),
+                value => self.set(
+                    0,
+                    math::multiply_divide_truncating(self.argument(0), 64, value),
+                ),
+            },
+            0x63 => self.set(
+                0,
+                math::multiply_divide(self.argument(0), self.argument(1), 64),
+            ),
+            // ABS, NEG, FLOOR, CEILING
+            0x64 => self.set(0, self.argument(0).wrapping_abs()),
+            0x65 => self.set(0, self.argument(0).wrapping_neg()),
+            0x66 => self.set(0, math::floor(self.argument(0))),
+            0x67 => self.set(0, math::ceil(self.argument(0))),
+            // ROUND
+            0x68..=0x6B => self.set(0, self.round(self.argument(0))),
+            // NROUND
+            0x6C..=0x6F => {}
+            // WCVTF
+            0x70 => {
+                let index = self.argument(0) as u32 as usize;
+                if index < self.control_values.len() {
+                    self.control_values[index] = math::multiply_fixed(self.argument(1), self.scale);
+                }
+            }
+            // DELTAC1, DELTAC2, DELTAC3
+            0x73..=0x75 => self.delta_control_value(),
+            // SROUND, S45ROUND
+            0x76 | 0x77 => {
+                let period = if opcode == 0x76 { 0x4000 } else { 0x2D41 };
+                self.set_super_round(period, self.argument(0));
+                self.state.round_state = opcode - 0x70;
+            }
+            // JROT, JROF
+            0x78 | 0x79 => {
+                if (self.argument(1) != 0) == (opcode == 0x78) {
+                    self.jump(self.argument(0))?;
+                }
+            }
+            // ROFF, RUTG, RDTG
+            0x7A => self.state.round_state = 5,
+            0x7C => self.state.round_state = 4,
+            0x7D => self.state.round_state = 3,
+            // SANGW, AA
+            0x7E | 0x7F => {}
+            // FLIPPT
+            0x80 => {
+                if self.backward_compatibility && self.post_iup() {
+                    self.state.loop_count = 1;
+                    self.new_top = self.args;
+                    return Ok(());
+                }
+                if (self.top as i32) < self.state.loop_count {
+                    self.state.loop_count = 1;
+                    self.new_top = self.args;
+                    return Ok(());
+                }
+                while self.state.loop_count > 0 {
+                    self.args -= 1;
+                    let point = self.stack[self.args] as u16 as usize;
+                    if point < self.glyph.len() {
+                        self.glyph.flags[point] ^= ON_CURVE;
+                    }
+                    self.state.loop_count -= 1;
+                }
+                self.state.loop_count = 1;
+                self.new_top = self.args;
+            }
+            // FLIPRGON, FLIPRGOFF
+            0x81 | 0x82 => {
+                if self.backward_compatibility && self.post_iup() {
+                    return Ok(());
+                }
+                let end = self.argument(1) as u16 as usize;
+                let start = self.argument(0) as u16 as usize;
+                if end >= self.glyph.len() || start >= self.glyph.len() {
+                    return Ok(());
+                }
+                for point in start..=end {
+                    match opcode {
+                        0x81 => self.glyph.flags[point] |= ON_CURVE,
+                        _ => self.glyph.flags[point] &= !ON_CURVE,
+                    }
+                }
+            }
+            // SCANCTRL
+            0x85 => {
+                let value = self.argument(0);
+                let threshold = value & 0xFF;
+                if threshold == 0xFF {
+                    self.state.scan_control = true;
+                } else if threshold == 0 {
+                    self.state.scan_control = false;
+                } else {
+                    if value & 0x100 != 0 && self.ppem <= threshold {
+                        self.state.scan_control = true;
+                    }
+                    if value & 0x800 != 0 && self.ppem > threshold {
+                        self.state.scan_control = false;
+                    }
+                }
+            }
+            // SDPVTL
+            0x86 | 0x87 => {
+                let first = self.argument(1) as u32 as usize;
+                let second = self.argument(0) as u32 as usize;
+                if first >= self.zone(2).len() || second >= self.zone(1).len() {
+                    return Ok(());
+                }
+                let mut rotate = opcode & 1 != 0;
+                let mut vector = |a: Point, b: Point| {
+                    let (mut x, mut y) = (a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y));
+                    if x == 0 && y == 0 {
+                        x = 0x4000;
+                        rotate = false;
+                    }
+                    if rotate {
+                        (x, y) = (y.wrapping_neg(), x);
+                    }
+                    math::normalize(x, y)
+                };
+                let original = vector(self.zone(1).original[second], self.zone(2).original[first]);
+                let current = vector(self.zone(1).current[second], self.zone(2).current[first]);
+                if let Some(value) = original {
+                    self.state.dual_vector = value;
+                }
+                if let Some(value) = current {
+                    self.state.projection_vector = value;
+                }
+                self.update_vectors();
+            }
+            // GETINFO
+            0x88 => {
+                let selector = self.argument(0);
+                let mut value = 0;
+                if selector & 1 != 0 {
+                    value = 40;
+                }
+                if self.subpixel {
+                    if selector & 64 != 0 {
+                        value |= 1 << 13;
+                    }
+                    if selector & 1024 != 0 {
+                        value |= 1 << 17;
+                    }
+                    if selector & 2048 != 0 {
+                        value |= 1 << 18;
+                    }
+                    if selector & 4096 != 0 {
+                        value |= 1 << 19;
+                    }
+                }
+                self.set(0, value);
+            }
+            // IDEF
+            0x89 => self.define_instruction()?,
+            // ROLL
+            0x8A => {
+                let (a, b, c) = (self.argument(2), self.argument(1), self.argument(0));
+                self.set(2, c);
+                self.set(1, a);
+                self.set(0, b);
+            }
+            // MAX, MIN
+            0x8B => self.set(0, self.argument(0).max(self.argument(1))),
+            0x8C => self.set(0, self.argument(0).min(self.argument(1))),
+            // SCANTYPE
+            0x8D => {
+                if self.argument(0) >= 0 {
+                    self.state.scan_type = self.argument(0) & 0xFFFF;
+                }
+            }
+            // INSTCTRL
+            0x8E => {
+                let selector = self.argument(1);
+                let value = self.argument(0);
+                if !(1..=3).contains(&selector) {
+                    return Ok(());
+                }
+                let flag = 1u8 << (selector - 1);
+                if value != 0 && value != flag as i32 {
+                    return Ok(());
+                }
+                let value = value as u8;
+                if self.initial_range == Range::ControlValue {
+                    self.state.instruct_control &= !flag;
+                    self.state.instruct_control |= value;
+                } else if self.initial_range == Range::Glyph && selector == 3 {
+                    self.backward_compatibility = value != 4;
+                }
+            }
+            // MDRP
+            0xC0..=0xDF => self.move_direct_relative_point(),
+            // MIRP
+            0xE0..=0xFF => self.move_indirect_relative_point(),
+            _ => {
+                let definition = self
+                    .instructions
+                    .iter()
+                    .find(|definition| definition.number == opcode as u32)
+                    .copied();
+                match definition {
+                    Some(definition) => {
+                        if self.calls.len() >= MAXIMUM_CALL_DEPTH {
+                            raise!("found too deeply nested functions in hinting instructions");
+                        }
+                        self.calls.push(Call {
+                            range: self.range,
+                            ip: self.ip + 1,
+                            count: 1,
+                            definition,
+                        });
+                        self.range = definition.range;
+                        self.ip = definition.start;
+                        self.step = false;
+                    }
+                    _ => raise!("found an unknown hinting instruction {opcode:#04x}"),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Machine {
+    #[inline]
+    fn argument(&self, index: usize) -> i32 {
+        self.stack[self.args + index]
+    }
+
+    #[inline]
+    fn set(&mut self, index: usize, value: i32) {
+        self.stack[self.args + index] = value;
+    }
+
+    #[inline]
+    fn zone(&self, pointer: usize) -> &Zone {
+        match self.state.zone_pointers[pointer] {
+            0 => &self.twilight,
+            _ => &self.glyph,
+        }
+    }
+
+    #[inline]
+    fn zone_mut(&mut self, pointer: usize) -> &mut Zone {
+        match self.state.zone_pointers[pointer] {
+            0 => &mut self.twilight,
+            _ => &mut self.glyph,
+        }
+    }
+
+    #[inline]
+    fn post_iup(&self) -> bool {
+        self.iup_called.0 && self.iup_called.1
+    }
+
+    fn jump(&mut self, offset: i32) -> Result<()> {
+        if offset == 0 && self.args == 0 {
+            raise!("found an infinite loop in hinting instructions");
+        }
+        let ip = self.ip as i64 + offset as i64;
+        let end = self.calls.last().map(|call| call.definition.end as i64);
+        if ip < 0 || end.is_some_and(|end| ip > end) {
+            raise!("found an invalid jump in hinting instructions");
+        }
+        self.ip = ip as usize;
+        self.step = false;
+        if offset < 0 {
+            self.negative_jumps.0 += 1;
+            if self.negative_jumps.0 > self.negative_jumps.1 {
+                raise!("found too many backward jumps in hinting instructions");
+            }
+        }
+        Ok(())
+    }
+
+    fn find_function(&self, number: i32) -> Option<Definition> {
+        let number = number as u32;
+        if number > self.maximum_function {
+            return None;
+        }
+        self.functions
+            .iter()
+            .find(|definition| definition.number == number)
+            .copied()
+    }
+
+    fn define_function(&mut self) -> Result<()> {
+        if self.initial_range == Range::Glyph {
+            raise!("found a function definition in glyph instructions");
+        }
+        let number = self.argument(0) as u32;
+        let index = match self
+            .functions
+            .iter()
+            .position(|value| value.number == number)
+        {
+            Some(index) => index,
+            _ if self.functions.len() >= self.function_limit => {
+                raise!("found too many function definitions")
+            }
+            _ => {
+                self.functions.push(Definition {
+                    number,
+                    range: self.range,
+                    start: 0,
+                    end: 0,
+                });
+                self.functions.len() - 1
+            }
+        };
+        if number > 0xFFFF {
+            raise!("found too many function definitions");
+        }
+        self.functions[index].range = self.range;
+        self.functions[index].start = self.ip + 1;
+        self.maximum_function = self.maximum_function.max(number);
+        self.functions[index].end = self.skip_definition()?;
+        Ok(())
+    }
+
+    fn define_instruction(&mut self) -> Result<()> {
+        if self.initial_range == Range::Glyph {
+            raise!("found an instruction definition in glyph instructions");
+        }
+        let number = self.argument(0) as u32;
+        let index = match self
+            .instructions
+            .iter()
+            .position(|value| value.number == number)
+        {
+            Some(index) => index,
+            _ if self.instructions.len() >= self.instruction_limit => {
+                raise!("found too many instruction definitions")
+            }
+            _ => {
+                self.instructions.push(Definition {
+                    number,
+                    range: self.range,
+                    start: 0,
+                    end: 0,
+                });
+                self.instructions.len() - 1
+            }
+        };
+        if number > 0xFF {
+            raise!("found too many instruction definitions");
+        }
+        self.instructions[index].range = self.range;
+        self.instructions[index].start = self.ip + 1;
+        self.instructions[index].end = self.skip_definition()?;
+        Ok(())
+    }
+
+    fn skip_definition(&mut self) -> Result<usize> {
+        loop {
+            self.skip()?;
+            match self.opcode {
+                0x2C | 0x89 => raise!("found nested definitions in hinting instructions"),
+                0x2D => return Ok(self.ip),
+                _ => {}
+            }
+        }
+    }
+
+    // Moves along an axis ignore the other component of the freedom vector.
+    fn freedom_vector(&self) -> (i32, i32) {
+        match self.state.freedom_vector {
+            (0x4000, _) if self.f_dot_p == 0x4000 => (0x4000, 0),
+            (_, 0x4000) if self.f_dot_p == 0x4000 => (0, 0x4000),
+            vector => vector,
+        }
+    }
+
+    fn update_vectors(&mut self) {
+        let (fx, fy) = self.state.freedom_vector;
+        let (px, py) = self.state.projection_vector;
+        self.f_dot_p = if fx == 0x4000 {
+            px
+        } else if fy == 0x4000 {
+            py
+        } else {
+            ((px as i64 * fx as i64 + py as i64 * fy as i64) >> 14) as i32
+        };
+        if self.f_dot_p.abs() < 0x400 {
+            self.f_dot_p = 0x4000;
+        }
+    }
+
+    fn vector_to_line(&self, first: usize, second: usize) -> Option<(i32, i32)> {
+        if first >= self.zone(2).len() || second >= self.zone(1).len() {
+            return None;
+        }
+        let a = self.zone(1).current[second];
+        let b = self.zone(2).current[first];
+        let (mut x, mut y) = (a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y));
+        if x == 0 && y == 0 {
+            x = 0x4000;
+        } else if self.opcode & 1 != 0 {
+            (x, y) = (y.wrapping_neg(), x);
+        }
+        math::normalize(x, y)
+    }
+
+    fn project_vector(vector: (i32, i32), dx: i32, dy: i32) -> i32 {
+        if vector.0 == 0x4000 {
+            dx
+        } else if vector.1 == 0x4000 {
+            dy
+        } else {
+            math::dot_14((dx, dy), vector)
+        }
+    }
+
+    #[inline]
+    fn project(&self, a: Point, b: Point) -> i32 {
+        let vector = self.state.projection_vector;
+        Self::project_vector(vector, a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y))
+    }
+
+    #[inline]
+    fn project_point(&self, a: Point) -> i32 {
+        Self::project_vector(self.state.projection_vector, a.x, a.y)
+    }
+
+    #[inline]
+    fn project_dual(&self, a: Point, b: Point) -> i32 {
+        let vector = self.state.dual_vector;
+        Self::project_vector(vector, a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y))
+    }
+
+    #[inline]
+    fn project_dual_point(&self, a: Point) -> i32 {
+        Self::project_vector(self.state.dual_vector, a.x, a.y)
+    }
+
+    fn move_point(&mut self, pointer: usize, point: usize, distance: i32) {
+        let (fx, fy) = self.freedom_vector();
+        let f_dot_p = self.f_dot_p;
+        let blocked = self.backward_compatibility && self.post_iup();
+        let backward_compatibility = self.backward_compatibility;
+        let zone = self.zone_mut(pointer);
+        if fx != 0 {
+            if !backward_compatibility {
+                let value = math::multiply_divide(distance, fx, f_dot_p);
+                zone.current[point].x = zone.current[point].x.wrapping_add(value);
+            }
+            zone.flags[point] |= TOUCHED_X;
+        }
+        if fy != 0 {
+            if !blocked {
+                let value = math::multiply_divide(distance, fy, f_dot_p);
+                zone.current[point].y = zone.current[point].y.wrapping_add(value);
+            }
+            zone.flags[point] |= TOUCHED_Y;
+        }
+    }
+
+    fn move_original(&mut self, pointer: usize, point: usize, distance: i32) {
+        let (fx, fy) = self.freedom_vector();
+        let f_dot_p = self.f_dot_p;
+        let zone = self.zone_mut(pointer);
+        if fx != 0 {
+            let value = math::multiply_divide(distance, fx, f_dot_p);
+            zone.original[point].x = zone.original[point].x.wrapping_add(value);
+        }
+        if fy != 0 {
+            let value = math::multiply_divide(distance, fy, f_dot_p);
+            zone.original[point].y = zone.original[point].y.wrapping_add(value);
+        }
+    }
+
+    fn shift_point(&mut self, point: usize, dx: i32, dy: i32, touch: bool) {
+        let (fx, fy) = self.state.freedom_vector;
+        let blocked = self.backward_compatibility && self.post_iup();
+        let backward_compatibility = self.backward_compatibility;
+        let zone = self.zone_mut(2);
+        if fx != 0 {
+            if !backward_compatibility {
+                zone.current[point].x = zone.current[point].x.wrapping_add(dx);
+            }
+            if touch {
+                zone.flags[point] |= TOUCHED_X;
+            }
+        }
+        if fy != 0 {
+            if !blocked {
+                zone.current[point].y = zone.current[point].y.wrapping_add(dy);
+            }
+            if touch {
+                zone.flags[point] |= TOUCHED_Y;
+            }
+        }
+    }
+
+    fn displacement(&self) -> Option<(i32, i32, usize, usize)> {
+        let (pointer, reference) = match self.opcode & 1 {
+            0 => (1, self.state.reference_points[2] as usize),
+            _ => (0, self.state.reference_points[1] as usize),
+        };
+        let zone = self.zone(pointer);
+        if reference >= zone.len() {
+            return None;
+        }
+        let distance = self.project(zone.current[reference], zone.original[reference]);
+        let dx = math::multiply_divide(distance, self.state.freedom_vector.0, self.f_dot_p);
+        let dy = math::multiply_divide(distance, self.state.freedom_vector.1, self.f_dot_p);
+        Some((dx, dy, pointer, reference))
+    }
+
+    fn round(&self, distance: i32) -> i32 {
+        let positive = |value: i32, minimum: i32| if value < 0 { minimum } else { value };
+        let negative = |value: i32, maximum: i32| if value > 0 { maximum } else { value };
+        match self.state.round_state {
+            0 if distance >= 0 => positive(math::floor(distance).wrapping_add(32), 32),
+            0 => negative(
+                math::floor(distance.wrapping_neg())
+                    .wrapping_add(32)
+                    .wrapping_neg(),
+                -32,
+            ),
+            1 if distance >= 0 => positive(math::round(distance), 0),
+            1 => negative(math::round(distance.wrapping_neg()).wrapping_neg(), 0),
+            2 if distance >= 0 => positive(distance.wrapping_add(16) & -32, 0),
+            2 => negative(
+                (distance.wrapping_neg().wrapping_add(16) & -32).wrapping_neg(),
+                0,
+            ),
+            3 if distance >= 0 => positive(math::floor(distance), 0),
+            3 => negative(math::floor(distance.wrapping_neg()).wrapping_neg(), 0),
+            4 if distance >= 0 => positive(math::ceil(distance), 0),
+            4 => negative(math::ceil(distance.wrapping_neg()).wrapping_neg(), 0),
+            6 if distance >= 0 => {
+                let value = distance.wrapping_add(self.threshold - self.phase) & -self.period;
+                positive(value.wrapping_add(self.phase), self.phase)
+            }
+            6 => {
+                let value = ((self.threshold - self.phase).wrapping_sub(distance) & -self.period)
+                    .wrapping_neg();
+                negative(value.wrapping_sub(self.phase), -self.phase)
+            }
+            7 if distance >= 0 => {
+                let value =
+                    distance.wrapping_add(self.threshold - self.phase) / self.period * self.period;
+                positive(value.wrapping_add(self.phase), self.phase)
+            }
+            7 => {
+                let value = ((self.threshold - self.phase).wrapping_sub(distance) / self.period
+                    * self.period)
+                    .wrapping_neg();
+                negative(value.wrapping_sub(self.phase), -self.phase)
+            }
+            _ => distance,
+        }
+    }
+
+    fn set_super_round(&mut self, period: i32, selector: i32) {
+        self.period = match selector & 0xC0 {
+            0x00 => period / 2,
+            0x80 => period * 2,
+            _ => period,
+        };
+        self.phase = match selector & 0x30 {
+            0x00 => 0,
+            0x10 => self.period / 4,
+            0x20 => self.period / 2,
+            _ => self.period * 3 / 4,
+        };
+        self.threshold = match selector & 0x0F {
+            0 => self.period - 1,
+            value => (value - 4) * self.period / 8,
+        };
+        self.period >>= 8;
+        self.phase >>= 8;
+        self.threshold >>= 8;
+    }
+
+    fn intersect(&mut self) {
+        let point = self.argument(0) as u16 as usize;
+        let a0 = self.argument(1) as u16 as usize;
+        let a1 = self.argument(2) as u16 as usize;
+        let b0 = self.argument(3) as u16 as usize;
+        let b1 = self.argument(4) as u16 as usize;
+        if b0 >= self.zone(0).len()
+            || b1 >= self.zone(0).len()
+            || a0 >= self.zone(1).len()
+            || a1 >= self.zone(1).len()
+            || point >= self.zone(2).len()
+        {
+            return;
+        }
+        let (pa0, pa1) = (self.zone(1).current[a0], self.zone(1).current[a1]);
+        let (pb0, pb1) = (self.zone(0).current[b0], self.zone(0).current[b1]);
+        let (dbx, dby) = (pb1.x.wrapping_sub(pb0.x), pb1.y.wrapping_sub(pb0.y));
+        let (dax, day) = (pa1.x.wrapping_sub(pa0.x), pa1.y.wrapping_sub(pa0.y));
+        let (dx, dy) = (pb0.x.wrapping_sub(pa0.x), pb0.y.wrapping_sub(pa0.y));
+        let discriminant = math::multiply_divide(dax, dby.wrapping_neg(), 0x40)
+            .wrapping_add(math::multiply_divide(day, dbx, 0x40));
+        let product = math::multiply_divide(dax, dbx, 0x40)
+            .wrapping_add(math::multiply_divide(day, dby, 0x40));
+        let value = if 19 * (discriminant as i64).abs() > (product as i64).abs() {
+            let value = math::multiply_divide(dx, dby.wrapping_neg(), 0x40)
+                .wrapping_add(math::multiply_divide(dy, dbx, 0x40));
+            Point {
+                x: pa0
+                    .x
+                    .wrapping_add(math::multiply_divide(value, dax, discriminant)),
+                y: pa0
+                    .y
+                    .wrapping_add(math::multiply_divide(value, day, discriminant)),
+            }
+        } else {
+            let sum = |a: i32, b: i32, c: i32, d: i32| {
+                a.wrapping_add(b).wrapping_add(c.wrapping_add(d)) / 4
+            };
+            Point {
+                x: sum(pa0.x, pa1.x, pb0.x, pb1.x),
+                y: sum(pa0.y, pa1.y, pb0.y, pb1.y),
+            }
+        };
+        let zone = self.zone_mut(2);
+        zone.current[point] = value;
+        zone.flags[point] |= TOUCHED_X | TOUCHED_Y;
+    }
+
+    fn interpolate_point(&mut self) {
+        if (self.top as i32) < self.state.loop_count {
+            self.state.loop_count = 1;
+            self.new_top = self.args;
+            return;
+        }
+        let twilight = self.state.zone_pointers.contains(&0);
+        let first = self.state.reference_points[1] as usize;
+        let second = self.state.reference_points[2] as usize;
+        if first >= self.zone(0).len() {
+            self.state.loop_count = 1;
+            self.new_top = self.args;
+            return;
+        }
+        let base = match twilight {
+            true => self.zone(0).original[first],
+            _ => self.zone(0).unscaled[first],
+        };
+        let current_base = self.zone(0).current[first];
+        let (old_range, current_range) = if second >= self.zone(1).len() {
+            (0, 0)
+        } else {
+            let zone = self.zone(1);
+            let other = match twilight {
+                true => zone.original[second],
+                _ => zone.unscaled[second],
+            };
+            (
+                self.project_dual(other, base),
+                self.project(zone.current[second], current_base),
+            )
+        };
+        while self.state.loop_count > 0 {
+            self.args -= 1;
+            let point = self.stack[self.args] as u32 as usize;
+            self.state.loop_count -= 1;
+            if point >= self.zone(2).len() {
+                continue;
+            }
+            let zone = self.zone(2);
+            let original = match twilight {
+                true => self.project_dual(zone.original[point], base),
+                _ => self.project_dual(zone.unscaled[point], base),
+            };
+            let current = self.project(zone.current[point], current_base);
+            let distance = if original == 0 {
+                0
+            } else if old_range != 0 {
+                math::multiply_divide(original, current_range, old_range)
+            } else {
+                original
+            };
+            self.move_point(2, point, distance.wrapping_sub(current));
+        }
+        self.state.loop_count = 1;
+        self.new_top = self.args;
+    }
+
+    fn interpolate_untouched(&mut self, horizontal: bool) {
+        if self.glyph.end_points.is_empty() {
+            return;
+        }
+        if self.backward_compatibility {
+            if self.post_iup() {
+                return;
+            }
+            if horizontal {
+                self.iup_called.0 = true;
+            } else {
+                self.iup_called.1 = true;
+            }
+        }
+        let zone = &mut self.glyph;
+        let mask = if horizontal { TOUCHED_X } else { TOUCHED_Y };
+        let get = |point: Point| if horizontal { point.x } else { point.y };
+        let mut worker = Worker {
+            unscaled: zone.unscaled.iter().map(|&point| get(point)).collect(),
+            original: zone.original.iter().map(|&point| get(point)).collect(),
+            current: zone.current.iter().map(|&point| get(point)).collect(),
+        };
+        let count = zone.len();
+        let mut point = 0;
+        for &end in zone.end_points.iter() {
+            let end = end.min(count - 1);
+            let start = point;
+            while point <= end && zone.flags[point] & mask == 0 {
+                point += 1;
+            }
+            if point <= end {
+                let first_touched = point;
+                let mut current_touched = point;
+                point += 1;
+                while point <= end {
+                    if zone.flags[point] & mask != 0 {
+                        worker.interpolate(current_touched + 1, point - 1, current_touched, point);
+                        current_touched = point;
+                    }
+                    point += 1;
+                }
+                if current_touched == first_touched {
+                    worker.shift(start, end, current_touched);
+                } else {
+                    worker.interpolate(current_touched + 1, end, current_touched, first_touched);
+                    if first_touched > 0 {
+                        worker.interpolate(
+                            start,
+                            first_touched - 1,
+                            current_touched,
+                            first_touched,
+                        );
+                    }
+                }
+            }
+        }
+        for (point, value) in zone.current.iter_mut().zip(worker.current) {
+            if horizontal {
+                point.x = value;
+            } else {
+                point.y = value;
+            }
+        }
+    }
+
+    fn delta_point(&mut self) {
+        let ppem = self.ppem;
+        let count = self.argument(0) as u32;
+        for _ in 0..count {
+            if self.args < 2 {
+                self.args = 0;
+                break;
+            }
+            self.args -= 2;
+            let point = self.stack[self.args + 1] as u16 as usize;
+            let value = self.stack[self.args];
+            if point >= self.zone(0).len() {
+                continue;
+            }
+            let mut base = ((value as u32 & 0xF0) >> 4) as i32;
+            base += match self.opcode {
+                0x71 => 16,
+                0x72 => 32,
+                _ => 0,
+            };
+            base += self.state.delta_base as i32;
+            if ppem != base {
+                continue;
+            }
+            let mut steps = (value & 0xF) - 8;
+            if steps >= 0 {
+                steps += 1;
+            }
+            let distance = steps * (1 << (6 - self.state.delta_shift));
+            if self.backward_compatibility {
+                let touched = self.zone(0).flags[point] & TOUCHED_Y != 0;
+                if !self.post_iup()
+                    && ((self.is_composite && self.state.freedom_vector.1 != 0) || touched)
+                {
+                    self.move_point(0, point, distance);
+                }
+            } else {
+                self.move_point(0, point, distance);
+            }
+        }
+        self.new_top = self.args;
+    }
+
+    fn delta_control_value(&mut self) {
+        let ppem = self.ppem;
+        let count = self.argument(0) as u32;
+        for _ in 0..count {
+            if self.args < 2 {
+                self.args = 0;
+                break;
+            }
+            self.args -= 2;
+            let index = self.stack[self.args + 1] as u32 as usize;
+            let value = self.stack[self.args];
+            if index >= self.control_values.len() {
+                continue;
+            }
+            let mut base = ((value as u32 & 0xF0) >> 4) as i32;
+            base += match self.opcode {
+                0x74 => 16,
+                0x75 => 32,
+                _ => 0,
+            };
+            base += self.state.delta_base as i32;
+            if ppem != base {
+                continue;
+            }
+            let mut steps = (value & 0xF) - 8;
+            if steps >= 0 {
+                steps += 1;
+            }
+            let distance = steps * (1 << (6 - self.state.delta_shift));
+            self.control_values[index] = self.control_values[index].wrapping_add(distance);
+        }
+        self.new_top = self.args;
+    }
+
+    fn move_direct_relative_point(&mut self) {
+        let opcode = self.opcode;
+        let point = self.argument(0) as u16;
+        let reference = self.state.reference_points[0] as usize;
+        if point as usize >= self.zone(1).len() || reference >= self.zone(0).len() {
+            self.finish_relative(point, opcode & 16 != 0);
+            return;
+        }
+        let point_index = point as usize;
+        let mut original = if self.state.zone_pointers[0] == 0 || self.state.zone_pointers[1] == 0 {
+            self.project_dual(
+                self.zone(1).original[point_index],
+                self.zone(0).original[reference],
+            )
+        } else {
+            let value = self.project_dual(
+                self.zone(1).unscaled[point_index],
+                self.zone(0).unscaled[reference],
+            );
+            math::multiply_fixed(value, self.unscaled_scale)
+        };
+        let (width, cutin) = (self.state.single_width_value, self.state.single_width_cutin);
+        if cutin > 0 && original < width + cutin && original > width - cutin {
+            original = if original >= 0 { width } else { -width };
+        }
+        let mut distance = if opcode & 4 != 0 {
+            self.round(original)
+        } else {
+            original
+        };
+        if opcode & 8 != 0 {
+            let minimum = self.state.minimum_distance;
+            if original >= 0 {
+                distance = distance.max(minimum);
+            } else {
+                distance = distance.min(minimum.wrapping_neg());
+            }
+        }
+        let current = self.project(
+            self.zone(1).current[point_index],
+            self.zone(0).current[reference],
+        );
+        self.move_point(1, point_index, distance.wrapping_sub(current));
+        self.finish_relative(point, opcode & 16 != 0);
+    }
+
+    fn move_indirect_relative_point(&mut self) {
+        let opcode = self.opcode;
+        let point = self.argument(0) as u16;
+        let entry = self.argument(1).wrapping_add(1) as u32 as usize;
+        let reference = self.state.reference_points[0] as usize;
+        if point as usize >= self.zone(1).len()
+            || entry > self.control_values.len()
+            || reference >= self.zone(0).len()
+        {
+            self.finish_relative(point, opcode & 16 != 0);
+            return;
+        }
+        let point_index = point as usize;
+        let mut value = match entry {
+            0 => 0,
+            _ => self.control_values[entry - 1],
+        };
+        let (width, cutin) = (self.state.single_width_value, self.state.single_width_cutin);
+        if value.wrapping_sub(width).wrapping_abs() < cutin {
+            value = if value >= 0 { width } else { -width };
+        }
+        if self.state.zone_pointers[1] == 0 {
+            let base = self.zone(0).original[reference];
+            let position = Point {
+                x: base
+                    .x
+                    .wrapping_add(math::multiply_14(value, self.state.freedom_vector.0)),
+                y: base
+                    .y
+                    .wrapping_add(math::multiply_14(value, self.state.freedom_vector.1)),
+            };
+            let zone = self.zone_mut(1);
+            zone.original[point_index] = position;
+            zone.current[point_index] = position;
+        }
+        let original = self.project_dual(
+            self.zone(1).original[point_index],
+            self.zone(0).original[reference],
+        );
+        let current = self.project(
+            self.zone(1).current[point_index],
+            self.zone(0).current[reference],
+        );
+        if self.state.auto_flip && (original ^ value) < 0 {
+            value = value.wrapping_neg();
+        }
+        let mut distance = if opcode & 4 != 0 {
+            if self.state.zone_pointers[0] == self.state.zone_pointers[1]
+                && value.wrapping_sub(original).wrapping_abs() > self.state.control_value_cutin
+            {
+                value = original;
+            }
+            self.round(value)
+        } else {
+            value
+        };
+        if opcode & 8 != 0 {
+            let minimum = self.state.minimum_distance;
+            if original >= 0 {
+                distance = distance.max(minimum);
+            } else {
+                distance = distance.min(minimum.wrapping_neg());
+            }
+        }
+        self.move_point(1, point_index, distance.wrapping_sub(current));
+        self.finish_relative(point, opcode & 16 != 0);
+    }
+
+    fn finish_relative(&mut self, point: u16, reset: bool) {
+        self.state.reference_points[1] = self.state.reference_points[0];
+        self.state.reference_points[2] = point;
+        if reset {
+            self.state.reference_points[0] = point;
+        }
+    }
+}
+
+struct Worker {
+    unscaled: Vec<i32>,
+    original: Vec<i32>,
+    current: Vec<i32>,
+}
+
+impl Worker {
+    fn shift(&mut self, start: usize, end: usize, reference: usize) {
+        let delta = self.current[reference].wrapping_sub(self.original[reference]);
+        if delta == 0 {
+            return;
+        }
+        for point in (start..reference).chain(reference + 1..=end) {
+            self.current[point] = self.current[point].wrapping_add(delta);
+        }
+    }
+
+    fn interpolate(&mut self, start: usize, end: usize, first: usize, second: usize) {
+        if start > end || first >= self.current.len() || second >= self.current.len() {
+            return;
+        }
+        let (mut first, mut second) = (first, second);
+        if self.unscaled[first] > self.unscaled[second] {
+            std::mem::swap(&mut first, &mut second);
+        }
+        let (unscaled1, unscaled2) = (self.unscaled[first], self.unscaled[second]);
+        let (original1, original2) = (self.original[first], self.original[second]);
+        let (current1, current2) = (self.current[first], self.current[second]);
+        let delta1 = current1.wrapping_sub(original1);
+        let delta2 = current2.wrapping_sub(original2);
+        let mut scale = None;
+        for point in start..=end {
+            let value = self.original[point];
+            self.current[point] = if value <= original1 {
+                value.wrapping_add(delta1)
+            } else if value >= original2 {
+                value.wrapping_add(delta2)
+            } else if current1 == current2 || unscaled1 == unscaled2 {
+                current1
+            } else {
+                let scale = *scale.get_or_insert_with(|| {
+                    math::divide_fixed(
+                        current2.wrapping_sub(current1),
+                        unscaled2.wrapping_sub(unscaled1),
+                    )
+                });
+                current1.wrapping_add(math::multiply_fixed(
+                    self.unscaled[point].wrapping_sub(unscaled1),
+                    scale,
+                ))
+            };
+        }
+    }
+}
+
+fn count_arguments(opcode: u8) -> (usize, usize) {
+    match opcode {
+        0x06..=0x0B => (2, 0),
+        0x0C | 0x0D => (0, 2),
+        0x0F => (5, 0),
+        0x10..=0x17 | 0x1A | 0x1C..=0x1F => (1, 0),
+        0x20 => (1, 2),
+        0x21 => (1, 0),
+        0x23 => (2, 2),
+        0x24 => (0, 1),
+        0x25 => (1, 1),
+        0x26 => (1, 0),
+        0x27 | 0x2A => (2, 0),
+        0x29 | 0x2B | 0x2C | 0x2E | 0x2F => (1, 0),
+        0x34..=0x38 => (1, 0),
+        0x3A | 0x3B | 0x3E | 0x3F => (2, 0),
+        0x42 => (2, 0),
+        0x43 => (1, 1),
+        0x44 => (2, 0),
+        0x45..=0x47 => (1, 1),
+        0x48 => (2, 0),
+        0x49 | 0x4A => (2, 1),
+        0x4B | 0x4C => (0, 1),
+        0x4F => (1, 0),
+        0x50..=0x55 => (2, 1),
+        0x56 | 0x57 => (1, 1),
+        0x58 => (1, 0),
+        0x5A | 0x5B => (2, 1),
+        0x5C => (1, 1),
+        0x5D..=0x5F => (1, 0),
+        0x60..=0x63 => (2, 1),
+        0x64..=0x6F => (1, 1),
+        0x70 => (2, 0),
+        0x71..=0x77 => (1, 0),
+        0x78 | 0x79 => (2, 0),
+        0x7E | 0x7F => (1, 0),
+        0x81 | 0x82 => (2, 0),
+        0x85 => (1, 0),
+        0x86 | 0x87 => (2, 0),
+        0x88 => (1, 1),
+        0x89 => (1, 0),
+        0x8A => (3, 3),
+        0x8B | 0x8C => (2, 1),
+        0x8D => (1, 0),
+        0x8E => (2, 0),
+        0xB0..=0xB7 => (0, (opcode - 0xAF) as usize),
+        0xB8..=0xBF => (0, (opcode - 0xB7) as usize),
+        0xC0..=0xDF => (1, 0),
+        0xE0..=0xFF => (2, 0),
+        _ => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Machine, Point, Range, Zone, TOUCHED_X, TOUCHED_Y};
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    fn glyph(points: &[(i32, i32)]) -> Zone {
+        let mut zone = Zone::new(points.len() + 4);
+        for (i, &(x, y)) in points.iter().enumerate() {
+            zone.original[i] = Point { x, y };
+            zone.current[i] = Point { x, y };
+        }
+        zone.end_points = vec![points.len() - 1];
+        zone
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut machine = Machine::new(8, 2, 0, 0, 0);
+        #[rustfmt::skip]
+        let program = vec![
+            0xB2, 0x00, 0x03, 0x05, // PUSHB[2]
+            0x60, // ADD
+            0x42, // WS
+            0xB2, 0x01, 0x80, 0xC0, // PUSHB[2]
+            0x63, // MUL
+            0x42, // WS
+        ];
+        machine.programs[Range::Font as usize] = program;
+        ok!(machine.run(Range::Font));
+        assert_eq!(machine.storage, [8, 384]);
+    }
+
+    #[test]
+    fn backward_compatibility() {
+        #[rustfmt::skip]
+        let program = vec![
+            0xB0, 0x00, // PUSHB[0]
+            0x01, // SVTCA[1]
+            0x2F, // MDAP[1]
+            0xB0, 0x00, // PUSHB[0]
+            0x00, // SVTCA[0]
+            0x2F, // MDAP[1]
+        ];
+        let mut machine = Machine::new(8, 0, 0, 0, 0);
+        machine.glyph = glyph(&[(10, 37)]);
+        machine.programs[Range::Glyph as usize] = program.clone();
+        ok!(machine.run(Range::Glyph));
+        assert_eq!(machine.glyph.current[0], Point { x: 0, y: 64 });
+
+        let mut machine = Machine::new(8, 0, 0, 0, 0);
+        machine.backward_compatibility = true;
+        machine.glyph = glyph(&[(10, 37)]);
+        machine.programs[Range::Glyph as usize] = program;
+        ok!(machine.run(Range::Glyph));
+        assert_eq!(machine.glyph.current[0], Point { x: 10, y: 64 });
+        assert_eq!(machine.glyph.flags[0], TOUCHED_X | TOUCHED_Y);
+    }
+
+    #[test]
+    fn functions() {
+        let mut machine = Machine::new(8, 2, 0, 1, 0);
+        #[rustfmt::skip]
+        let program = vec![
+            0xB0, 0x00, // PUSHB[0]
+            0x2C, // FDEF
+            0xB0, 0x07, // PUSHB[0]
+            0x60, // ADD
+            0x2D, // ENDF
+            0xB2, 0x00, 0x03, 0x00, // PUSHB[2]
+            0x2B, // CALL
+            0x42, // WS
+            0xB3, 0x01, 0x00, 0x02, 0x00, // PUSHB[3]
+            0x2A, // LOOPCALL
+            0x42, // WS
+        ];
+        machine.programs[Range::Font as usize] = program;
+        ok!(machine.run(Range::Font));
+        assert_eq!(machine.storage, [10, 14]);
+    }
+
+    #[test]
+    fn rounding() {
+        let mut machine = Machine::new(8, 0, 0, 0, 0);
+        #[rustfmt::skip]
+        let program = vec![
+            0x00, // SVTCA[0]
+            0xB0, 0x00, // PUSHB[0]
+            0x2F, // MDAP[1]
+            0x7D, // RDTG
+            0xB0, 0x01, // PUSHB[0]
+            0x2F, // MDAP[1]
+            0x7A, // ROFF
+            0xB0, 0x02, // PUSHB[0]
+            0x2F, // MDAP[1]
+        ];
+        machine.glyph = glyph(&[(0, 37), (0, 100), (0, 100)]);
+        machine.programs[Range::Glyph as usize] = program;
+        ok!(machine.run(Range::Glyph));
+        let values = machine.glyph.current[..3]
+            .iter()
+            .map(|point| point.y)
+            .collect::<Vec<_>>();
+        assert_eq!(values, [64, 64, 100]);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut machine = Machine::new(1, 0, 0, 0, 0);
+        machine.programs[Range::Font as usize] = vec![0xB1, 0x01, 0x02];
+        assert!(machine.run(Range::Font).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+// Fixed-point arithmetic with the rounding behavior of the reference rasterizers. Coordinates
+// are in 26.6, vectors in 2.14, and scales in 16.16.
+
+pub fn multiply_divide(a: i32, b: i32, c: i32) -> i32 {
+    let sign = (a < 0) ^ (b < 0) ^ (c < 0);
+    let (a, b, c) = (
+        a.unsigned_abs() as u64,
+        b.unsigned_abs() as u64,
+        c.unsigned_abs() as u64,
+    );
+    let value = match c {
+        0 => 0x7FFF_FFFF,
+        _ => (a * b + (c >> 1)) / c,
+    } as i32;
+    if sign {
+        value.wrapping_neg()
+    } else {
+        value
+    }
+}
+
+pub fn multiply_divide_truncating(a: i32, b: i32, c: i32) -> i32 {
+    let sign = (a < 0) ^ (b < 0) ^ (c < 0);
+    let (a, b, c) = (
+        a.unsigned_abs() as u64,
+        b.unsigned_abs() as u64,
+        c.unsigned_abs() as u64,
+    );
+    let value = match c {
+        0 => 0x7FFF_FFFF,
+        _ => a * b / c,
+    } as i32;
+    if sign {
+        value.wrapping_neg()
+    } else {
+        value
+    }
+}
+
+pub fn multiply_fixed(a: i32, b: i32) -> i32 {
+    let product = a as i64 * b as i64;
+    ((product + 0x8000 + (product >> 63)) >> 16) as i32
+}
+
+pub fn divide_fixed(a: i32, b: i32) -> i32 {
+    let sign = (a < 0) ^ (b < 0);
+    let (a, b) = (a.unsigned_abs() as u64, b.unsigned_abs() as u64);
+    let value = match b {
+        0 => 0x7FFF_FFFF,
+        _ => ((a << 16) + (b >> 1)) / b,
+    } as i32;
+    if sign {
+        value.wrapping_neg()
+    } else {
+        value
+    }
+}
+
+pub fn multiply_14(a: i32, b: i32) -> i32 {
+    let product = a as i64 * b as i64;
+    ((product + 0x2000 + (product >> 63)) >> 14) as i32
+}
+
+pub fn dot_14((ax, ay): (i32, i32), (bx, by): (i32, i32)) -> i32 {
+    let product = ax as i64 * bx as i64 + ay as i64 * by as i64;
+    ((product + 0x2000 + (product >> 63)) >> 14) as i32
+}
+
+#[inline]
+pub fn floor(value: i32) -> i32 {
+    value & -64
+}
+
+#[inline]
+pub fn round(value: i32) -> i32 {
+    floor(value.wrapping_add(32))
+}
+
+#[inline]
+pub fn ceil(value: i32) -> i32 {
+    floor(value.wrapping_add(63))
+}
+
+// Normalize a vector to a unit vector in 2.14 using the iterative approximation of the
+// reference implementation so that projections agree bit for bit.
+pub fn normalize(x: i32, y: i32) -> Option<(i32, i32)> {
+    if x == 0 && y == 0 {
+        return None;
+    }
+    let (x, y) = normalize_16(x, y);
+    Some((x / 4, y / 4))
+}
+
+fn normalize_16(x: i32, y: i32) -> (i32, i32) {
+    let (sx, sy) = (x < 0, y < 0);
+    let mut ux = x.unsigned_abs();
+    let mut uy = y.unsigned_abs();
+    let sign = |value: u32, negative: bool| {
+        if negative {
+            (value as i32).wrapping_neg()
+        } else {
+            value as i32
+        }
+    };
+    if ux == 0 {
+        return (0, sign(0x10000, sy));
+    }
+    if uy == 0 {
+        return (sign(0x10000, sx), 0);
+    }
+    let mut length = if ux > uy {
+        ux + (uy >> 1)
+    } else {
+        uy + (ux >> 1)
+    };
+    let mut shift = length.leading_zeros() as i32;
+    shift -= 15 + (length >= (0xAAAA_AAAAu32 >> shift)) as i32;
+    if shift > 0 {
+        ux <<= shift;
+        uy <<= shift;
+        length = if ux > uy {
+            ux + (uy >> 1)
+        } else {
+            uy + (ux >> 1)
+        };
+    } else {
+        ux >>= -shift;
+        uy >>= -shift;
+        length >>= -shift;
+    }
+    let mut b = 0x10000i32.wrapping_sub(length as i32);
+    let (x, y) = (ux as i32, uy as i32);
+    let (mut u, mut v);
+    loop {
+        u = x.wrapping_add(x.wrapping_mul(b) >> 16) as u32;
+        v = y.wrapping_add(y.wrapping_mul(b) >> 16) as u32;
+        let mut z =
+            (u.wrapping_mul(u).wrapping_add(v.wrapping_mul(v)) as i32).wrapping_neg() / 0x200;
+        z = z.wrapping_mul((0x10000 + b) >> 8) / 0x10000;
+        b += z;
+        if z <= 0 {
+            break;
+        }
+    }
+    (sign(u, sx), sign(v, sy))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn multiply_divide() {
+        assert_eq!(super::multiply_divide(3, 5, 2), 8);
+        assert_eq!(super::multiply_divide(-3, 5, 2), -8);
+        assert_eq!(super::multiply_divide_truncating(-3, 5, 2), -7);
+        assert_eq!(super::multiply_fixed(-0x8000, 1), -1);
+        assert_eq!(super::divide_fixed(1, 2), 0x8000);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(super::normalize(0, -5), Some((0, -0x4000)));
+        assert_eq!(super::normalize(7, 0), Some((0x4000, 0)));
+        assert_eq!(super::normalize(0, 0), None);
+        let (x, y) = super::normalize(100, 100).unwrap();
+        assert_eq!(x, y);
+        assert!((x - 11585).abs() <= 1);
+    }
+}
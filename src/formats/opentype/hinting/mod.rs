@@ -0,0 +1,445 @@
+// Grid fitting of TrueType outlines follows the reference rasterizer in interpreter version 40
+// with backward compatibility, which leaves horizontal positions intact and hints only along
+// the vertical axis unless a font opts out.
+
+mod machine;
+mod math;
+
+use std::io::Result;
+
+use opentype::truetype::tables::glyph_data::{
+    CompositeDescription, Description, GlyphData, SimpleDescription,
+};
+use opentype::truetype::tables::MaximumProfile;
+use opentype::truetype::{GlyphID, Tag};
+
+use crate::formats::opentype::cache::Cache;
+use crate::formats::opentype::metrics::Metrics;
+use crate::formats::opentype::truetype;
+use crate::glyph::{Builder, Glyph};
+use crate::offset::Offset;
+use crate::Number;
+
+use self::machine::{Machine, Point, Range, State, Zone, ON_CURVE};
+
+// The maximum depth of nested composite glyphs regardless of the maximum profile.
+const DEPTH: usize = 16;
+
+macro_rules! expect(
+    ($condition:expr) => (
+        if !$condition {
+            raise!("found a malformed glyph");
+        }
+    )
+);
+
+#[derive(Clone)]
+pub struct Hinter {
+    machine: Machine,
+    state: State,
+    scale: i32,
+    enabled: bool,
+    twilight_size: usize,
+    maximum_depth: usize,
+}
+
+#[derive(Default)]
+struct Outline {
+    points: Vec<Point>,
+    flags: Vec<u8>,
+    end_points: Vec<usize>,
+}
+
+pub fn draw<T: crate::Read>(cache: &mut Cache<T>, glyph_id: GlyphID, ppem: u16) -> Result<Glyph> {
+    let mut hinter = Hinter::clone(cache.hinter(ppem)?);
+    let metrics = cache.metrics()?.clone();
+    let glyph_data = cache.glyph_data()?.clone();
+    let glyph_data = glyph_data.borrow();
+    let mut outline = Outline::default();
+    let phantoms = hinter.load(&glyph_data, &metrics, glyph_id, &mut outline, 0)?;
+
+    let shift = phantoms[0].x;
+    for point in outline.points.iter_mut() {
+        point.x = point.x.wrapping_sub(shift);
+    }
+    let pixels = |value: i32| value as Number / 64.0;
+    let mut builder = Builder::default();
+    let advance_width = math::round(phantoms[1].x.wrapping_sub(phantoms[0].x));
+    let mut bounding_box = (Number::NAN, Number::NAN, Number::NAN, Number::NAN);
+    if let Some(first) = outline.points.first() {
+        let (mut min, mut max) = (*first, *first);
+        for point in outline.points.iter() {
+            min = Point {
+                x: min.x.min(point.x),
+                y: min.y.min(point.y),
+            };
+            max = Point {
+                x: max.x.max(point.x),
+                y: max.y.max(point.y),
+            };
+        }
+        bounding_box = (pixels(min.x), pixels(min.y), pixels(max.x), pixels(max.y));
+        builder.set_bounding_box(bounding_box);
+    }
+    builder.set_horizontal_metrics((pixels(advance_width), bounding_box.0));
+    builder.set_vertical_metrics((
+        pixels(phantoms[2].y.wrapping_sub(phantoms[3].y)),
+        pixels(phantoms[2].y) - bounding_box.3,
+    ));
+    let on_curve = outline
+        .flags
+        .iter()
+        .map(|&flags| flags & ON_CURVE != 0)
+        .collect::<Vec<_>>();
+    let mut last = Point::default();
+    let points = outline
+        .points
+        .iter()
+        .map(|&point| {
+            let offset = Offset(
+                pixels(point.x.wrapping_sub(last.x)),
+                pixels(point.y.wrapping_sub(last.y)),
+            );
+            last = point;
+            offset
+        })
+        .collect::<Vec<_>>();
+    truetype::draw_contours(
+        &mut builder,
+        outline.end_points.iter().copied(),
+        &on_curve,
+        &points,
+    )?;
+    Ok(builder.into())
+}
+
+impl Hinter {
+    pub fn new<T: crate::Read>(cache: &mut Cache<T>, ppem: u16) -> Result<Self> {
+        let units_per_em = cache.font_header()?.borrow().units_per_em;
+        let profile = match &*cache.maximum_profile()?.borrow() {
+            MaximumProfile::Version1(profile) => *profile,
+            _ => raise!("found no hinting limits in the maximum profile"),
+        };
+        let control_values = cache
+            .data(Tag(*b"cvt "))?
+            .unwrap_or_default()
+            .chunks_exact(2)
+            .map(|chunk| i16::from_be_bytes([chunk[0], chunk[1]]) as i32 * 64)
+            .collect::<Vec<_>>();
+        let twilight_size = profile.max_twilight_points as usize + 4;
+        let mut machine = Machine::new(
+            profile.max_stack_elements as usize + 32,
+            profile.max_storage as usize,
+            twilight_size,
+            profile.max_function_definitions as usize,
+            profile.max_instruction_definitions as usize,
+        );
+        machine.programs[Range::Font as usize] = cache.data(Tag(*b"fpgm"))?.unwrap_or_default();
+        machine.programs[Range::ControlValue as usize] =
+            cache.data(Tag(*b"prep"))?.unwrap_or_default();
+        machine.point_size = ppem as i32 * 64;
+        machine.control_values = vec![0; control_values.len()];
+        machine.subpixel = false;
+        machine.run(Range::Font)?;
+
+        let scale = math::divide_fixed(ppem as i32 * 64, units_per_em as i32);
+        machine.ppem = ppem as i32;
+        machine.scale = scale;
+        machine.unscaled_scale = scale;
+        machine.control_values = control_values
+            .into_iter()
+            .map(|value| math::multiply_fixed(value, scale >> 6))
+            .collect();
+        machine.subpixel = true;
+        machine.state = State::default();
+        machine.run(Range::ControlValue)?;
+        // The control-value program cannot change the following part of the graphics state.
+        let state = State {
+            reference_points: [0; 3],
+            dual_vector: (0x4000, 0),
+            projection_vector: (0x4000, 0),
+            freedom_vector: (0x4000, 0),
+            loop_count: 1,
+            zone_pointers: [1; 3],
+            ..machine.state
+        };
+        let mut control = state.instruct_control;
+        if control & 2 != 0 {
+            control = 0;
+        }
+        machine.backward_compatibility = control & 4 == 0;
+        Ok(Self {
+            machine,
+            state,
+            scale,
+            enabled: state.instruct_control & 1 == 0,
+            twilight_size,
+            maximum_depth: profile.max_component_depth as usize,
+        })
+    }
+
+    fn load(
+        &mut self,
+        glyph_data: &GlyphData,
+        metrics: &Metrics,
+        glyph_id: GlyphID,
+        outline: &mut Outline,
+        depth: usize,
+    ) -> Result<[Point; 4]> {
+        if depth == 0 {
+            self.machine.twilight.limit = self.twilight_size;
+        }
+        if depth > DEPTH || depth > 1 && depth > self.maximum_depth {
+            raise!("found a too deeply nested composite glyph");
+        }
+        let glyph = match glyph_data.get(glyph_id as usize) {
+            Some(glyph) => glyph.as_ref(),
+            _ => raise!("found no data for glyph {}", glyph_id),
+        };
+        let (min_x, max_y) = glyph.map_or((0, 0), |glyph| (glyph.min_x, glyph.max_y));
+        let (advance_width, left_side_bearing) = metrics.get(glyph_id);
+        let (advance_height, top_side_bearing) = metrics.get_vertical(glyph_id, max_y.into());
+        let left = min_x as i32 - left_side_bearing as i32;
+        let top = max_y as i32 + top_side_bearing as i32;
+        let phantoms = [
+            Point { x: left, y: 0 },
+            Point {
+                x: left + advance_width as i32,
+                y: 0,
+            },
+            Point { x: 0, y: top },
+            Point {
+                x: 0,
+                y: top - advance_height as i32,
+            },
+        ];
+        match glyph.map(|glyph| &glyph.description) {
+            Some(Description::Simple(description)) => {
+                self.load_simple(description, phantoms, outline)
+            }
+            Some(Description::Composite(description)) => {
+                let phantoms = phantoms.map(|point| self.scale(point));
+                self.load_composite(glyph_data, metrics, description, phantoms, outline, depth)
+            }
+            _ => Ok(phantoms.map(|point| self.scale(point))),
+        }
+    }
+
+    fn load_simple(
+        &mut self,
+        description: &SimpleDescription,
+        phantoms: [Point; 4],
+        outline: &mut Outline,
+    ) -> Result<[Point; 4]> {
+        let SimpleDescription {
+            end_points,
+            instructions,
+            flags,
+            x,
+            y,
+            ..
+        } = description;
+        let count = flags.len();
+        expect!(count == x.len());
+        expect!(count == y.len());
+        let mut zone = Zone::new(count + 4);
+        let mut point = Point::default();
+        for i in 0..count {
+            point.x += x[i] as i32;
+            point.y += y[i] as i32;
+            zone.unscaled[i] = point;
+            zone.flags[i] = flags[i].is_on_curve() as u8 * ON_CURVE;
+        }
+        zone.unscaled[count..].copy_from_slice(&phantoms);
+        zone.current = zone
+            .unscaled
+            .iter()
+            .map(|&point| self.scale(point))
+            .collect();
+        let mut previous = None;
+        for &end in end_points.iter() {
+            let end = end as usize;
+            expect!(end < count && previous.is_none_or(|previous| previous < end));
+            zone.end_points.push(end);
+            previous = Some(end);
+        }
+        if self.enabled {
+            zone = self.hint(zone, instructions, false);
+        }
+        Ok(outline.append(zone))
+    }
+
+    fn load_composite(
+        &mut self,
+        glyph_data: &GlyphData,
+        metrics: &Metrics,
+        description: &CompositeDescription,
+        mut phantoms: [Point; 4],
+        outline: &mut Outline,
+        depth: usize,
+    ) -> Result<[Point; 4]> {
+        use opentype::truetype::tables::glyph_data::{Arguments, Options};
+
+        let start_point = outline.points.len();
+        let start_contour = outline.end_points.len();
+        let mut instructed = false;
+        for component in description.components.iter() {
+            let base = outline.points.len();
+            let component_phantoms =
+                self.load(glyph_data, metrics, component.glyph_id, outline, depth + 1)?;
+            if component.flags.should_use_metrics() {
+                phantoms = component_phantoms;
+            }
+            instructed = component.flags.has_instructions();
+            if outline.points.len() == base {
+                continue;
+            }
+            let matrix = match component.options {
+                Options::None => None,
+                Options::Scalar(value) => Some((value.0, 0, 0, value.0)),
+                Options::Vector(x, y) => Some((x.0, 0, 0, y.0)),
+                Options::Matrix(xx, yx, xy, yy) => Some((xx.0, xy.0, yx.0, yy.0)),
+            }
+            .map(|(xx, xy, yx, yy)| {
+                let scale = |value: i16| value as i32 * 4;
+                (scale(xx), scale(xy), scale(yx), scale(yy))
+            });
+            if let Some((xx, xy, yx, yy)) = matrix {
+                for point in outline.points[base..].iter_mut() {
+                    let (x, y) = (point.x, point.y);
+                    point.x = math::multiply_fixed(x, xx).wrapping_add(math::multiply_fixed(y, xy));
+                    point.y = math::multiply_fixed(x, yx).wrapping_add(math::multiply_fixed(y, yy));
+                }
+            }
+            let (x, y) = match component.arguments {
+                Arguments::Offsets(x, y) => {
+                    let (mut x, mut y) = (x as i32, y as i32);
+                    if let Some((xx, xy, yx, yy)) = matrix {
+                        if component.flags.is_offset_scaled()
+                            && !component.flags.is_offset_unscaled()
+                        {
+                            let length = |a: i32, b: i32| (a as f64).hypot(b as f64).round() as i32;
+                            x = math::multiply_fixed(x, length(xx, xy));
+                            y = math::multiply_fixed(y, length(yy, yx));
+                        }
+                    }
+                    x = math::multiply_fixed(x, self.scale);
+                    y = math::multiply_fixed(y, self.scale);
+                    if self.enabled && component.flags.should_round_xy_to_grid() {
+                        y = math::round(y);
+                    }
+                    (x, y)
+                }
+                Arguments::Indices(parent, child) => {
+                    let (parent, child) = (start_point + parent as usize, base + child as usize);
+                    if parent >= base || child >= outline.points.len() {
+                        raise!("found a component with malformed point indices");
+                    }
+                    let (parent, child) = (outline.points[parent], outline.points[child]);
+                    (
+                        parent.x.wrapping_sub(child.x),
+                        parent.y.wrapping_sub(child.y),
+                    )
+                }
+            };
+            if x == 0 && y == 0 {
+                continue;
+            }
+            for point in outline.points[base..].iter_mut() {
+                point.x = point.x.wrapping_add(x);
+                point.y = point.y.wrapping_add(y);
+            }
+        }
+        if !self.enabled
+            || !instructed
+            || description.instructions.is_empty()
+            || outline.points.len() == start_point
+        {
+            return Ok(phantoms);
+        }
+        let count = outline.points.len() - start_point;
+        let mut zone = Zone::new(count + 4);
+        zone.current[..count].copy_from_slice(&outline.points[start_point..]);
+        zone.current[count..].copy_from_slice(&phantoms);
+        for (flags, &value) in zone.flags.iter_mut().zip(&outline.flags[start_point..]) {
+            *flags = value & ON_CURVE;
+        }
+        zone.end_points = outline.end_points[start_contour..]
+            .iter()
+            .map(|&end| end - start_point)
+            .collect();
+        let zone = self.hint(zone, &description.instructions, true);
+        outline.points.truncate(start_point);
+        outline.flags.truncate(start_point);
+        outline.end_points.truncate(start_contour);
+        Ok(outline.append(zone))
+    }
+
+    fn hint(&mut self, mut zone: Zone, instructions: &[u8], composite: bool) -> Zone {
+        // The phantom points are rounded even if there are no instructions to run.
+        let count = zone.current.len();
+        let (left, right) = (zone.current[count - 4].x, zone.current[count - 3].x);
+        zone.current[count - 4].x = math::round(zone.current[count - 4].x);
+        zone.current[count - 3].x = math::round(zone.current[count - 3].x);
+        zone.current[count - 2].y = math::round(zone.current[count - 2].y);
+        zone.current[count - 1].y = math::round(zone.current[count - 1].y);
+        if !instructions.is_empty() {
+            zone = self.run(zone, instructions, composite);
+        }
+        // In the backward-compatibility mode, the horizontal metrics stay unrounded.
+        if self.machine.backward_compatibility {
+            zone.current[count - 4].x = left;
+            zone.current[count - 3].x = right;
+        }
+        zone
+    }
+
+    fn run(&mut self, mut zone: Zone, instructions: &[u8], composite: bool) -> Zone {
+        zone.original = zone.current.clone();
+        if composite {
+            zone.unscaled = zone.current.clone();
+            self.machine.unscaled_scale = 1 << 16;
+        } else {
+            self.machine.unscaled_scale = self.scale;
+        }
+        let machine = &mut self.machine;
+        machine.state = State {
+            round_state: 1,
+            ..self.state
+        };
+        machine.glyph = zone;
+        machine.programs[Range::Glyph as usize] = instructions.to_vec();
+        machine.is_composite = composite;
+        let control_values = machine.control_values.clone();
+        let storage = machine.storage.clone();
+        // Errors in glyph programs leave the outline as far as it has been fitted.
+        let _ = machine.run(Range::Glyph);
+        machine.control_values = control_values;
+        machine.storage = storage;
+        std::mem::take(&mut machine.glyph)
+    }
+
+    fn scale(&self, point: Point) -> Point {
+        Point {
+            x: math::multiply_fixed(point.x, self.scale),
+            y: math::multiply_fixed(point.y, self.scale),
+        }
+    }
+}
+
+impl Outline {
+    fn append(&mut self, zone: Zone) -> [Point; 4] {
+        let count = zone.current.len() - 4;
+        let offset = self.points.len();
+        self.points.extend_from_slice(&zone.current[..count]);
+        self.flags.extend_from_slice(&zone.flags[..count]);
+        self.end_points
+            .extend(zone.end_points.iter().map(|&end| end + offset));
+        [
+            zone.current[count],
+            zone.current[count + 1],
+            zone.current[count + 2],
+            zone.current[count + 3],
+        ]
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Result;
+use std::ops::DerefMut;
+
+use opentype::layout::{Class, Coverage};
+use opentype::tables::glyph_positioning::{GlyphPositioning, PairAdjustment, Single, Type};
+use opentype::truetype::{GlyphID, Tag};
+
+use crate::formats::opentype::cache::Cache;
+use crate::Number;
+
+#[derive(Clone, Debug, Default)]
+pub struct Kerning {
+    pub tables: Vec<Table>,
+}
+
+table! {
+    #[derive(Copy)]
+    pub Header {
+        version  (u16), // version
+        size     (u16), // length
+        coverage (u16), // coverage
+    }
+}
+
+table! {
+    pub Table {
+        header     (Header), // header
+        pair_count (u16), // nPairs
+
+        search_range   (u16), // searchRange
+        entry_selector (u16), // entrySelector
+        range_shift    (u16), // rangeShift
+
+        pairs (Vec<Pair>) |this, tape| { // kernPairs
+            tape.take_given(this.pair_count as usize)
+        },
+    }
+}
+
+table! {
+    #[derive(Copy)]
+    pub Pair {
+        left  (GlyphID), // left
+        right (GlyphID), // right
+        value (i16), // value
+    }
+}
+
+pub struct Pairs {
+    simple: HashMap<(GlyphID, GlyphID), Number>,
+    classes: Vec<Classes>,
+}
+
+struct Classes {
+    coverage: HashSet<GlyphID>,
+    left: HashMap<GlyphID, u16>,
+    right: HashMap<GlyphID, u16>,
+    values: Vec<Vec<Number>>,
+}
+
+impl opentype::Table for Kerning {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"kern")
+    }
+}
+
+impl opentype::value::Read for Kerning {
+    fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+        if tape.take::<u16>()? != 0 {
+            return Ok(Default::default());
+        }
+        let table_count = tape.take::<u16>()?;
+        let mut tables = Vec::with_capacity(table_count as usize);
+        for i in 0..table_count {
+            let position = tape.position()?;
+            let header = tape.peek::<Header>()?;
+            if header.coverage >> 8 == 0 {
+                // The length of large subtables overflows, and the pair count is used instead.
+                tables.push(tape.take()?);
+            } else if i + 1 < table_count {
+                tape.jump(position + header.size as u64)?;
+            }
+        }
+        Ok(Self { tables })
+    }
+}
+
+impl Pairs {
+    fn add(&mut self, table: &PairAdjustment) {
+        match table {
+            PairAdjustment::Format1(table) => {
+                for (glyph_id, pairs) in uncover(&table.coverage).zip(&table.records) {
+                    for pair in pairs.records.iter() {
+                        self.simple
+                            .entry((glyph_id, pair.glyph2_id))
+                            .or_insert_with(|| advance(&pair.value1));
+                    }
+                }
+            }
+            PairAdjustment::Format2(table) => {
+                self.classes.push(Classes {
+                    coverage: uncover(&table.coverage).collect(),
+                    left: unclass(&table.class1),
+                    right: unclass(&table.class2),
+                    values: table
+                        .records
+                        .iter()
+                        .map(|pairs| {
+                            pairs
+                                .records
+                                .iter()
+                                .map(|pair| advance(&pair.value1))
+                                .collect()
+                        })
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    fn add_positioning<T: opentype::tape::Read>(
+        &mut self,
+        tape: &mut T,
+        position: u64,
+        table: &GlyphPositioning,
+    ) -> Result<()> {
+        for index in lookups(table) {
+            let (offset, record) = match (
+                table.lookups.offsets.get(index),
+                table.lookups.records.get(index),
+            ) {
+                (Some(offset), Some(record)) => (offset, record),
+                _ => continue,
+            };
+            for (table_offset, value) in record.table_offsets.iter().zip(&record.tables) {
+                match value {
+                    Type::PairAdjustment(value) => self.add(value),
+                    // Extension subtables point to the actual ones relative to themselves.
+                    Type::ExtensionPositioning(value) if value.r#type == 2 => {
+                        tape.jump(
+                            position
+                                + table.lookup_offset as u64
+                                + *offset as u64
+                                + *table_offset as u64
+                                + value.offset as u64,
+                        )?;
+                        self.add(&tape.take()?);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_kerning(&mut self, table: &Kerning) {
+        for table in table.tables.iter() {
+            let coverage = table.header.coverage;
+            // Only horizontal kerning that is neither minimum nor cross-stream adjusts advances.
+            if coverage & 0b0001 == 0 || coverage & 0b0110 != 0 {
+                continue;
+            }
+            for pair in table.pairs.iter() {
+                let value = Number::from(pair.value);
+                let entry = self.simple.entry((pair.left, pair.right)).or_default();
+                if coverage & 0b1000 != 0 {
+                    *entry = value;
+                } else {
+                    *entry += value;
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, left: GlyphID, right: GlyphID) -> Number {
+        if let Some(value) = self.simple.get(&(left, right)) {
+            return *value;
+        }
+        for classes in self.classes.iter() {
+            if !classes.coverage.contains(&left) {
+                continue;
+            }
+            let left = classes.left.get(&left).cloned().unwrap_or(0) as usize;
+            let right = classes.right.get(&right).cloned().unwrap_or(0) as usize;
+            if let Some(value) = classes
+                .values
+                .get(left)
+                .and_then(|values| values.get(right))
+            {
+                return *value;
+            }
+        }
+        0.0
+    }
+}
+
+pub(crate) fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Pairs> {
+    let mut pairs = Pairs {
+        simple: Default::default(),
+        classes: Default::default(),
+    };
+    if let Some(table) = cache.try_glyph_positioning()?.cloned() {
+        let position = match cache.position(Tag(*b"GPOS")) {
+            Some(position) => position,
+            _ => raise!("cannot find the glyph-positioning table"),
+        };
+        let mut tape = cache.tape.borrow_mut();
+        pairs.add_positioning(tape.deref_mut(), position, &table.borrow())?;
+    }
+    if pairs.simple.is_empty() && pairs.classes.is_empty() {
+        if let Some(table) = cache.try_kerning()?.cloned() {
+            pairs.add_kerning(&table.borrow());
+        }
+    }
+    Ok(pairs)
+}
+
+fn advance(value: &Option<Single>) -> Number {
+    value
+        .as_ref()
+        .and_then(|value| value.x_advance)
+        .map(Number::from)
+        .unwrap_or(0.0)
+}
+
+fn lookups(table: &GlyphPositioning) -> Vec<usize> {
+    let mut indices = table
+        .features
+        .headers
+        .iter()
+        .zip(table.features.records.iter())
+        .filter(|(header, _)| header.tag == b"kern")
+        .flat_map(|(_, record)| record.lookup_indices.iter().cloned().map(usize::from))
+        .collect::<Vec<_>>();
+    indices.sort();
+    indices.dedup();
+    indices
+}
+
+fn unclass(value: &Class) -> HashMap<GlyphID, u16> {
+    let mut values = HashMap::default();
+    match value {
+        Class::Format1(value) => {
+            let range = value.start_glyph_id..value.start_glyph_id + value.glyph_count;
+            values.extend(range.zip(value.indices.iter().cloned()));
+        }
+        Class::Format2(value) => {
+            for record in value.records.iter() {
+                for glyph_id in record.start_glyph_id..=record.end_glyph_id {
+                    values.insert(glyph_id, record.index);
+                }
+            }
+        }
+    }
+    values
+}
+
+fn uncover(value: &Coverage) -> Box<dyn Iterator<Item = GlyphID> + '_> {
+    match value {
+        Coverage::Format1(value) => Box::new(value.glyph_ids.iter().cloned()),
+        Coverage::Format2(value) => Box::new(
+            value
+                .records
+                .iter()
+                .flat_map(|record| record.start_glyph_id..=record.end_glyph_id),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::tables::glyph_positioning::GlyphPositioning;
+    use opentype::value::Read;
+
+    use super::{Kerning, Pairs};
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn extension() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x01, 0x00, 0x00, // majorVersion, minorVersion
+            0x00, 0x0A, 0x00, 0x0C, 0x00, 0x1A, // scriptListOffset, featureListOffset, lookupListOffset
+            0x00, 0x00, // scriptCount
+            0x00, 0x01, 0x6B, 0x65, 0x72, 0x6E, 0x00, 0x08, // featureCount, featureTag, featureOffset
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, // featureParamsOffset, lookupIndexCount, lookupListIndices
+            0x00, 0x01, 0x00, 0x04, // lookupCount, lookupOffsets
+            0x00, 0x09, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08, // lookupType, lookupFlag, subTableCount, subtableOffsets
+            0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x08, // posFormat, extensionLookupType, extensionOffset
+            0x00, 0x01, 0x00, 0x12, 0x00, 0x04, 0x00, 0x00, // posFormat, coverageOffset, valueFormat1, valueFormat2
+            0x00, 0x01, 0x00, 0x0C, // pairSetCount, pairSetOffsets
+            0x00, 0x01, 0x00, 0x02, 0xFF, 0xCE, // pairValueCount, secondGlyph, valueRecord1
+            0x00, 0x01, 0x00, 0x01, 0x00, 0x01, // coverageFormat, glyphCount, glyphArray
+        ];
+        let mut tape = Cursor::new(data);
+        let table = ok!(GlyphPositioning::read(&mut tape));
+        let mut pairs = Pairs {
+            simple: Default::default(),
+            classes: Default::default(),
+        };
+        ok!(pairs.add_positioning(&mut tape, 0, &table));
+        assert_eq!(pairs.get(1, 2), -50.0);
+        assert_eq!(pairs.get(2, 1), 0.0);
+    }
+
+    #[test]
+    fn kerning() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x03, // version, nTables
+            0x00, 0x00, 0x00, 0x14, 0x00, 0x01, // version, length, coverage
+            0x00, 0x01, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, // nPairs, searchRange, entrySelector, rangeShift
+            0x00, 0x01, 0x00, 0x02, 0xFF, 0xCE, // left, right, value
+            0x00, 0x00, 0x00, 0x14, 0x00, 0x05, // version, length, coverage
+            0x00, 0x01, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, // nPairs, searchRange, entrySelector, rangeShift
+            0x00, 0x01, 0x00, 0x02, 0x00, 0x64, // left, right, value
+            0x00, 0x00, 0xFF, 0xFF, 0x00, 0x01, // version, length, coverage
+            0x00, 0x01, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, // nPairs, searchRange, entrySelector, rangeShift
+            0x00, 0x03, 0x00, 0x04, 0xFF, 0xEC, // left, right, value
+        ];
+        let table = ok!(Kerning::read(&mut Cursor::new(data)));
+        assert_eq!(table.tables.len(), 3);
+        let mut pairs = Pairs {
+            simple: Default::default(),
+            classes: Default::default(),
+        };
+        pairs.add_kerning(&table);
+        assert_eq!(pairs.get(1, 2), -50.0);
+        assert_eq!(pairs.get(3, 4), -20.0);
+    }
+}
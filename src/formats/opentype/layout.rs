@@ -0,0 +1,72 @@
+use std::io::Result;
+
+use crate::formats::opentype::cache::Cache;
+use crate::formats::opentype::metrics;
+use crate::glyph::{Glyph, GlyphID};
+use crate::layout::{Layout, Placement};
+use crate::{Number, Offset};
+
+pub fn measure<T, F>(
+    cache: &mut Cache<T>,
+    text: &str,
+    size: Number,
+    spacing: Number,
+    mut draw: F,
+) -> Result<Layout>
+where
+    T: crate::Read,
+    F: FnMut(&mut Cache<T>, GlyphID) -> Result<Glyph>,
+{
+    let metrics = metrics::read(cache)?;
+    let mapping = cache.forward_mapping()?.clone();
+    let pairs = cache.pairs()?.clone();
+    let scale = size / metrics.granularity;
+    let mut layout = Layout {
+        line_height: (metrics.ascender - metrics.descender + metrics.line_gap) * scale,
+        ..Default::default()
+    };
+    let mut position = 0.0;
+    let mut previous = None;
+    for character in text.chars() {
+        let glyph_id = mapping.get(character).unwrap_or(0);
+        if let Some(previous) = previous {
+            position += pairs.get(previous, glyph_id) + spacing / scale;
+        }
+        let mut glyph = draw(cache, glyph_id)?;
+        let advance_width = glyph.advance_width;
+        glyph.scale(scale);
+        let offset = Offset(position * scale, 0.0);
+        let (min_x, min_y, max_x, max_y) = glyph.bounding_box;
+        let min = Offset(min_x, min_y) + offset;
+        let max = Offset(max_x, max_y) + offset;
+        let (min, max) = (
+            Offset(layout.bounding_box.0, layout.bounding_box.1).min(min),
+            Offset(layout.bounding_box.2, layout.bounding_box.3).max(max),
+        );
+        layout.bounding_box = (min.0, min.1, max.0, max.1);
+        position += advance_width;
+        layout.glyphs.push(Placement {
+            character,
+            glyph_id,
+            offset,
+            glyph,
+        });
+        previous = Some(glyph_id);
+    }
+    layout.advance_width = position * scale;
+    Ok(layout)
+}
+
+pub fn outline<T, F>(
+    cache: &mut Cache<T>,
+    text: &str,
+    size: Number,
+    spacing: Number,
+    draw: F,
+) -> Result<Glyph>
+where
+    T: crate::Read,
+    F: FnMut(&mut Cache<T>, GlyphID) -> Result<Glyph>,
+{
+    Ok(measure(cache, text, size, spacing, draw)?.outline())
+}
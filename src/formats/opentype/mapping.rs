@@ -1,48 +1,167 @@
 use std::collections::{BTreeSet, HashMap};
 use std::io::Result;
 
-use opentype::truetype::tables::character_mapping::{CharacterMapping, Encoding};
 use opentype::truetype::GlyphID;
 
-pub struct Forward(HashMap<u32, GlyphID>);
+use crate::formats::opentype::encoding::CharacterMapping;
 
-pub struct Reverse(HashMap<GlyphID, BTreeSet<u32>>);
+pub struct Forward {
+    values: HashMap<u32, GlyphID>,
+    // Ranges of characters mapped to a single glyph, which are looked up without expansion.
+    ranges: Vec<(u32, u32, GlyphID)>,
+}
+
+pub struct Reverse {
+    values: HashMap<GlyphID, BTreeSet<u32>>,
+    ranges: HashMap<GlyphID, Vec<(u32, u32)>>,
+}
 
 impl Forward {
-    pub fn new(character_mapping: &CharacterMapping) -> Result<Self> {
-        for encoding in character_mapping.encodings.iter() {
-            match encoding {
-                Encoding::Format0(encoding) => return Ok(Self(encoding.mapping())),
-                Encoding::Format4(encoding) => return Ok(Self(encoding.mapping())),
-                Encoding::Format6(encoding) => return Ok(Self(encoding.mapping())),
-                Encoding::Format12(encoding) => return Ok(Self(encoding.mapping())),
-                _ => {}
-            }
+    pub fn new(character_mapping: &CharacterMapping, selection: Option<usize>) -> Result<Self> {
+        if let Some(index) = character_mapping.choose(selection) {
+            return Ok(Self {
+                values: character_mapping.mapping(index),
+                ranges: character_mapping.ranges(index),
+            });
         }
         raise!("found no known character-to-glyph encoding")
     }
 
-    #[inline]
     pub fn get(&self, character: char) -> Option<GlyphID> {
-        self.0.get(&(character as u32)).copied()
+        let character = character as u32;
+        if let Some(glyph_id) = self.values.get(&character) {
+            return Some(*glyph_id);
+        }
+        let index = self.ranges.partition_point(|(_, end, _)| *end < character);
+        self.ranges
+            .get(index)
+            .filter(|(start, _, _)| *start <= character)
+            .map(|(_, _, glyph_id)| *glyph_id)
     }
 }
 
 impl Reverse {
     pub fn new(mapping: &Forward) -> Self {
         let mut values = HashMap::<_, BTreeSet<_>>::default();
-        for (character_id, glyph_id) in &mapping.0 {
+        for (character_id, glyph_id) in &mapping.values {
             values.entry(*glyph_id).or_default().insert(*character_id);
         }
-        Self(values)
+        let mut ranges = HashMap::<_, Vec<_>>::default();
+        for (start, end, glyph_id) in &mapping.ranges {
+            ranges.entry(*glyph_id).or_default().push((*start, *end));
+        }
+        Self { values, ranges }
     }
 
-    #[inline]
     pub fn get(&self, glyph_id: GlyphID) -> Option<char> {
-        self.0
+        let first = self
+            .values
             .get(&glyph_id)
             .and_then(BTreeSet::first)
             .cloned()
-            .and_then(char::from_u32)
+            .and_then(char::from_u32);
+        let ranges = self
+            .ranges
+            .get(&glyph_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|(start, end)| (*start..=*end).find_map(char::from_u32));
+        first.into_iter().chain(ranges).min()
+    }
+
+    pub fn get_all(&self, glyph_id: GlyphID) -> impl Iterator<Item = char> + '_ {
+        self.values
+            .get(&glyph_id)
+            .into_iter()
+            .flatten()
+            .cloned()
+            .chain(
+                self.ranges
+                    .get(&glyph_id)
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(start, end)| *start..=*end),
+            )
+            .filter_map(char::from_u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::{Forward, Reverse};
+    use crate::formats::opentype::encoding::CharacterMapping;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn ranges() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x01, // version, numTables
+            0x00, 0x03, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x0C, // platformID, encodingID, offset
+            0x00, 0x0D, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, // format, reserved, length
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, // language, numGroups
+            0x00, 0x00, 0xD8, 0x00, 0x00, 0x00, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x01,
+            0x00, 0x01, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x02,
+        ];
+        let table = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+        let mapping = ok!(Forward::new(&table, None));
+        assert_eq!(mapping.get('A'), None);
+        assert_eq!(mapping.get('\u{E000}'), Some(1));
+        assert_eq!(mapping.get('\u{1F600}'), Some(2));
+        assert_eq!(mapping.get('\u{10FFFF}'), Some(2));
+        let mapping = Reverse::new(&mapping);
+        assert_eq!(mapping.get(1), Some('\u{E000}'));
+        assert_eq!(mapping.get(2), Some('\u{10000}'));
+        assert_eq!(mapping.get_all(2).count(), 0x100000);
+    }
+
+    #[test]
+    fn symbol() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x01, // version, numTables
+            0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, // platformID, encodingID, offset
+            0x00, 0x06, 0x00, 0x0C, 0x00, 0x00, // format, length, language
+            0xF0, 0x41, 0x00, 0x02, 0x00, 0x05, 0x00, 0x06, // firstCode, entryCount, glyphIdArray
+        ];
+        let table = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+        let mapping = ok!(Forward::new(&table, None));
+        assert_eq!(mapping.get('A'), Some(5));
+        assert_eq!(mapping.get('B'), Some(6));
+        assert_eq!(mapping.get('\u{F041}'), Some(5));
+        assert_eq!(mapping.get('C'), None);
+        let mut ranges = table.characters(0);
+        ranges.sort();
+        assert_eq!(ranges, [(0x41, 0x42), (0xF041, 0xF042)]);
+    }
+
+    #[test]
+    fn multibyte() {
+        #[rustfmt::skip]
+        let mut data = vec![
+            0x00, 0x00, 0x00, 0x01, // version, numTables
+            0x00, 0x03, 0x00, 0x02, 0x00, 0x00, 0x00, 0x0C, // platformID, encodingID, offset
+            0x00, 0x02, 0x00, 0x00, 0x00, 0x00, // format, length, language
+        ];
+        for high in 0..256 {
+            data.extend([0x00, if high == 0x82 { 0x08 } else { 0x00 }]);
+        }
+        data.extend([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        data.extend([0x00, 0xA0, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02]);
+        data.extend([0x00, 0x05]);
+        let size = (data.len() - 12) as u16;
+        data[14..16].copy_from_slice(&size.to_be_bytes());
+        let table = ok!(CharacterMapping::read(&mut Cursor::new(data)));
+        let encoding = &table.encodings[ok!(table.choose(None))];
+        assert_eq!(encoding.codes().get(&0x82A0), Some(&5));
+        assert!(encoding.characters().is_empty());
+        let mapping = ok!(Forward::new(&table, None));
+        assert_eq!(mapping.get('\u{82A0}'), None);
+        assert_eq!(mapping.get('\u{3042}'), None);
     }
 }
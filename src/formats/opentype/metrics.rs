@@ -1,19 +1,33 @@
 use std::io::Result;
 
-use opentype::truetype::tables::HorizontalMetrics;
+use opentype::truetype::tables::{HorizontalMetrics, PostScript, WindowsMetrics};
 use opentype::truetype::GlyphID;
 
 use crate::formats::opentype::cache::{Cache, Reference};
+use crate::formats::opentype::vertical::{VerticalMetrics, VerticalOrigin};
 use crate::Number;
 
 pub struct Metrics {
     horizontal_metrics: Reference<HorizontalMetrics>,
+    vertical: Vertical,
+}
+
+pub enum Vertical {
+    Metrics(Reference<VerticalMetrics>),
+    Origin {
+        advance_height: Number,
+        ascender: Number,
+        origins: Option<Reference<VerticalOrigin>>,
+    },
 }
 
 impl Metrics {
     #[inline]
-    pub fn new(horizontal_metrics: Reference<HorizontalMetrics>) -> Self {
-        Metrics { horizontal_metrics }
+    pub fn new(horizontal_metrics: Reference<HorizontalMetrics>, vertical: Vertical) -> Self {
+        Metrics {
+            horizontal_metrics,
+            vertical,
+        }
     }
 
     #[inline]
@@ -21,13 +35,107 @@ impl Metrics {
         let (advance_width, left_side_bearing) = self.horizontal_metrics.borrow().get(glyph_id);
         (advance_width.into(), left_side_bearing.into())
     }
+
+    pub fn get_vertical(&self, glyph_id: GlyphID, max_y: Number) -> (Number, Number) {
+        match &self.vertical {
+            Vertical::Metrics(vertical_metrics) => {
+                let (advance_height, top_side_bearing) = vertical_metrics.borrow().get(glyph_id);
+                (advance_height.into(), top_side_bearing.into())
+            }
+            Vertical::Origin {
+                advance_height,
+                ascender,
+                origins,
+            } => {
+                let origin = match origins {
+                    Some(origins) => origins.borrow().get(glyph_id).into(),
+                    _ => *ascender,
+                };
+                (*advance_height, origin - max_y)
+            }
+        }
+    }
 }
 
-pub fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<crate::Metrics> {
-    use opentype::truetype::tables::WindowsMetrics;
+pub fn vertical<T: crate::Read>(cache: &mut Cache<T>) -> Result<Vertical> {
+    if let Some(vertical_metrics) = cache.try_vertical_metrics()? {
+        return Ok(Vertical::Metrics(vertical_metrics.clone()));
+    }
+    let (ascender, descender, _) = typographic(cache)?;
+    Ok(Vertical::Origin {
+        advance_height: ascender - descender,
+        ascender,
+        origins: cache.try_vertical_origin()?.cloned(),
+    })
+}
+
+pub fn read_line<T: crate::Read>(
+    cache: &mut Cache<T>,
+    policy: crate::LinePolicy,
+) -> Result<crate::LineMetrics> {
+    let horizontal_header = *cache.horizontal_header()?.borrow();
+    let windows_metrics = cache.windows_metrics()?.borrow();
+    macro_rules! get(
+        ($($version:ident),+) => (
+            match &*windows_metrics {
+                $(WindowsMetrics::$version(ref table) => (
+                    table.selection_flags.should_use_typographic_metrics(),
+                    (
+                        Number::from(table.typographic_ascender),
+                        Number::from(table.typographic_descender),
+                        Number::from(table.typographic_line_gap),
+                    ),
+                    (
+                        Number::from(table.windows_ascender),
+                        -Number::from(table.windows_descender),
+                    ),
+                ),)*
+            }
+        );
+    );
+    let (typographic_flag, typographic, windows) =
+        get!(Version0, Version1, Version2, Version3, Version4, Version5);
+    let horizontal = (
+        Number::from(horizontal_header.ascender),
+        Number::from(horizontal_header.descender),
+        Number::from(horizontal_header.line_gap),
+    );
+    let (ascender, descender, line_gap) =
+        choose(policy, typographic_flag, typographic, horizontal, windows);
+    Ok(crate::LineMetrics {
+        ascender,
+        descender,
+        line_gap,
+    })
+}
+
+pub fn read_vertical<T: crate::Read>(cache: &mut Cache<T>) -> Result<crate::VerticalMetrics> {
+    let granularity = cache.font_header()?.borrow().units_per_em.into();
+    if let Some(vertical_header) = cache.try_vertical_header()? {
+        let vertical_header = vertical_header.borrow();
+        return Ok(crate::VerticalMetrics {
+            granularity,
+            ascender: vertical_header.ascender.into(),
+            descender: vertical_header.descender.into(),
+            line_gap: vertical_header.line_gap.into(),
+            synthesized: false,
+        });
+    }
+    let (ascender, descender, line_gap) = typographic(cache)?;
+    Ok(crate::VerticalMetrics {
+        granularity,
+        ascender: (ascender - descender) / 2.0,
+        descender: (descender - ascender) / 2.0,
+        line_gap,
+        synthesized: true,
+    })
+}
 
+pub fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<crate::Metrics> {
     let font_header = cache.font_header()?.clone();
     let font_header = font_header.borrow();
+    let horizontal_header = cache.try_horizontal_header()?.map(|table| *table.borrow());
+    let postscript = cache.try_postscript()?.cloned();
     let windows_metrics = cache.windows_metrics()?.borrow();
     macro_rules! get(
         (@version0 $($version:ident),+) => (
@@ -38,7 +146,26 @@ pub fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<crate::Metrics> {
                     table.typographic_descender.into(),
                     -Number::from(table.windows_descender),
                     table.typographic_line_gap.into(),
+                    (table.strikeout_position.into(), table.strikeout_size.into()),
+                    (
+                        (table.subscript_x_size.into(), table.subscript_y_size.into()),
+                        (table.subscript_x_offset.into(), -Number::from(table.subscript_y_offset)),
+                    ),
+                    (
+                        (table.superscript_x_size.into(), table.superscript_y_size.into()),
+                        (table.superscript_x_offset.into(), table.superscript_y_offset.into()),
+                    ),
+                ),)*
+            }
+        );
+        (@postscript $($version:ident),+) => (
+            match postscript.as_ref().map(|table| table.borrow()).as_deref() {
+                $(Some(PostScript::$version(ref table)) => (
+                    table.italic_angle.into(),
+                    table.underline_position.into(),
+                    table.underline_thickness.into(),
                 ),)*
+                _ => (0.0, Number::NAN, Number::NAN),
             }
         );
         (@version2 $($version:ident),+) => (
@@ -54,8 +181,18 @@ pub fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<crate::Metrics> {
             }
         );
     );
-    let (clipping_ascender, ascender, descender, clipping_descender, line_gap) =
-        get!(@version0 Version0, Version1, Version2, Version3, Version4, Version5);
+    let (
+        clipping_ascender,
+        ascender,
+        descender,
+        clipping_descender,
+        line_gap,
+        (strikeout_position, strikeout_thickness),
+        (subscript_size, subscript_offset),
+        (superscript_size, superscript_offset),
+    ) = get!(@version0 Version0, Version1, Version2, Version3, Version4, Version5);
+    let (italic_angle, underline_position, underline_thickness) =
+        get!(@postscript Version1, Version2, Version3);
     let (cap_height, x_height) = get!(@version2 Version2, Version3, Version4, Version5);
     Ok(crate::Metrics {
         granularity: font_header.units_per_em.into(),
@@ -71,5 +208,104 @@ pub fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<crate::Metrics> {
         descender,
         clipping_descender,
         line_gap,
+        underline_position,
+        underline_thickness,
+        strikeout_position,
+        strikeout_thickness,
+        subscript_size,
+        subscript_offset,
+        superscript_size,
+        superscript_offset,
+        italic_angle,
+        caret_slope: match horizontal_header {
+            Some(ref table) => (table.caret_slope_rise.into(), table.caret_slope_run.into()),
+            _ => (1.0, 0.0),
+        },
+        caret_offset: match horizontal_header {
+            Some(ref table) => table.caret_offset.into(),
+            _ => 0.0,
+        },
     })
 }
+
+fn choose(
+    policy: crate::LinePolicy,
+    typographic_flag: bool,
+    typographic: (Number, Number, Number),
+    horizontal: (Number, Number, Number),
+    windows: (Number, Number),
+) -> (Number, Number, Number) {
+    use crate::LinePolicy;
+
+    let defined =
+        |(ascender, descender, _): (Number, Number, Number)| ascender != 0.0 || descender != 0.0;
+    match policy {
+        LinePolicy::Browser if typographic_flag => typographic,
+        LinePolicy::Browser if defined(horizontal) => horizontal,
+        LinePolicy::Browser if defined(typographic) => typographic,
+        LinePolicy::Browser => (windows.0, windows.1, 0.0),
+        LinePolicy::Macintosh if defined(horizontal) => horizontal,
+        LinePolicy::Macintosh => typographic,
+        LinePolicy::Windows if typographic_flag => typographic,
+        LinePolicy::Windows => {
+            let extent = (horizontal.0 - horizontal.1) - (windows.0 - windows.1);
+            (windows.0, windows.1, (horizontal.2 + extent).max(0.0))
+        }
+    }
+}
+
+// The typographic ascender, descender, and line gap falling back to the horizontal header.
+fn typographic<T: crate::Read>(cache: &mut Cache<T>) -> Result<(Number, Number, Number)> {
+    if let Some(windows_metrics) = cache.try_windows_metrics()? {
+        macro_rules! get(
+            ($($version:ident),+) => (
+                match &*windows_metrics.borrow() {
+                    $(WindowsMetrics::$version(ref table) => (
+                        table.typographic_ascender.into(),
+                        table.typographic_descender.into(),
+                        table.typographic_line_gap.into(),
+                    ),)*
+                }
+            );
+        );
+        return Ok(get!(
+            Version0, Version1, Version2, Version3, Version4, Version5
+        ));
+    }
+    let horizontal_header = cache.horizontal_header()?.borrow();
+    Ok((
+        horizontal_header.ascender.into(),
+        horizontal_header.descender.into(),
+        horizontal_header.line_gap.into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LinePolicy;
+
+    #[test]
+    fn choose() {
+        let typographic = (800.0, -200.0, 90.0);
+        let horizontal = (900.0, -250.0, 10.0);
+        let windows = (1000.0, -300.0);
+        let choose = |policy, flag| super::choose(policy, flag, typographic, horizontal, windows);
+        assert_eq!(choose(LinePolicy::Browser, false), horizontal);
+        assert_eq!(choose(LinePolicy::Browser, true), typographic);
+        assert_eq!(choose(LinePolicy::Macintosh, true), horizontal);
+        assert_eq!(choose(LinePolicy::Windows, true), typographic);
+        assert_eq!(choose(LinePolicy::Windows, false), (1000.0, -300.0, 0.0));
+
+        let windows = (920.0, -250.0);
+        let choose = |policy| super::choose(policy, false, typographic, horizontal, windows);
+        assert_eq!(choose(LinePolicy::Windows), (920.0, -250.0, 0.0));
+        let horizontal = (950.0, -250.0, 40.0);
+        let choose = |policy| super::choose(policy, false, typographic, horizontal, windows);
+        assert_eq!(choose(LinePolicy::Windows), (920.0, -250.0, 70.0));
+
+        let horizontal = (0.0, 0.0, 0.0);
+        let choose = |policy| super::choose(policy, false, typographic, horizontal, windows);
+        assert_eq!(choose(LinePolicy::Browser), typographic);
+        assert_eq!(choose(LinePolicy::Macintosh), typographic);
+    }
+}
@@ -9,14 +9,24 @@ pub mod tables;
 pub mod timestamps;
 
 pub(crate) mod cache;
+pub(crate) mod layout;
 pub(crate) mod metrics;
 
+mod bitmap;
+mod color;
+mod documents;
+mod encoding;
 mod font;
+mod gzip;
+mod hinting;
+mod kerning;
 mod mapping;
 mod postscript;
 mod truetype;
+mod variants;
+mod vertical;
 
-pub use self::font::{write, Disposition, Font};
+pub use self::font::{save, write, Disposition, Font};
 
 use std::cell::RefCell;
 use std::io::Result;
@@ -1,12 +1,671 @@
 //! Multilingual strings.
 
+use std::collections::HashMap;
 use std::io::Result;
+use std::sync::OnceLock;
+
+use opentype::truetype::tables::names::language::{Macintosh, Windows};
+use opentype::truetype::tables::names::{EncodingID, LanguageID, NameID, PlatformID};
 
 use crate::formats::opentype::cache::{Cache, Reference};
 
+type Key = (PlatformID, EncodingID, LanguageID, NameID);
+type Records = Vec<(Key, String)>;
+type Raw = Vec<(Key, Vec<u8>)>;
+
 /// Multilingual strings.
-pub type Names = Reference<opentype::truetype::tables::Names>;
+#[derive(Clone)]
+pub struct Names {
+    table: Reference<opentype::truetype::tables::Names>,
+}
+
+impl Names {
+    /// Return the value of a name record preferring Windows English, Unicode, and then Macintosh
+    /// English records.
+    pub fn get(&self, name_id: NameID) -> Option<String> {
+        self.table
+            .borrow()
+            .iter()
+            .filter(|((_, _, _, other), value)| *other == name_id && value.is_some())
+            .min_by_key(|((platform_id, encoding_id, language_id, _), _)| {
+                rank(*platform_id, *encoding_id, *language_id)
+            })
+            .and_then(|(_, value)| value)
+    }
+
+    /// Return the value of a name record in a language given as a BCP 47 tag.
+    ///
+    /// The tag is shortened one subtag at a time, keeping the script if any, and then English and
+    /// any other language are tried in this order.
+    pub fn get_in(&self, name_id: NameID, language: &str) -> Option<String> {
+        let table = self.table.borrow();
+        let language_tags = table.language_tags().collect::<Vec<_>>();
+        let records = table
+            .iter()
+            .filter(|((_, _, _, other), value)| *other == name_id && value.is_some())
+            .filter_map(|((platform_id, encoding_id, language_id, _), value)| {
+                Some((
+                    tag(language_id, &language_tags)?,
+                    rank(platform_id, encoding_id, language_id),
+                    value,
+                ))
+            })
+            .collect::<Vec<_>>();
+        for candidate in candidates(language)
+            .iter()
+            .map(String::as_str)
+            .chain(["en"])
+        {
+            let prefix = format!("{candidate}-");
+            let value = records
+                .iter()
+                .filter(|(tag, _, _)| tag == candidate)
+                .min_by_key(|(_, rank, _)| *rank)
+                .or_else(|| {
+                    records
+                        .iter()
+                        .filter(|(tag, _, _)| tag.starts_with(&prefix))
+                        .min_by_key(|(_, rank, _)| *rank)
+                });
+            if let Some((_, _, value)) = value {
+                return value.clone();
+            }
+        }
+        drop(table);
+        self.get(name_id)
+    }
+
+    /// Return the BCP 47 tags of the languages a name record is available in.
+    pub fn languages(&self, name_id: NameID) -> Vec<String> {
+        let table = self.table.borrow();
+        let language_tags = table.language_tags().collect::<Vec<_>>();
+        let mut values = table
+            .iter()
+            .filter(|((_, _, _, other), _)| *other == name_id)
+            .filter_map(|((_, _, language_id, _), _)| tag(language_id, &language_tags))
+            .collect::<Vec<_>>();
+        values.sort();
+        values.dedup();
+        values
+    }
+
+    /// Return the typographic family name in a language falling back to the family name.
+    #[inline]
+    pub fn typographic_family_in(&self, language: &str) -> Option<String> {
+        self.first_in(
+            &[NameID::TypographicFamilyName, NameID::FontFamilyName],
+            language,
+        )
+    }
+
+    /// Return the typographic subfamily name in a language falling back to the subfamily name.
+    #[inline]
+    pub fn typographic_subfamily_in(&self, language: &str) -> Option<String> {
+        self.first_in(
+            &[NameID::TypographicSubfamilyName, NameID::FontSubfamilyName],
+            language,
+        )
+    }
+
+    /// Set the value of a name record in a language given as a BCP 47 tag.
+    ///
+    /// The records in the language are updated on all platforms, dropping Macintosh ones whose
+    /// encoding cannot represent the value, and a Windows record is added if there is none.
+    /// Unicode records, which have no language, are treated as English.
+    pub fn set(&self, name_id: NameID, language: &str, value: &str) -> Result<()> {
+        let language = normalize(language);
+        let (mut records, mut raw, mut language_tags) = self.records()?;
+        raw.retain(|(key, _)| {
+            if matches(key, name_id, &language, &language_tags) {
+                records.push((*key, Default::default()));
+                return false;
+            }
+            true
+        });
+        let mut context = Default::default();
+        let mut found = false;
+        records.retain_mut(|(key, other)| {
+            if !matches(key, name_id, &language, &language_tags) {
+                return true;
+            }
+            if key.0 == PlatformID::Macintosh {
+                let records = [(*key, value)];
+                let language_tags = Vec::<String>::new();
+                let table = opentype::truetype::tables::Names::from_iter(
+                    records,
+                    language_tags,
+                    &mut context,
+                );
+                if table.is_err() {
+                    return false;
+                }
+            }
+            found |= key.0 == PlatformID::Windows;
+            *other = value.into();
+            true
+        });
+        if !found {
+            let language_id = match windows(&language) {
+                Some(language_id) => language_id,
+                _ => {
+                    let index = match language_tags.iter().position(|other| {
+                        other.as_deref().map(normalize).as_ref() == Some(&language)
+                    }) {
+                        Some(index) => index,
+                        _ => {
+                            language_tags.push(Some(language.clone()));
+                            language_tags.len() - 1
+                        }
+                    };
+                    LanguageID::Other(index as u16)
+                }
+            };
+            records.push(((PlatformID::Windows, 1, language_id, name_id), value.into()));
+        }
+        self.replace(records, raw, language_tags)
+    }
+
+    /// Remove the name records in a language given as a BCP 47 tag.
+    ///
+    /// Unicode records, which have no language, are treated as English.
+    pub fn remove(&self, name_id: NameID, language: &str) -> Result<()> {
+        let language = normalize(language);
+        let (mut records, mut raw, language_tags) = self.records()?;
+        records.retain(|(key, _)| !matches(key, name_id, &language, &language_tags));
+        raw.retain(|(key, _)| !matches(key, name_id, &language, &language_tags));
+        self.replace(records, raw, language_tags)
+    }
+
+    /// Return the family name.
+    #[inline]
+    pub fn family(&self) -> Option<String> {
+        self.get(NameID::FontFamilyName)
+    }
+
+    /// Return the subfamily name.
+    #[inline]
+    pub fn subfamily(&self) -> Option<String> {
+        self.get(NameID::FontSubfamilyName)
+    }
+
+    /// Return the typographic family name falling back to the family name.
+    #[inline]
+    pub fn typographic_family(&self) -> Option<String> {
+        self.first(&[NameID::TypographicFamilyName, NameID::FontFamilyName])
+    }
+
+    /// Return the typographic subfamily name falling back to the subfamily name.
+    #[inline]
+    pub fn typographic_subfamily(&self) -> Option<String> {
+        self.first(&[NameID::TypographicSubfamilyName, NameID::FontSubfamilyName])
+    }
+
+    /// Return the weight-width-slope family name falling back to the typographic family name.
+    #[inline]
+    pub fn wws_family(&self) -> Option<String> {
+        self.first(&[
+            NameID::WWSFamilyName,
+            NameID::TypographicFamilyName,
+            NameID::FontFamilyName,
+        ])
+    }
+
+    /// Return the weight-width-slope subfamily name falling back to the typographic subfamily
+    /// name.
+    #[inline]
+    pub fn wws_subfamily(&self) -> Option<String> {
+        self.first(&[
+            NameID::WWSSubfamilyName,
+            NameID::TypographicSubfamilyName,
+            NameID::FontSubfamilyName,
+        ])
+    }
+
+    /// Return the full name.
+    #[inline]
+    pub fn full_name(&self) -> Option<String> {
+        self.get(NameID::FullFontName)
+    }
+
+    /// Return the PostScript name.
+    #[inline]
+    pub fn postscript_name(&self) -> Option<String> {
+        self.get(NameID::PostScriptFontName)
+    }
+
+    /// Return the version.
+    #[inline]
+    pub fn version(&self) -> Option<String> {
+        self.get(NameID::VersionString)
+    }
+
+    /// Return the designer.
+    #[inline]
+    pub fn designer(&self) -> Option<String> {
+        self.get(NameID::DesignerName)
+    }
+
+    /// Return the license description.
+    #[inline]
+    pub fn license(&self) -> Option<String> {
+        self.get(NameID::LicenseDescription)
+    }
+
+    /// Return the license URL.
+    #[inline]
+    pub fn license_url(&self) -> Option<String> {
+        self.get(NameID::LicenseURL)
+    }
+
+    fn first(&self, name_ids: &[NameID]) -> Option<String> {
+        name_ids.iter().find_map(|name_id| self.get(*name_id))
+    }
+
+    // Return the decodable records along with the bytes of the undecodable ones, which are written
+    // back unchanged.
+    fn records(&self) -> Result<(Records, Raw, Vec<Option<String>>)> {
+        use opentype::truetype::tables::Names::*;
+
+        let table = self.table.borrow();
+        let (entries, data) = match &*table {
+            Format0(ref table) => (&table.records, &table.data),
+            Format1(ref table) => (&table.records, &table.data),
+        };
+        let (mut records, mut raw) = (vec![], vec![]);
+        for (entry, (key, value)) in entries.iter().zip(table.iter()) {
+            match value {
+                Some(value) => records.push((key, value)),
+                _ => {
+                    let (offset, size) = (entry.offset as usize, entry.size as usize);
+                    match data.get(offset..(offset + size)) {
+                        Some(value) => raw.push((key, value.to_vec())),
+                        _ => raise!("found a malformed name record ({key:?})"),
+                    }
+                }
+            }
+        }
+        let language_tags = table.language_tags().collect::<Vec<_>>();
+        if language_tags.iter().any(Option::is_none) {
+            raise!("found an undecodable language tag");
+        }
+        Ok((records, raw, language_tags))
+    }
+
+    fn replace(
+        &self,
+        mut records: Records,
+        raw: Raw,
+        language_tags: Vec<Option<String>>,
+    ) -> Result<()> {
+        use opentype::truetype::tables::names::Record;
+        use opentype::truetype::tables::Names::*;
+
+        records.sort_by_key(|((platform_id, encoding_id, language_id, name_id), _)| {
+            (
+                u16::from(*platform_id),
+                *encoding_id,
+                u16::from(*language_id),
+                u16::from(*name_id),
+            )
+        });
+        let mut table = opentype::truetype::tables::Names::from_iter(
+            records,
+            language_tags.into_iter().flatten(),
+            &mut Default::default(),
+        )?;
+        // The storage is read up to the end of the last name record, and therefore, the language
+        // tags are moved to the front.
+        if let opentype::truetype::tables::Names::Format1(ref mut table) = table {
+            let size = table
+                .language_tags
+                .first()
+                .map(|tag| tag.offset as usize)
+                .unwrap_or(table.data.len());
+            table.data.rotate_left(size);
+            let other = table.data.len() - size;
+            for record in table.records.iter_mut() {
+                record.offset = match u16::try_from(record.offset as usize + other) {
+                    Ok(value) => value,
+                    _ => raise!("found a name record beyond the storage limit"),
+                };
+            }
+            for tag in table.language_tags.iter_mut() {
+                tag.offset = match (tag.offset as usize).checked_sub(size) {
+                    Some(value) => value as u16,
+                    _ => raise!("found a malformed language tag"),
+                };
+            }
+        }
+        let (entries, data, count, offset) = match table {
+            Format0(ref mut table) => (
+                &mut table.records,
+                &mut table.data,
+                &mut table.count,
+                &mut table.offset,
+            ),
+            Format1(ref mut table) => (
+                &mut table.records,
+                &mut table.data,
+                &mut table.count,
+                &mut table.offset,
+            ),
+        };
+        for ((platform_id, encoding_id, language_id, name_id), value) in raw {
+            let (position, size) = match (u16::try_from(data.len()), u16::try_from(value.len())) {
+                (Ok(position), Ok(size)) => (position, size),
+                _ => raise!("found a name record beyond the storage limit"),
+            };
+            data.extend(value);
+            entries.push(Record {
+                platform_id,
+                encoding_id,
+                language_id,
+                name_id,
+                size,
+                offset: position,
+            });
+        }
+        entries.sort_by_key(|record| {
+            (
+                u16::from(record.platform_id),
+                record.encoding_id,
+                u16::from(record.language_id),
+                u16::from(record.name_id),
+            )
+        });
+        *offset += 12 * (entries.len() as u16 - *count);
+        *count = entries.len() as u16;
+        *self.table.borrow_mut() = table;
+        Ok(())
+    }
+
+    fn first_in(&self, name_ids: &[NameID], language: &str) -> Option<String> {
+        name_ids
+            .iter()
+            .find_map(|name_id| self.get_in(*name_id, language))
+    }
+}
+
+dereference! { Names::table => Reference<opentype::truetype::tables::Names> }
 
 pub(crate) fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Names> {
-    Ok(cache.names()?.clone())
+    Ok(Names {
+        table: cache.names()?.clone(),
+    })
+}
+
+pub(crate) fn try_read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Option<Names>> {
+    Ok(cache.try_names()?.cloned().map(|table| Names { table }))
+}
+
+pub(crate) fn set<T: crate::Read>(
+    cache: &mut Cache<T>,
+    name_id: NameID,
+    language: &str,
+    value: &str,
+) -> Result<()> {
+    read(cache)?.set(name_id, language, value)
+}
+
+pub(crate) fn remove<T: crate::Read>(
+    cache: &mut Cache<T>,
+    name_id: NameID,
+    language: &str,
+) -> Result<()> {
+    read(cache)?.remove(name_id, language)
+}
+
+// Check if a record is of a name in a language treating Unicode records as English.
+fn matches(key: &Key, name_id: NameID, language: &str, language_tags: &[Option<String>]) -> bool {
+    key.3 == name_id
+        && match key.0 {
+            PlatformID::Unicode => language == "en",
+            _ => tag(key.2, language_tags).as_deref() == Some(language),
+        }
+}
+
+// Windows US English, other Windows English, Unicode, Macintosh English, and other Windows in
+// this order.
+fn rank(platform_id: PlatformID, encoding_id: u16, language_id: LanguageID) -> usize {
+    let english = matches!(language_id.tag::<String>(&[]), Some(tag) if tag.starts_with("en"));
+    match (platform_id, encoding_id, u16::from(language_id)) {
+        (PlatformID::Windows, 1 | 10, 0x0409) => 0,
+        (PlatformID::Windows, 1 | 10, _) if english => 1,
+        (PlatformID::Unicode, _, _) => 2,
+        (PlatformID::Macintosh, 0, 0) => 3,
+        (PlatformID::Windows, 1 | 10, _) => 4,
+        _ => 5,
+    }
+}
+
+// The shortened forms of a tag with the script implied for Chinese, stopping at the script.
+fn candidates(language: &str) -> Vec<String> {
+    let language = normalize(language);
+    let mut values = vec![language.clone()];
+    let mut value = language.as_str();
+    while let Some(index) = value.rfind('-') {
+        let (rest, last) = (&value[..index], &value[(index + 1)..]);
+        if last.len() == 4
+            && last
+                .chars()
+                .all(|character| character.is_ascii_alphabetic())
+        {
+            break;
+        }
+        value = rest;
+        values.push(value.into());
+    }
+    values
+}
+
+fn normalize(value: &str) -> String {
+    let value = value.replace('_', "-").to_ascii_lowercase();
+    match value.as_str() {
+        "zh" | "zh-cn" => "zh-hans-cn".into(),
+        "zh-sg" => "zh-hans-sg".into(),
+        "zh-tw" => "zh-hant-tw".into(),
+        "zh-hk" => "zh-hant-hk".into(),
+        "zh-mo" => "zh-hant-mo".into(),
+        _ => match value.split_once('-') {
+            Some(pair) if REGIONS.contains(&pair) => pair.0.into(),
+            _ => value,
+        },
+    }
+}
+
+// The regions implied by languages, which Windows language identifiers omit.
+#[rustfmt::skip]
+const REGIONS: &[(&str, &str)] = &[
+    ("ar", "eg"), ("bg", "bg"), ("bn", "bd"), ("ca", "es"), ("cs", "cz"), ("da", "dk"),
+    ("de", "de"), ("el", "gr"), ("en", "us"), ("es", "es"), ("et", "ee"), ("fa", "ir"),
+    ("fi", "fi"), ("fr", "fr"), ("he", "il"), ("hi", "in"), ("hr", "hr"), ("hu", "hu"),
+    ("id", "id"), ("it", "it"), ("ja", "jp"), ("ko", "kr"), ("lt", "lt"), ("lv", "lv"),
+    ("ms", "my"), ("nb", "no"), ("nl", "nl"), ("pl", "pl"), ("pt", "br"), ("ro", "ro"),
+    ("ru", "ru"), ("sk", "sk"), ("sl", "si"), ("sr", "rs"), ("sv", "se"), ("th", "th"),
+    ("tr", "tr"), ("uk", "ua"), ("vi", "vn"),
+];
+
+fn tag(language_id: LanguageID, language_tags: &[Option<String>]) -> Option<String> {
+    // The opentype crate gives "es", "cz", and "si", which are Spanish, a region, and Sinhala,
+    // while ISO 639-1 assigns the following codes to the languages listed under Macintosh
+    // language identifiers 27, 38, and 40 in the specification of the naming table.
+    let value = match language_id {
+        LanguageID::Macintosh(Macintosh::Estonian) => "et",
+        LanguageID::Macintosh(Macintosh::Czech) => "cs",
+        LanguageID::Macintosh(Macintosh::Slovenian) => "sl",
+        _ => language_id.tag(language_tags)?,
+    };
+    Some(normalize(value))
+}
+
+fn windows(language: &str) -> Option<LanguageID> {
+    static LANGUAGES: OnceLock<HashMap<String, Windows>> = OnceLock::new();
+    LANGUAGES
+        .get_or_init(|| {
+            let mut languages = HashMap::new();
+            for value in (0..0x8000).filter_map(|value| Windows::try_from(value).ok()) {
+                languages
+                    .entry(normalize(<&'static str>::from(value)))
+                    .or_insert(value);
+            }
+            languages
+        })
+        .get(language)
+        .copied()
+        .map(LanguageID::Windows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use opentype::truetype::tables::names::language::{Macintosh, Windows};
+    use opentype::truetype::tables::names::{LanguageID, NameID, PlatformID};
+
+    use super::Names;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn get_in() {
+        let family = NameID::FontFamilyName;
+        #[rustfmt::skip]
+        let records = [
+            ((PlatformID::Macintosh, 0, LanguageID::Macintosh(Macintosh::English), family), "Mac"),
+            ((PlatformID::Windows, 1, LanguageID::Windows(Windows::EnglishUnitedStates), family), "Windows"),
+            ((PlatformID::Windows, 1, LanguageID::Windows(Windows::ChineseTaiwan), family), "繁體 TW"),
+            ((PlatformID::Windows, 1, LanguageID::Windows(Windows::ChineseHongKongSAR), family), "繁體 HK"),
+            ((PlatformID::Windows, 1, LanguageID::Windows(Windows::ChineseChina), family), "简体"),
+            ((PlatformID::Windows, 1, LanguageID::Other(0), family), "Deutsch"),
+        ];
+        let table = ok!(opentype::truetype::tables::Names::from_iter(
+            records,
+            ["de-CH"],
+            &mut Default::default(),
+        ));
+        let names = Names {
+            table: Rc::new(RefCell::new(table)),
+        };
+        assert_eq!(ok!(names.get_in(family, "zh-Hant-TW")), "繁體 TW");
+        assert_eq!(ok!(names.get_in(family, "zh-HK")), "繁體 HK");
+        assert_eq!(ok!(names.get_in(family, "zh-Hant-MO")), "繁體 TW");
+        assert_eq!(ok!(names.get_in(family, "zh-CN")), "简体");
+        assert_eq!(ok!(names.get_in(family, "zh-Hans")), "简体");
+        assert_eq!(ok!(names.get_in(family, "de")), "Deutsch");
+        assert_eq!(ok!(names.get_in(family, "de-CH")), "Deutsch");
+        assert_eq!(ok!(names.get_in(family, "en-GB")), "Windows");
+        assert_eq!(ok!(names.get_in(family, "fr")), "Windows");
+        assert_eq!(
+            names.languages(family),
+            ["de-ch", "en", "zh-hans-cn", "zh-hant-hk", "zh-hant-tw"],
+        );
+    }
+
+    #[test]
+    fn tag() {
+        let tag = |value| super::tag(LanguageID::Macintosh(value), &[]);
+        assert_eq!(ok!(tag(Macintosh::Estonian)), "et");
+        assert_eq!(ok!(tag(Macintosh::Czech)), "cs");
+        assert_eq!(ok!(tag(Macintosh::Slovenian)), "sl");
+        assert_eq!(ok!(tag(Macintosh::Spanish)), "es");
+        assert_eq!(ok!(tag(Macintosh::ChineseSimplified)), "zh-hans-cn");
+        assert_eq!(ok!(tag(Macintosh::ChineseTraditional)), "zh-hant");
+    }
+
+    #[test]
+    fn set() {
+        let family = NameID::FontFamilyName;
+        #[rustfmt::skip]
+        let records = [
+            ((PlatformID::Macintosh, 0, LanguageID::Macintosh(Macintosh::English), family), "Mac"),
+            ((PlatformID::Windows, 1, LanguageID::Windows(Windows::EnglishUnitedStates), family), "Windows"),
+        ];
+        let table = ok!(opentype::truetype::tables::Names::from_iter(
+            records,
+            Vec::<String>::new(),
+            &mut Default::default(),
+        ));
+        let names = Names {
+            table: Rc::new(RefCell::new(table)),
+        };
+        let platforms = |names: &Names| {
+            names
+                .borrow()
+                .iter()
+                .map(|((platform_id, ..), value)| (platform_id, ok!(value)))
+                .collect::<Vec<_>>()
+        };
+
+        ok!(names.set(family, "en", "Café"));
+        assert_eq!(
+            platforms(&names),
+            [
+                (PlatformID::Macintosh, "Café".into()),
+                (PlatformID::Windows, "Café".into()),
+            ],
+        );
+
+        ok!(names.set(family, "en", "Café Ⅱ"));
+        assert_eq!(platforms(&names), [(PlatformID::Windows, "Café Ⅱ".into())]);
+    }
+
+    #[test]
+    fn set_undecodable() {
+        use opentype::truetype::tables::names::{Names0, Record};
+
+        let family = NameID::FontFamilyName;
+        let record = |platform_id, encoding_id, language_id, size, offset| Record {
+            platform_id,
+            encoding_id,
+            language_id,
+            name_id: family,
+            size,
+            offset,
+        };
+        #[rustfmt::skip]
+        let table = opentype::truetype::tables::Names::Format0(Names0 {
+            format: 0,
+            count: 3,
+            offset: 42,
+            records: vec![
+                record(PlatformID::Unicode, 3, LanguageID::Unicode, 6, 0),
+                record(PlatformID::Macintosh, 1, LanguageID::Macintosh(Macintosh::Japanese), 2, 6),
+                record(PlatformID::Windows, 1, LanguageID::Windows(Windows::EnglishUnitedStates), 6, 0),
+            ],
+            data: vec![0x00, 0x4F, 0x00, 0x6C, 0x00, 0x64, 0x82, 0xA0],
+        });
+        let names = Names {
+            table: Rc::new(RefCell::new(table)),
+        };
+        let records = |names: &Names| {
+            names
+                .borrow()
+                .iter()
+                .map(|((platform_id, ..), value)| (platform_id, value))
+                .collect::<Vec<_>>()
+        };
+
+        ok!(names.set(family, "en", "New"));
+        assert_eq!(
+            records(&names),
+            [
+                (PlatformID::Unicode, Some("New".into())),
+                (PlatformID::Macintosh, None),
+                (PlatformID::Windows, Some("New".into())),
+            ],
+        );
+        match *names.borrow() {
+            opentype::truetype::tables::Names::Format0(ref table) => {
+                assert_eq!((table.count, table.offset), (3, 42));
+                let record = table.records[1];
+                let (offset, size) = (record.offset as usize, record.size as usize);
+                assert_eq!(table.data[offset..(offset + size)], [0x82, 0xA0]);
+            }
+            _ => unreachable!(),
+        }
+
+        ok!(names.remove(family, "en"));
+        assert_eq!(records(&names), [(PlatformID::Macintosh, None)]);
+    }
 }
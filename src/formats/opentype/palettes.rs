@@ -2,11 +2,204 @@
 
 use std::io::Result;
 
+use opentype::tables::color_palettes::{ColorPalettes, Header};
+use opentype::truetype::tables::names::NameID;
+use opentype::truetype::Tag;
+
 use crate::formats::opentype::cache::{Cache, Reference};
+use crate::Names;
 
 /// Color palettes.
-pub type Palettes = Option<Reference<opentype::tables::ColorPalettes>>;
+#[derive(Clone)]
+pub struct Palettes {
+    table: Reference<ColorPalettes>,
+    labels: Reference<Labels>,
+    names: Option<Names>,
+}
+
+/// A color palette.
+#[derive(Clone, Debug)]
+pub struct Palette {
+    /// The colors as red, green, blue, and alpha.
+    pub colors: Vec<[u8; 4]>,
+    /// The flag indicating that the palette is appropriate for a light background.
+    pub light: bool,
+    /// The flag indicating that the palette is appropriate for a dark background.
+    pub dark: bool,
+    /// The label.
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Labels {
+    types: Vec<u32>,
+    palette_labels: Vec<u16>,
+    entry_labels: Vec<u16>,
+}
+
+impl Palettes {
+    /// Return a palette.
+    pub fn get(&self, index: usize) -> Option<Palette> {
+        let table = self.table.borrow();
+        let colors = table
+            .iter()
+            .nth(index)?
+            .map(|color| [color.red, color.green, color.blue, color.alpha])
+            .collect();
+        let labels = self.labels.borrow();
+        let flags = labels.types.get(index).copied().unwrap_or(0);
+        Some(Palette {
+            colors,
+            light: flags & 0b01 != 0,
+            dark: flags & 0b10 != 0,
+            label: self.label(labels.palette_labels.get(index).copied()),
+        })
+    }
+
+    /// Iterate over the palettes.
+    pub fn iter(&self) -> impl Iterator<Item = Palette> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Return the number of palettes.
+    pub fn len(&self) -> usize {
+        match &self.table.borrow().header {
+            Header::Version0(header) => header.palette_count as usize,
+            Header::Version1(header) => header.palette_count as usize,
+        }
+    }
+
+    /// Check if there are no palettes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the labels of the entries, which are shared by all palettes.
+    pub fn entry_labels(&self) -> Vec<Option<String>> {
+        let entry_count = match &self.table.borrow().header {
+            Header::Version0(header) => header.entry_count as usize,
+            Header::Version1(header) => header.entry_count as usize,
+        };
+        let labels = self.labels.borrow();
+        (0..entry_count)
+            .map(|index| self.label(labels.entry_labels.get(index).copied()))
+            .collect()
+    }
+
+    fn label(&self, name_id: Option<u16>) -> Option<String> {
+        match name_id? {
+            0xFFFF => None,
+            name_id => self.names.as_ref()?.get(NameID::from(name_id)),
+        }
+    }
+}
+
+dereference! { Palettes::table => Reference<ColorPalettes> }
+
+impl opentype::Table for Labels {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"CPAL")
+    }
+}
+
+impl opentype::value::Read for Labels {
+    fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let (palette_count, entry_count) = match tape.take::<Header>()? {
+            Header::Version0(_) => return Ok(Default::default()),
+            Header::Version1(header) => (header.palette_count, header.entry_count),
+        };
+        let type_offset = tape.take::<u32>()?;
+        let palette_label_offset = tape.take::<u32>()?;
+        let entry_label_offset = tape.take::<u32>()?;
+        let mut table = Labels::default();
+        if type_offset > 0 {
+            tape.jump(position + type_offset as u64)?;
+            table.types = tape.take_given(palette_count as usize)?;
+        }
+        if palette_label_offset > 0 {
+            tape.jump(position + palette_label_offset as u64)?;
+            table.palette_labels = tape.take_given(palette_count as usize)?;
+        }
+        if entry_label_offset > 0 {
+            tape.jump(position + entry_label_offset as u64)?;
+            table.entry_labels = tape.take_given(entry_count as usize)?;
+        }
+        Ok(table)
+    }
+}
+
+pub(crate) fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Option<Palettes>> {
+    let table = match cache.try_color_palettes()? {
+        Some(table) => table.clone(),
+        _ => return Ok(None),
+    };
+    let labels = cache.color_palette_labels()?.clone();
+    let names = crate::formats::opentype::names::try_read(cache)?;
+    Ok(Some(Palettes {
+        table,
+        labels,
+        names,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::Cursor;
+    use std::rc::Rc;
+
+    use opentype::tables::ColorPalettes;
+    use opentype::value::Read;
+
+    use super::{Labels, Palettes};
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
 
-pub(crate) fn read<T: crate::Read>(cache: &mut Cache<T>) -> Result<Palettes> {
-    Ok(cache.try_color_palettes()?.cloned())
+    #[test]
+    fn read() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x01, 0x00, 0x02, // version, numPaletteEntries
+            0x00, 0x02, 0x00, 0x03, // numPalettes, numColorRecords
+            0x00, 0x00, 0x00, 0x20, // colorRecordsArrayOffset
+            0x00, 0x00, 0x00, 0x01, // colorRecordIndices
+            0x00, 0x00, 0x00, 0x2C, // paletteTypesArrayOffset
+            0x00, 0x00, 0x00, 0x34, // paletteLabelsArrayOffset
+            0x00, 0x00, 0x00, 0x38, // paletteEntryLabelsArrayOffset
+            0x00, 0x00, 0x00, 0x00, // padding
+            0x00, 0x00, 0xFF, 0xFF, // blue, green, red, alpha
+            0xFF, 0x00, 0x00, 0x80, // blue, green, red, alpha
+            0x00, 0xFF, 0x00, 0xFF, // blue, green, red, alpha
+            0x00, 0x00, 0x00, 0x01, // paletteTypes[0]
+            0x00, 0x00, 0x00, 0x02, // paletteTypes[1]
+            0x01, 0x00, 0xFF, 0xFF, // paletteLabels
+            0xFF, 0xFF, 0x01, 0x01, // paletteEntryLabels
+        ];
+        let table = ok!(ColorPalettes::read(&mut Cursor::new(data.clone())));
+        let labels = ok!(Labels::read(&mut Cursor::new(data)));
+        assert_eq!(labels.palette_labels, [256, 0xFFFF]);
+        assert_eq!(labels.entry_labels, [0xFFFF, 257]);
+        let palettes = Palettes {
+            table: Rc::new(RefCell::new(table)),
+            labels: Rc::new(RefCell::new(labels)),
+            names: None,
+        };
+        assert_eq!(palettes.len(), 2);
+        let values = palettes.iter().collect::<Vec<_>>();
+        assert_eq!(
+            values[0].colors,
+            [[0xFF, 0x00, 0x00, 0xFF], [0x00, 0x00, 0xFF, 0x80]],
+        );
+        assert_eq!(
+            values[1].colors,
+            [[0x00, 0x00, 0xFF, 0x80], [0x00, 0xFF, 0x00, 0xFF]],
+        );
+        assert!(values[0].light && !values[0].dark);
+        assert!(!values[1].light && values[1].dark);
+        assert!(values[0].label.is_none());
+        assert_eq!(palettes.entry_labels(), [None, None]);
+    }
 }
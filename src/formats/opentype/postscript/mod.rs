@@ -0,0 +1,478 @@
+mod program;
+
+use std::io::Result;
+
+use opentype::postscript::compact1::font_set::character_name_keyed;
+use opentype::postscript::compact1::{FontSet, Operations};
+use opentype::truetype::GlyphID;
+
+use crate::formats::opentype::metrics::Metrics;
+use crate::glyph::{Builder, Component, Glyph, Hints, Mask};
+use crate::offset::Offset;
+use crate::Number;
+
+use self::program::Program;
+
+macro_rules! expect(
+    ($condition:expr) => (
+        if !$condition {
+            raise!("found a malformed glyph");
+        }
+    )
+);
+
+#[inline]
+pub(super) fn draw(
+    font_set: &FontSet,
+    metrics: &Metrics,
+    id: usize,
+    glyph_id: GlyphID,
+) -> Result<Glyph> {
+    draw_root(font_set, metrics, id, glyph_id, true)
+}
+
+#[inline]
+pub(super) fn draw_shallow(
+    font_set: &FontSet,
+    metrics: &Metrics,
+    id: usize,
+    glyph_id: GlyphID,
+) -> Result<Glyph> {
+    draw_root(font_set, metrics, id, glyph_id, false)
+}
+
+// Report the base and the accent of a glyph composed by endchar as components.
+pub(super) fn components(
+    font_set: &FontSet,
+    id: usize,
+    glyph_id: GlyphID,
+) -> Result<Vec<Component>> {
+    use opentype::postscript::type2::Operator::*;
+
+    let component = |glyph_id, offset: (Number, Number)| Component {
+        glyph_id,
+        offset: offset.into(),
+        transform: (1.0, 0.0, 0.0, 1.0),
+        points: None,
+        use_metrics: false,
+        round_to_grid: false,
+        scaled_offset: false,
+    };
+    let mut program = load(font_set, id, glyph_id)?;
+    while let Some((operator, operands)) = program.next()? {
+        if operator == EndChar {
+            expect!(operands.len() == 4);
+            return Ok(vec![
+                component(find(font_set, id, operands[2])?, (0.0, 0.0)),
+                component(find(font_set, id, operands[3])?, (operands[0], operands[1])),
+            ]);
+        }
+    }
+    Ok(vec![])
+}
+
+fn draw_root(
+    font_set: &FontSet,
+    metrics: &Metrics,
+    id: usize,
+    glyph_id: GlyphID,
+    decompose: bool,
+) -> Result<Glyph> {
+    use opentype::postscript::type2::Operator::*;
+
+    let record = record(font_set, id)?;
+    let load = |glyph_id: GlyphID| load(font_set, id, glyph_id);
+    let mut program = load(glyph_id)?;
+    let mut builder = Builder::default();
+    let mut hints = blue(&record.operations);
+    let mut position = Offset::default();
+    let (mut max, mut min) = (Offset::undefined(), Offset::undefined());
+    let (mut contour, mut segment) = (0, 0);
+    let mut mask = None;
+    macro_rules! build(
+        ($function:ident($(($x:expr, $y:expr)),+ $(,)?)) => (
+            builder.$function($(($x, $y)),+);
+            build!(@track $function($(($x, $y)),+));
+        );
+        (@track move_relative($(($x:expr, $y:expr)),+)) => (
+            if segment > 0 {
+                contour += 1;
+                segment = 0;
+            }
+            $(position += ($x, $y);)+
+        );
+        (@track $function:ident($(($x:expr, $y:expr)),+)) => (
+            if let Some(stems) = mask.take() {
+                hints.masks.push(Mask { contour, segment, stems });
+            }
+            segment += 1;
+            build!(@update);
+            $(position += ($x, $y);)+
+            build!(@update);
+        );
+        (@update) => (
+            max = max.max(position);
+            min = min.min(position);
+        );
+    );
+    let mut clear = false;
+    let mut accent = None;
+    let mut component = false;
+    loop {
+        while let Some((operator, operands)) = program.next()? {
+            let count = operands.len();
+            match operator {
+                RMoveTo | HMoveTo | VMoveTo => builder.flush(),
+                _ => {}
+            }
+            match operator {
+                RMoveTo => {
+                    expect!(count == 2 || !clear && count == 3);
+                    build!(move_relative((operands[0], operands[1])));
+                }
+                HMoveTo => {
+                    expect!(count == 1 || !clear && count == 2);
+                    build!(move_relative((operands[0], 0.0)));
+                }
+                VMoveTo => {
+                    expect!(count == 1 || !clear && count == 2);
+                    build!(move_relative((0.0, operands[0])));
+                }
+                RLineTo => {
+                    expect!(count % 2 == 0);
+                    for i in 0..(count / 2) {
+                        let j = 2 * i;
+                        build!(add_linear((operands[j], operands[j + 1])));
+                    }
+                }
+                HLineTo => {
+                    for (i, operand) in operands.iter().enumerate().take(count) {
+                        if i % 2 == 0 {
+                            build!(add_linear((*operand, 0.0)));
+                        } else {
+                            build!(add_linear((0.0, *operand)));
+                        }
+                    }
+                }
+                VLineTo => {
+                    for (i, operand) in operands.iter().enumerate().take(count) {
+                        if i % 2 == 1 {
+                            build!(add_linear((*operand, 0.0)));
+                        } else {
+                            build!(add_linear((0.0, *operand)));
+                        }
+                    }
+                }
+                RRCurveTo => {
+                    expect!(count % 6 == 0);
+                    for i in 0..(count / 6) {
+                        let j = 6 * i;
+                        build!(add_cubic(
+                            (operands[j], operands[j + 1]),
+                            (operands[j + 2], operands[j + 3]),
+                            (operands[j + 4], operands[j + 5]),
+                        ));
+                    }
+                }
+                HHCurveTo => {
+                    let (offset, first) = if count % 4 == 0 {
+                        (0, 0.0)
+                    } else {
+                        expect!((count - 1) % 4 == 0);
+                        (1, operands[0])
+                    };
+                    for i in 0..((count - offset) / 4) {
+                        let j = offset + 4 * i;
+                        let first = if i == 0 { first } else { 0.0 };
+                        build!(add_cubic(
+                            (operands[j], first),
+                            (operands[j + 1], operands[j + 2]),
+                            (operands[j + 3], 0.0),
+                        ));
+                    }
+                }
+                HVCurveTo => {
+                    let (steps, last) = if count % 4 == 0 {
+                        (count / 4, 0.0)
+                    } else {
+                        expect!((count - 1) % 4 == 0);
+                        ((count - 1) / 4, operands[count - 1])
+                    };
+                    for i in 0..steps {
+                        let j = 4 * i;
+                        let last = if i + 1 == steps { last } else { 0.0 };
+                        if i % 2 == 0 {
+                            build!(add_cubic(
+                                (operands[j], 0.0),
+                                (operands[j + 1], operands[j + 2]),
+                                (last, operands[j + 3]),
+                            ));
+                        } else {
+                            build!(add_cubic(
+                                (0.0, operands[j]),
+                                (operands[j + 1], operands[j + 2]),
+                                (operands[j + 3], last),
+                            ));
+                        }
+                    }
+                }
+                VHCurveTo => {
+                    let (steps, last) = if count % 4 == 0 {
+                        (count / 4, 0.0)
+                    } else {
+                        expect!((count - 1) % 4 == 0);
+                        ((count - 1) / 4, operands[count - 1])
+                    };
+                    for i in 0..steps {
+                        let j = 4 * i;
+                        let last = if i + 1 == steps { last } else { 0.0 };
+                        if i % 2 == 1 {
+                            build!(add_cubic(
+                                (operands[j], 0.0),
+                                (operands[j + 1], operands[j + 2]),
+                                (last, operands[j + 3]),
+                            ));
+                        } else {
+                            build!(add_cubic(
+                                (0.0, operands[j]),
+                                (operands[j + 1], operands[j + 2]),
+                                (operands[j + 3], last),
+                            ));
+                        }
+                    }
+                }
+                VVCurveTo => {
+                    let (offset, first) = if count % 4 == 0 {
+                        (0, 0.0)
+                    } else {
+                        expect!((count - 1) % 4 == 0);
+                        (1, operands[0])
+                    };
+                    for i in 0..((count - offset) / 4) {
+                        let j = offset + 4 * i;
+                        let first = if i == 0 { first } else { 0.0 };
+                        build!(add_cubic(
+                            (first, operands[j]),
+                            (operands[j + 1], operands[j + 2]),
+                            (0.0, operands[j + 3]),
+                        ));
+                    }
+                }
+                RCurveLine => {
+                    expect!(count >= 2 && (count - 2) % 6 == 0);
+                    for i in 0..((count - 2) / 6) {
+                        let j = 6 * i;
+                        build!(add_cubic(
+                            (operands[j], operands[j + 1]),
+                            (operands[j + 2], operands[j + 3]),
+                            (operands[j + 4], operands[j + 5]),
+                        ));
+                    }
+                    let j = count - 2;
+                    build!(add_linear((operands[j], operands[j + 1])));
+                }
+                RLineCurve => {
+                    expect!(count >= 6 && (count - 6) % 2 == 0);
+                    for i in 0..((count - 6) / 2) {
+                        let j = 2 * i;
+                        build!(add_linear((operands[j], operands[j + 1])));
+                    }
+                    let j = count - 6;
+                    build!(add_cubic(
+                        (operands[j], operands[j + 1]),
+                        (operands[j + 2], operands[j + 3]),
+                        (operands[j + 4], operands[j + 5]),
+                    ));
+                }
+                HStem | HStemHM | VStem | VStemHM | HintMask | CntrMask if component => {}
+                HStem | HStemHM => {
+                    expect!(count % 2 == 0);
+                    stem(&mut hints.horizontal_stems, &operands);
+                }
+                VStem | VStemHM => {
+                    expect!(count % 2 == 0);
+                    stem(&mut hints.vertical_stems, &operands);
+                }
+                HintMask | CntrMask => {
+                    expect!(count % 2 == 0);
+                    stem(&mut hints.vertical_stems, &operands);
+                    let count = hints.horizontal_stems.len() + hints.vertical_stems.len();
+                    let stems = (0..count)
+                        .map(|i| program.mask()[i >> 3] & (0x80 >> (i & 7)) != 0)
+                        .collect();
+                    if operator == HintMask {
+                        mask = Some(stems);
+                    } else {
+                        hints.counter_masks.push(stems);
+                    }
+                }
+                EndChar if !decompose => break,
+                EndChar => {
+                    expect!(count == 4 && !component);
+                    accent = Some((find(font_set, id, operands[3])?, (operands[0], operands[1])));
+                    program = load(find(font_set, id, operands[2])?)?;
+                    clear = false;
+                    component = true;
+                }
+                Flex | Flex1 | HFlex | HFlex1 => {}
+                operator => raise!("found an unknown operation with operator {operator:?}"),
+            }
+            match operator {
+                HMoveTo | VMoveTo | RMoveTo | HStem | HStemHM | VStem | VStemHM | CntrMask
+                | HintMask => {
+                    clear = true;
+                }
+                _ => {}
+            }
+        }
+        match accent.take() {
+            Some((glyph_id, (x, y))) => {
+                builder.flush();
+                build!(move_relative((x - position.0, y - position.1)));
+                program = load(glyph_id)?;
+                clear = false;
+            }
+            _ => break,
+        }
+    }
+    builder.flush();
+    builder.set_hints(hints);
+    builder.set_bounding_box((min.0, min.1, max.0, max.1));
+    builder.set_horizontal_metrics(metrics.get(glyph_id));
+    builder.set_vertical_metrics(metrics.get_vertical(glyph_id, max.1));
+    Ok(builder.into())
+}
+
+fn record(font_set: &FontSet, id: usize) -> Result<&character_name_keyed::Record> {
+    use opentype::postscript::compact1::font_set::Record;
+
+    match &font_set.records[id] {
+        Record::CharacterNameKeyed(ref record) => Ok(record),
+        _ => raise!("found a character-ID-keyed font, which is not supported yet"),
+    }
+}
+
+fn load(font_set: &FontSet, id: usize, glyph_id: GlyphID) -> Result<Program<'_>> {
+    let record = record(font_set, id)?;
+    match font_set.character_strings[id].get(glyph_id as usize) {
+        Some(character_string) => Ok(Program::new(
+            character_string,
+            &font_set.subroutines,
+            &record.subroutines,
+        )),
+        _ => raise!("found no char string for glyph {}", glyph_id),
+    }
+}
+
+fn blue(operations: &Operations) -> Hints {
+    use opentype::postscript::compact1::{Number as Value, Operator};
+
+    let get = |operator| -> Vec<Number> {
+        let mut last = 0.0;
+        operations
+            .get(operator)
+            .unwrap_or_default()
+            .iter()
+            .map(|value| match *value {
+                Value::Integer(value) => value as Number,
+                Value::Real(value) => value,
+            })
+            .map(|value| {
+                last += value;
+                last
+            })
+            .collect()
+    };
+    let pair = |values: Vec<Number>| {
+        values
+            .chunks_exact(2)
+            .map(|chunk| (chunk[0], chunk[1]))
+            .collect()
+    };
+    let single = |operator| get(operator).first().copied().unwrap_or_default();
+    Hints {
+        blue_zones: pair(get(Operator::BlueValues)),
+        other_blue_zones: pair(get(Operator::OtherBlues)),
+        blue_scale: single(Operator::BlueScale),
+        blue_shift: single(Operator::BlueShift),
+        blue_fuzz: single(Operator::BlueFuzz),
+        ..Default::default()
+    }
+}
+
+fn stem(stems: &mut Vec<(Number, Number)>, operands: &[Number]) {
+    let mut last = stems
+        .last()
+        .map(|(start, size)| start + size)
+        .unwrap_or_default();
+    for chunk in operands.chunks_exact(2) {
+        stems.push((last + chunk[0], chunk[1]));
+        last += chunk[0] + chunk[1];
+    }
+}
+
+fn find(font_set: &FontSet, id: usize, code: Number) -> Result<GlyphID> {
+    use opentype::postscript::compact1::character_set::CharacterSet;
+    use opentype::postscript::compact1::Encoding;
+
+    let string_id = match Encoding::Standard.get(code as GlyphID) {
+        Some(string_id) if string_id > 0 && code == code as GlyphID as Number => string_id,
+        _ => raise!("found no standard character with code {code}"),
+    };
+    let glyph_id = match &font_set.character_sets[id] {
+        CharacterSet::ISOAdobe => Some(string_id),
+        CharacterSet::Format0(character_set) => character_set
+            .glyphs
+            .iter()
+            .position(|&value| value == string_id)
+            .map(|index| index as GlyphID + 1),
+        CharacterSet::Format1(character_set) => {
+            let ranges = character_set.ranges.iter();
+            position(
+                ranges.map(|range| (range.first_string_id, range.left_count as u16)),
+                string_id,
+            )
+        }
+        CharacterSet::Format2(character_set) => {
+            let ranges = character_set.ranges.iter();
+            position(
+                ranges.map(|range| (range.first_string_id, range.left_count)),
+                string_id,
+            )
+        }
+        _ => None,
+    };
+    match glyph_id {
+        Some(glyph_id) if (glyph_id as usize) < font_set.character_strings[id].count as usize => {
+            Ok(glyph_id)
+        }
+        _ => raise!("found no glyph for standard character with code {code}"),
+    }
+}
+
+fn position<T>(ranges: T, string_id: u16) -> Option<GlyphID>
+where
+    T: Iterator<Item = (u16, u16)>,
+{
+    let mut glyph_id: GlyphID = 1;
+    for (first, left) in ranges {
+        if (first..=first.saturating_add(left)).contains(&string_id) {
+            return glyph_id.checked_add(string_id - first);
+        }
+        glyph_id = glyph_id.checked_add(left)?.checked_add(1)?;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::position;
+
+    #[test]
+    fn position_overflow() {
+        let ranges = [(10, 2), (20, 0xFFFD), (5, 0)];
+        assert_eq!(position(ranges.into_iter(), 11), Some(2));
+        assert_eq!(position(ranges.into_iter(), 20), Some(4));
+        assert_eq!(position(ranges.into_iter(), 5), None);
+    }
+}
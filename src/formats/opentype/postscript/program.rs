@@ -0,0 +1,335 @@
+// The interpretation of Type 2 charstrings mirrors the one of the postscript crate, which skips the
+// bytes of hint and counter masks and the operands of accented endchar without exposing them.
+
+use std::io::Result;
+
+use opentype::postscript::type2::{Operand, Operation, Operator};
+
+// The maximum nesting of subroutine calls.
+const DEPTH: usize = 10;
+
+pub struct Program<'l> {
+    routine: Routine<'l>,
+    global: &'l [Vec<u8>],
+    local: &'l [Vec<u8>],
+    stack: Vec<Operand>,
+    stems: usize,
+    mask: Vec<u8>,
+}
+
+struct Routine<'l> {
+    code: &'l [u8],
+    position: usize,
+    depth: usize,
+    caller: Option<Box<Routine<'l>>>,
+}
+
+impl<'l> Program<'l> {
+    #[inline]
+    pub fn new(code: &'l [u8], global: &'l [Vec<u8>], local: &'l [Vec<u8>]) -> Self {
+        Self {
+            routine: Routine::new(code),
+            global,
+            local,
+            stack: vec![],
+            stems: 0,
+            mask: vec![],
+        }
+    }
+
+    // Return the bytes of the last hint or counter mask.
+    #[inline]
+    pub fn mask(&self) -> &[u8] {
+        &self.mask
+    }
+
+    pub fn next(&mut self) -> Result<Option<Operation>> {
+        use opentype::postscript::type2::Operator::*;
+
+        macro_rules! pop(
+            () => (match self.stack.pop() {
+                Some(value) => value,
+                _ => raise!("found no operand in a char string"),
+            });
+            (bool) => (pop!() != 0.0);
+            (i32) => (match self.stack.pop() {
+                Some(value) if value as i32 as Operand == value => value as i32,
+                _ => raise!("found a non-integer operand in a char string"),
+            });
+        );
+        macro_rules! push(
+            ($operand:expr, bool) => (push!(if $operand { 1.0 } else { 0.0 }));
+            ($operand:expr) => ({
+                let operand = $operand;
+                self.stack.push(operand);
+            });
+        );
+        loop {
+            let operator = loop {
+                let code = match self.routine.peek() {
+                    Some(code) => code,
+                    _ => return Ok(None),
+                };
+                match code {
+                    0x1c | 0x20..=0xff => push!(self.routine.take_operand()?),
+                    _ => break self.routine.take_operator()?,
+                }
+            };
+            macro_rules! clear(
+                (@modulo $step:expr) => ({
+                    let start = self.stack.len() % $step;
+                    clear!(@from start)
+                });
+                (@from $start:expr) => ({
+                    // Leading operands are the width difference.
+                    let operands = self.stack.drain($start..).collect();
+                    self.stack.clear();
+                    return Ok(Some((operator, operands)));
+                });
+                ($count:expr) => ({
+                    match self.stack.len().checked_sub($count) {
+                        Some(start) => clear!(@from start),
+                        _ => raise!("found malformed operands in a char string"),
+                    }
+                });
+            );
+            match operator {
+                RMoveTo => clear!(2),
+                HMoveTo | VMoveTo => clear!(1),
+                RLineTo | HLineTo | VLineTo | RRCurveTo | HHCurveTo | VVCurveTo | HVCurveTo
+                | VHCurveTo | RCurveLine | RLineCurve => clear!(@from 0),
+                Flex => clear!(13),
+                Flex1 => clear!(11),
+                HFlex => clear!(7),
+                HFlex1 => clear!(9),
+                EndChar => {
+                    let length = self.stack.len();
+                    self.routine = Routine::new(&[]);
+                    // The deprecated form with four arguments composes an accented glyph.
+                    if length >= 4 {
+                        clear!(4);
+                    }
+                    return Ok(None);
+                }
+                HStem | VStem | HStemHM | VStemHM => {
+                    self.stems += self.stack.len() >> 1;
+                    clear!(@modulo 2);
+                }
+                HintMask | CntrMask => {
+                    self.stems += self.stack.len() >> 1;
+                    self.mask = self.routine.take_bytes((self.stems + 7) >> 3)?.to_vec();
+                    clear!(@modulo 2);
+                }
+                Abs => push!(pop!().abs()),
+                Add => push!(pop!() + pop!()),
+                Sub => {
+                    let (right, left) = (pop!(), pop!());
+                    push!(left - right);
+                }
+                Div => {
+                    let (right, left) = (pop!(), pop!());
+                    push!(left / right);
+                }
+                Neg => push!(-pop!()),
+                Mul => push!(pop!() * pop!()),
+                Sqrt => push!(pop!().sqrt()),
+                Drop => {
+                    pop!();
+                }
+                Exch => {
+                    let (right, left) = (pop!(), pop!());
+                    push!(right);
+                    push!(left);
+                }
+                Index => {
+                    let index = pop!(i32).max(0) as usize;
+                    match self.stack.len().checked_sub(index + 1) {
+                        Some(index) => push!(self.stack[index]),
+                        _ => raise!("found no operand in a char string"),
+                    }
+                }
+                Roll => {
+                    let (shift, span) = (pop!(i32), pop!(i32));
+                    let length = self.stack.len();
+                    if span < 0 || span as usize > length {
+                        raise!("found a malformed roll in a char string");
+                    }
+                    if span > 0 {
+                        let stack = &mut self.stack[length - span as usize..];
+                        let shift = shift.rem_euclid(span) as usize;
+                        stack.rotate_right(shift);
+                    }
+                }
+                Dup => {
+                    let operand = pop!();
+                    push!(operand);
+                    push!(operand);
+                }
+                And => {
+                    let (right, left) = (pop!(bool), pop!(bool));
+                    push!(left && right, bool);
+                }
+                Or => {
+                    let (right, left) = (pop!(bool), pop!(bool));
+                    push!(left || right, bool);
+                }
+                Not => push!(!pop!(bool), bool),
+                Eq => {
+                    let (right, left) = (pop!(), pop!());
+                    push!(left == right, bool);
+                }
+                IfElse => {
+                    let (right, left, no, yes) = (pop!(), pop!(), pop!(), pop!());
+                    push!(if left <= right { yes } else { no });
+                }
+                CallSubr | CallGSubr => {
+                    let subroutines = if operator == CallSubr {
+                        self.local
+                    } else {
+                        self.global
+                    };
+                    let index = pop!(i32) + bias(subroutines.len());
+                    let code = match usize::try_from(index)
+                        .ok()
+                        .and_then(|index| subroutines.get(index))
+                    {
+                        Some(code) => code,
+                        _ => raise!("found no subroutine with index {index}"),
+                    };
+                    let depth = self.routine.depth + 1;
+                    if depth > DEPTH {
+                        raise!("found too deeply nested subroutines in a char string");
+                    }
+                    let caller = std::mem::replace(&mut self.routine, Routine::new(code));
+                    self.routine.depth = depth;
+                    self.routine.caller = Some(Box::new(caller));
+                }
+                Return => match self.routine.caller.take() {
+                    Some(caller) => self.routine = *caller,
+                    _ => raise!("found a return without a caller in a char string"),
+                },
+                operator => raise!("found an unknown operator {operator:?} in a char string"),
+            }
+        }
+    }
+}
+
+impl<'l> Routine<'l> {
+    #[inline]
+    fn new(code: &'l [u8]) -> Self {
+        Self {
+            code,
+            position: 0,
+            depth: 0,
+            caller: None,
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while self.position >= self.code.len() {
+            // Subroutines ending without a return continue with the caller.
+            *self = *self.caller.take()?;
+        }
+        Some(self.code[self.position])
+    }
+
+    fn take_bytes(&mut self, count: usize) -> Result<&'l [u8]> {
+        let code = self.code;
+        match code.get(self.position..self.position + count) {
+            Some(bytes) => {
+                self.position += count;
+                Ok(bytes)
+            }
+            _ => raise!("found a truncated char string"),
+        }
+    }
+
+    fn take_operand(&mut self) -> Result<Operand> {
+        let first = self.take_bytes(1)?[0] as i32;
+        Ok(match first {
+            0x20..=0xf6 => (first - 139) as Operand,
+            0xf7..=0xfa => ((first - 247) * 256 + self.take_bytes(1)?[0] as i32 + 108) as Operand,
+            0xfb..=0xfe => (-(first - 251) * 256 - self.take_bytes(1)?[0] as i32 - 108) as Operand,
+            0x1c => {
+                let bytes = self.take_bytes(2)?;
+                i16::from_be_bytes([bytes[0], bytes[1]]) as Operand
+            }
+            _ => {
+                let bytes = self.take_bytes(4)?;
+                i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as Operand / 65536.0
+            }
+        })
+    }
+
+    fn take_operator(&mut self) -> Result<Operator> {
+        let first = self.take_bytes(1)?[0] as u16;
+        match first {
+            0x0c => Operator::from(0x0c00 | self.take_bytes(1)?[0] as u16),
+            _ => Operator::from(first),
+        }
+    }
+}
+
+#[inline]
+fn bias(count: usize) -> i32 {
+    if count < 1240 {
+        107
+    } else if count < 33900 {
+        1131
+    } else {
+        32768
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentype::postscript::type2::Operator::*;
+
+    use super::Program;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn next() {
+        #[rustfmt::skip]
+        let code = vec![
+            0x95, 0x9F, 0x12, // 10 20 hstemhm
+            0xA9, 0xB3, 0x13, 0x80, // 30 40 hintmask
+            0x20, 0x0A, 0x8B, 0x15, // -107 callsubr 0 rmoveto
+            0x95, 0x8B, 0x05, // 10 0 rlineto
+            0x0E, // endchar
+        ];
+        let subroutines = vec![vec![0x95, 0x0B]];
+        let mut program = Program::new(&code, &[], &subroutines);
+        assert_eq!(ok!(program.next()), Some((HStemHM, vec![10.0, 20.0])));
+        assert_eq!(ok!(program.next()), Some((HintMask, vec![30.0, 40.0])));
+        assert_eq!(program.mask(), [0x80]);
+        assert_eq!(ok!(program.next()), Some((RMoveTo, vec![10.0, 0.0])));
+        assert_eq!(ok!(program.next()), Some((RLineTo, vec![10.0, 0.0])));
+        assert_eq!(ok!(program.next()), None);
+    }
+
+    #[test]
+    fn next_accent() {
+        #[rustfmt::skip]
+        let code = vec![
+            0x95, // 10 (width)
+            0x8B, 0xF7, 0x44, 0xA7, 0xC2, 0x0E, // 0 176 28 55 endchar
+            0x95, 0x8B, 0x05, // 10 0 rlineto
+        ];
+        let mut program = Program::new(&code, &[], &[]);
+        assert_eq!(
+            ok!(program.next()),
+            Some((EndChar, vec![0.0, 176.0, 28.0, 55.0])),
+        );
+        assert_eq!(ok!(program.next()), None);
+    }
+
+    #[test]
+    fn next_cycle() {
+        let code = vec![0x20, 0x0A]; // -107 callsubr
+        let subroutines = vec![vec![0x20, 0x0A, 0x0B]]; // -107 callsubr return
+        let mut program = Program::new(&code, &[], &subroutines);
+        assert!(program.next().is_err());
+    }
+}
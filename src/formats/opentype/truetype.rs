@@ -4,10 +4,15 @@ use opentype::truetype::tables::glyph_data::{
     self, CompositeDescription, GlyphData, SimpleDescription,
 };
 
-use crate::formats::opentype::mapping::Forward as Mapping;
+use opentype::truetype::GlyphID;
+
 use crate::formats::opentype::metrics::Metrics;
-use crate::glyph::{Builder, Glyph};
+use crate::glyph::{Builder, Component, Glyph};
 use crate::offset::Offset;
+use crate::Number;
+
+// The maximum depth of nested composite glyphs.
+const DEPTH: usize = 16;
 
 macro_rules! expect(
     ($condition:expr) => (
@@ -17,31 +22,89 @@ macro_rules! expect(
     )
 );
 
-pub(super) fn draw(
+#[inline]
+pub(super) fn draw(glyph_data: &GlyphData, metrics: &Metrics, glyph_id: GlyphID) -> Result<Glyph> {
+    draw_root(glyph_data, metrics, glyph_id, true)
+}
+
+#[inline]
+pub(super) fn draw_shallow(
     glyph_data: &GlyphData,
-    mapping: &Mapping,
     metrics: &Metrics,
-    character: char,
-) -> Result<Option<Glyph>> {
-    let mut builder = Builder::default();
-    let glyph_id = match mapping.get(character) {
-        Some(glyph_id) => glyph_id,
-        _ => return Ok(None),
+    glyph_id: GlyphID,
+) -> Result<Glyph> {
+    draw_root(glyph_data, metrics, glyph_id, false)
+}
+
+pub(super) fn components(glyph_data: &GlyphData, glyph_id: GlyphID) -> Result<Vec<Component>> {
+    use opentype::truetype::tables::glyph_data::Arguments;
+    use opentype::truetype::tables::glyph_data::Description::*;
+
+    let description = match glyph_data.get(glyph_id as usize) {
+        Some(Some(glyph)) => match &glyph.description {
+            Composite(ref description) => description,
+            _ => return Ok(vec![]),
+        },
+        Some(&None) => return Ok(vec![]),
+        _ => raise!("found no data for glyph {}", glyph_id),
     };
+    let mut points = match_points(description);
+    let mut components = vec![];
+    for component in description.components.iter() {
+        let glyph = match glyph_data.get(component.glyph_id as usize) {
+            Some(glyph) => glyph.as_ref(),
+            _ => raise!("found no data for glyph {}", component.glyph_id),
+        };
+        let (offset, transform) = arrange(glyph_data, component, glyph, points.as_mut(), 1)?;
+        components.push(Component {
+            glyph_id: component.glyph_id,
+            offset,
+            transform,
+            points: match component.arguments {
+                Arguments::Indices(parent, child) => Some((parent, child)),
+                _ => None,
+            },
+            use_metrics: component.flags.should_use_metrics(),
+            round_to_grid: component.flags.should_round_xy_to_grid(),
+            scaled_offset: component.flags.is_offset_scaled()
+                && !component.flags.is_offset_unscaled(),
+        });
+    }
+    Ok(components)
+}
+
+fn draw_root(
+    glyph_data: &GlyphData,
+    metrics: &Metrics,
+    glyph_id: GlyphID,
+    decompose: bool,
+) -> Result<Glyph> {
+    use opentype::truetype::tables::glyph_data::Description::*;
+
+    let mut builder = Builder::default();
     let glyph = match glyph_data.get(glyph_id as usize) {
         Some(glyph) => glyph,
-        _ => raise!(
-            "found no data for character {} with glyph {}",
-            character,
-            glyph_id,
-        ),
+        _ => raise!("found no data for glyph {}", glyph_id),
     };
     builder.set_horizontal_metrics(metrics.get(glyph_id));
     if let Some(ref glyph) = glyph {
-        draw_glyph(glyph_data, metrics, &mut builder, glyph)?;
+        match &glyph.description {
+            Composite(ref description) if !decompose => {
+                for component in description.components.iter() {
+                    if component.flags.should_use_metrics() {
+                        builder.set_horizontal_metrics(metrics.get(component.glyph_id));
+                    }
+                }
+            }
+            _ => draw_glyph(glyph_data, metrics, &mut builder, glyph, 0)?,
+        }
         builder.set_bounding_box((glyph.min_x, glyph.min_y, glyph.max_x, glyph.max_y));
     }
-    Ok(Some(builder.into()))
+    let max_y = glyph
+        .as_ref()
+        .map_or(Number::NAN, |glyph| glyph.max_y.into());
+    builder.set_vertical_metrics(metrics.get_vertical(glyph_id, max_y));
+    Ok(builder.into())
 }
 
 fn draw_glyph(
@@ -49,11 +112,14 @@ fn draw_glyph(
     metrics: &Metrics,
     builder: &mut Builder,
     glyph: &glyph_data::Glyph,
+    depth: usize,
 ) -> Result<()> {
     use opentype::truetype::tables::glyph_data::Description::*;
 
     match &glyph.description {
-        Composite(ref description) => draw_composite(glyph_data, metrics, builder, description),
+        Composite(ref description) => {
+            draw_composite(glyph_data, metrics, builder, description, depth + 1)
+        }
         Simple(ref description) => draw_simple(builder, description),
     }
 }
@@ -63,36 +129,123 @@ fn draw_composite(
     metrics: &Metrics,
     builder: &mut Builder,
     description: &CompositeDescription,
+    depth: usize,
 ) -> Result<()> {
-    use opentype::truetype::tables::glyph_data::{Arguments, Options};
-
+    if depth > DEPTH {
+        raise!("found a too deeply nested composite glyph");
+    }
+    let mut points = match_points(description);
     for component in description.components.iter() {
         let glyph_id = component.glyph_id;
-        let offset = match &component.arguments {
-            &Arguments::Offsets(x, y) => Offset::from((x, y)),
-            arguments => raise!("found a unknown component with arguments {arguments:?}"),
-        };
-        let scale = match component.options {
-            Options::None => (1.0, 0.0, 0.0, 1.0),
-            Options::Scalar(value) => (value.into(), 0.0, 0.0, value.into()),
-            Options::Vector(x, y) => (x.into(), 0.0, 0.0, y.into()),
-            Options::Matrix(xx, xy, yx, yy) => (xx.into(), xy.into(), yx.into(), yy.into()),
-        };
         let glyph = match glyph_data.get(glyph_id as usize) {
             Some(Some(glyph)) => glyph,
             Some(&None) => continue,
             _ => raise!("found no data for glyph {}", glyph_id),
         };
+        let (offset, scale) = arrange(glyph_data, component, Some(glyph), points.as_mut(), depth)?;
         if component.flags.should_use_metrics() {
             builder.set_horizontal_metrics(metrics.get(glyph_id));
         }
         builder.nest(offset, scale, |builder| {
-            draw_glyph(glyph_data, metrics, builder, glyph)
+            draw_glyph(glyph_data, metrics, builder, glyph, depth)
         })?;
     }
     Ok(())
 }
 
+// Return a buffer for the points of the components if any component is positioned by matching
+// points, which is the only case requiring them.
+fn match_points(description: &CompositeDescription) -> Option<Vec<Offset>> {
+    use opentype::truetype::tables::glyph_data::Arguments;
+
+    description
+        .components
+        .iter()
+        .any(|component| matches!(component.arguments, Arguments::Indices(..)))
+        .then(Vec::new)
+}
+
+// Compute the offset and transformation of a component and append its points to the ones of the
+// previous components if they are tracked.
+fn arrange(
+    glyph_data: &GlyphData,
+    component: &glyph_data::Component,
+    glyph: Option<&glyph_data::Glyph>,
+    points: Option<&mut Vec<Offset>>,
+    depth: usize,
+) -> Result<(Offset, (Number, Number, Number, Number))> {
+    use opentype::truetype::tables::glyph_data::{Arguments, Options};
+
+    let scale: (Number, Number, Number, Number) = match component.options {
+        Options::None => (1.0, 0.0, 0.0, 1.0),
+        Options::Scalar(value) => (value.into(), 0.0, 0.0, value.into()),
+        Options::Vector(x, y) => (x.into(), 0.0, 0.0, y.into()),
+        Options::Matrix(xx, yx, xy, yy) => (xx.into(), xy.into(), yx.into(), yy.into()),
+    };
+    let transform = |point: Offset| {
+        Offset::from((
+            scale.0 * point.0 + scale.1 * point.1,
+            scale.2 * point.0 + scale.3 * point.1,
+        ))
+    };
+    let children = match (&points, glyph) {
+        (Some(_), Some(glyph)) => extract(glyph_data, glyph, depth + 1)?,
+        _ => vec![],
+    };
+    let offset = match component.arguments {
+        Arguments::Offsets(x, y) => {
+            let mut offset = Offset::from((x, y));
+            if component.flags.is_offset_scaled() && !component.flags.is_offset_unscaled() {
+                offset.0 *= scale.0.hypot(scale.1);
+                offset.1 *= scale.3.hypot(scale.2);
+            }
+            offset
+        }
+        Arguments::Indices(parent, child) => {
+            let points = points
+                .as_ref()
+                .and_then(|points| points.get(parent as usize));
+            match (points, children.get(child as usize)) {
+                (Some(&parent), Some(&child)) => parent - transform(child),
+                _ => raise!("found a component with malformed point indices {parent} and {child}",),
+            }
+        }
+    };
+    if let Some(points) = points {
+        points.extend(children.into_iter().map(|point| offset + transform(point)));
+    }
+    Ok((offset, scale))
+}
+
+// Compute the absolute positions of the points of a glyph.
+fn extract(glyph_data: &GlyphData, glyph: &glyph_data::Glyph, depth: usize) -> Result<Vec<Offset>> {
+    use opentype::truetype::tables::glyph_data::Description::*;
+
+    let mut points = vec![];
+    match &glyph.description {
+        Composite(ref description) => {
+            if depth > DEPTH {
+                raise!("found a too deeply nested composite glyph");
+            }
+            for component in description.components.iter() {
+                let glyph = match glyph_data.get(component.glyph_id as usize) {
+                    Some(glyph) => glyph.as_ref(),
+                    _ => raise!("found no data for glyph {}", component.glyph_id),
+                };
+                arrange(glyph_data, component, glyph, Some(&mut points), depth)?;
+            }
+        }
+        Simple(ref description) => {
+            let mut point = Offset::default();
+            for (&x, &y) in description.x.iter().zip(description.y.iter()) {
+                point += (x, y);
+                points.push(point);
+            }
+        }
+    }
+    Ok(points)
+}
+
 fn draw_simple(builder: &mut Builder, description: &SimpleDescription) -> Result<()> {
     let SimpleDescription {
         end_points,
@@ -101,15 +254,42 @@ fn draw_simple(builder: &mut Builder, description: &SimpleDescription) -> Result
         y,
         ..
     } = description;
-    let point_count = flags.len();
-    expect!(point_count == x.len());
-    expect!(point_count == y.len());
+    expect!(flags.len() == x.len());
+    expect!(flags.len() == y.len());
+    let on_curve = flags
+        .iter()
+        .map(|flags| flags.is_on_curve())
+        .collect::<Vec<_>>();
+    let points = x
+        .iter()
+        .zip(y.iter())
+        .map(|(&x, &y)| Offset::from((x, y)))
+        .collect::<Vec<_>>();
+    draw_contours(
+        builder,
+        end_points.iter().map(|&k| k as usize),
+        &on_curve,
+        &points,
+    )
+}
+
+pub(super) fn draw_contours<T>(
+    builder: &mut Builder,
+    end_points: T,
+    on_curve: &[bool],
+    points: &[Offset],
+) -> Result<()>
+where
+    T: Iterator<Item = usize>,
+{
+    let point_count = on_curve.len();
+    expect!(point_count == points.len());
     let mut i = 0;
     let mut sum = Offset::default();
-    for k in end_points.iter().map(|&k| k as usize) {
+    for k in end_points {
         expect!(i < point_count);
-        let start = Offset::from((x[i], y[i]));
-        let mut control = match flags[i].is_on_curve() {
+        let start = points[i];
+        let mut control = match on_curve[i] {
             false => Some(Offset::default()),
             _ => None,
         };
@@ -117,9 +297,9 @@ fn draw_simple(builder: &mut Builder, description: &SimpleDescription) -> Result
         let mut offset = Offset::default();
         for j in (i + 1)..=k {
             expect!(j < point_count);
-            let current = (x[j], y[j]).into();
+            let current = points[j];
             sum_delta += current;
-            match (flags[j].is_on_curve(), &mut control) {
+            match (on_curve[j], &mut control) {
                 (false, control @ &mut None) => {
                     *control = Some(current);
                 }
@@ -140,7 +320,7 @@ fn draw_simple(builder: &mut Builder, description: &SimpleDescription) -> Result
                 }
             }
         }
-        match (flags[i].is_on_curve(), control) {
+        match (on_curve[i], control) {
             (false, None) => {
                 let control = (sum + start) - (sum + sum_delta);
                 builder.move_control(control);
@@ -181,3 +361,65 @@ fn draw_simple(builder: &mut Builder, description: &SimpleDescription) -> Result
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::truetype::tables::glyph_data::{Glyph, GlyphData};
+    use opentype::value::Read;
+
+    use crate::offset::Offset;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn extract() {
+        #[rustfmt::skip]
+        let simple = vec![
+            0x00, 0x01, // numberOfContours
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x64, 0x00, 0x64, // xMin, yMin, xMax, yMax
+            0x00, 0x02, // endPtsOfContours
+            0x00, 0x00, // instructionLength
+            0x01, 0x01, 0x01, // flags
+            0x00, 0x00, 0x00, 0x64, 0x00, 0x00, // xCoordinates
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x64, // yCoordinates
+        ];
+        #[rustfmt::skip]
+        let composite = vec![
+            0xFF, 0xFF, // numberOfContours
+            0x00, 0x0A, 0x00, 0x14, 0x00, 0x6E, 0x00, 0xAA, // xMin, yMin, xMax, yMax
+            0x00, 0x23, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x14, // flags, glyphIndex, argument1, argument2
+            0x00, 0x08, 0x00, 0x00, 0x02, 0x01, 0x20, 0x00, // flags, glyphIndex, argument1, argument2, scale
+        ];
+        let glyph_data = GlyphData(vec![
+            Some(ok!(Glyph::read(&mut Cursor::new(simple)))),
+            Some(ok!(Glyph::read(&mut Cursor::new(composite)))),
+        ]);
+        let points = ok!(super::extract(&glyph_data, ok!(glyph_data[1].as_ref()), 1));
+        assert_eq!(
+            points,
+            [
+                (10.0, 20.0),
+                (110.0, 20.0),
+                (110.0, 120.0),
+                (60.0, 120.0),
+                (110.0, 120.0),
+                (110.0, 170.0),
+            ]
+            .map(Offset::from),
+        );
+    }
+
+    #[test]
+    fn extract_cycle() {
+        #[rustfmt::skip]
+        let composite = vec![
+            0xFF, 0xFF, // numberOfContours
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // xMin, yMin, xMax, yMax
+            0x00, 0x03, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x14, // flags, glyphIndex, argument1, argument2
+        ];
+        let glyph_data = GlyphData(vec![Some(ok!(Glyph::read(&mut Cursor::new(composite))))]);
+        assert!(super::extract(&glyph_data, ok!(glyph_data[0].as_ref()), 1).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use std::io::Result;
+
+use opentype::truetype::tables::character_mapping::{Header, Record};
+use opentype::truetype::{GlyphID, Tag};
+
+#[derive(Clone, Debug, Default)]
+pub struct Variants {
+    pub records: Vec<Selector>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Selector {
+    pub character: u32,
+    pub default: Vec<(u32, u32)>,
+    pub non_default: Vec<(u32, GlyphID)>,
+}
+
+impl Variants {
+    pub fn get(&self, character: u32, selector: u32) -> Option<Option<GlyphID>> {
+        let record = self
+            .records
+            .binary_search_by_key(&selector, |record| record.character)
+            .ok()
+            .map(|index| &self.records[index])?;
+        if let Ok(index) = record
+            .non_default
+            .binary_search_by_key(&character, |(character, _)| *character)
+        {
+            return Some(Some(record.non_default[index].1));
+        }
+        if record
+            .default
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&character))
+        {
+            return Some(None);
+        }
+        None
+    }
+
+    // Sequences without a non-default mapping fall back to the base glyph.
+    pub fn resolve(&self, character: u32, selector: u32, base: Option<GlyphID>) -> Option<GlyphID> {
+        match self.get(character, selector) {
+            Some(Some(glyph_id)) => Some(glyph_id),
+            _ => base,
+        }
+    }
+}
+
+impl opentype::Table for Variants {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"cmap")
+    }
+}
+
+impl opentype::value::Read for Variants {
+    fn read<T: opentype::tape::Read>(tape: &mut T) -> Result<Self> {
+        let position = tape.position()?;
+        let header = tape.take::<Header>()?;
+        let mut records = Vec::with_capacity(header.table_count as usize);
+        for _ in 0..header.table_count {
+            records.push(tape.take::<Record>()?);
+        }
+        for record in records.iter() {
+            let position = position + record.offset as u64;
+            tape.jump(position)?;
+            if tape.peek::<u16>()? != 14 {
+                continue;
+            }
+            tape.jump(position + 6)?;
+            let count = tape.take::<u32>()?;
+            let mut headers = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                headers.push((take_u24(tape)?, tape.take::<u32>()?, tape.take::<u32>()?));
+            }
+            let mut records = Vec::with_capacity(headers.len());
+            for (character, default_offset, non_default_offset) in headers {
+                let mut record = Selector {
+                    character,
+                    ..Default::default()
+                };
+                if default_offset > 0 {
+                    tape.jump(position + default_offset as u64)?;
+                    for _ in 0..tape.take::<u32>()? {
+                        let start = take_u24(tape)?;
+                        let count = tape.take::<u8>()?;
+                        record.default.push((start, start + count as u32));
+                    }
+                }
+                if non_default_offset > 0 {
+                    tape.jump(position + non_default_offset as u64)?;
+                    for _ in 0..tape.take::<u32>()? {
+                        record.non_default.push((take_u24(tape)?, tape.take()?));
+                    }
+                }
+                records.push(record);
+            }
+            records.sort_by_key(|record| record.character);
+            return Ok(Self { records });
+        }
+        Ok(Default::default())
+    }
+}
+
+fn take_u24<T: opentype::tape::Read>(tape: &mut T) -> Result<u32> {
+    let buffer = tape.take::<[u8; 3]>()?;
+    Ok(u32::from(buffer[0]) << 16 | u32::from(buffer[1]) << 8 | u32::from(buffer[2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::Variants;
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn read() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x00, 0x00, 0x01, // version, numTables
+            0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x0C, // platformID, encodingID, offset
+            0x00, 0x0E, 0x00, 0x00, 0x00, 0x31, 0x00, 0x00, 0x00, 0x02, // format, length, count
+            0x00, 0xFE, 0x00, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, // U+FE00
+            0x0E, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, // U+E0100
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x82, 0x2A, 0x02, // default: U+822A..U+822C
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x82, 0x2A, 0x00, 0x2A, // non-default: U+822A => 42
+        ];
+        let table = ok!(Variants::read(&mut Cursor::new(data)));
+        assert_eq!(table.records.len(), 2);
+        assert_eq!(table.get(0x822A, 0xFE00), Some(None));
+        assert_eq!(table.get(0x822C, 0xFE00), Some(None));
+        assert_eq!(table.get(0x822D, 0xFE00), None);
+        assert_eq!(table.get(0x822A, 0xE0100), Some(Some(42)));
+        assert_eq!(table.get(0x822B, 0xE0100), None);
+        assert_eq!(table.get(0x822A, 0xFE01), None);
+        assert_eq!(table.resolve(0x822B, 0xFE00, Some(7)), Some(7));
+        assert_eq!(table.resolve(0x822A, 0xE0100, Some(7)), Some(42));
+        assert_eq!(table.resolve(0x822B, 0xE0100, Some(7)), Some(7));
+        assert_eq!(table.resolve(0x822D, 0xFE00, None), None);
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::Result;
+
+use opentype::truetype::tables::MaximumProfile;
+use opentype::truetype::{GlyphID, Tag};
+
+table! {
+    #[derive(Copy)]
+    pub VerticalHeader {
+        version                 (u32), // version
+        ascender                (i16), // vertTypoAscender
+        descender               (i16), // vertTypoDescender
+        line_gap                (i16), // vertTypoLineGap
+        max_advance_height      (u16), // advanceHeightMax
+        min_top_side_bearing    (i16), // minTopSideBearing
+        min_bottom_side_bearing (i16), // minBottomSideBearing
+        max_y_extent            (i16), // yMaxExtent
+        caret_slope_rise        (i16), // caretSlopeRise
+        caret_slope_run         (i16), // caretSlopeRun
+        caret_offset            (i16), // caretOffset
+        reserved1               (i16), // reserved
+        reserved2               (i16), // reserved
+        reserved3               (i16), // reserved
+        reserved4               (i16), // reserved
+        metric_data_format      (i16), // metricDataFormat
+        vertical_metric_count   (u16), // numOfLongVerMetrics
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct VerticalMetrics {
+    pub records: Vec<(u16, i16)>,
+    pub top_side_bearings: Vec<i16>,
+}
+
+table! {
+    pub VerticalOrigin {
+        major_version (u16) = { 1 }, // majorVersion
+        minor_version (u16) = { 0 }, // minorVersion
+        default_y     (i16), // defaultVertOriginY
+        record_count  (u16), // numVertOriginYMetrics
+
+        records (Vec<(GlyphID, i16)>) |this, tape| { // vertOriginYMetrics
+            tape.take_given(this.record_count as usize)
+        },
+    }
+}
+
+impl VerticalMetrics {
+    pub fn get(&self, glyph_id: GlyphID) -> (u16, i16) {
+        let index = glyph_id as usize;
+        let advance_height = match self.records.get(index).or(self.records.last()) {
+            Some((advance_height, _)) => *advance_height,
+            _ => 0,
+        };
+        let top_side_bearing = match self.records.get(index) {
+            Some((_, top_side_bearing)) => *top_side_bearing,
+            _ => self
+                .top_side_bearings
+                .get(index - self.records.len())
+                .or(self.top_side_bearings.last())
+                .cloned()
+                .unwrap_or(0),
+        };
+        (advance_height, top_side_bearing)
+    }
+}
+
+impl VerticalOrigin {
+    pub fn get(&self, glyph_id: GlyphID) -> i16 {
+        match self
+            .records
+            .binary_search_by_key(&glyph_id, |(glyph_id, _)| *glyph_id)
+        {
+            Ok(index) => self.records[index].1,
+            _ => self.default_y,
+        }
+    }
+}
+
+impl opentype::Table for VerticalHeader {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"vhea")
+    }
+}
+
+impl opentype::Table for VerticalMetrics {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"vmtx")
+    }
+}
+
+impl opentype::Table for VerticalOrigin {
+    #[inline]
+    fn tag() -> Tag {
+        Tag(*b"VORG")
+    }
+}
+
+impl<'l> opentype::walue::Read<'l> for VerticalMetrics {
+    type Parameter = (&'l VerticalHeader, &'l MaximumProfile);
+
+    fn read<T: opentype::tape::Read>(
+        tape: &mut T,
+        (header, profile): Self::Parameter,
+    ) -> Result<Self> {
+        let metric_count = header.vertical_metric_count as usize;
+        let glyph_count = profile.glyph_count();
+        if metric_count == 0 || metric_count > glyph_count {
+            raise!("found a malformed vertical header");
+        }
+        let mut table = VerticalMetrics {
+            records: Vec::with_capacity(metric_count),
+            top_side_bearings: Vec::with_capacity(glyph_count - metric_count),
+        };
+        for _ in 0..metric_count {
+            table.records.push((tape.take()?, tape.take()?));
+        }
+        for _ in metric_count..glyph_count {
+            table.top_side_bearings.push(tape.take()?);
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use opentype::value::Read;
+
+    use super::{VerticalMetrics, VerticalOrigin};
+
+    macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+    #[test]
+    fn metrics() {
+        let table = VerticalMetrics {
+            records: vec![(1000, 80), (1100, 90)],
+            top_side_bearings: vec![100, 110],
+        };
+        assert_eq!(table.get(0), (1000, 80));
+        assert_eq!(table.get(1), (1100, 90));
+        assert_eq!(table.get(2), (1100, 100));
+        assert_eq!(table.get(3), (1100, 110));
+        assert_eq!(table.get(4), (1100, 110));
+    }
+
+    #[test]
+    fn origin() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x01, 0x00, 0x00, // majorVersion, minorVersion
+            0x03, 0x70, 0x00, 0x02, // defaultVertOriginY, numVertOriginYMetrics
+            0x00, 0x05, 0x03, 0x84, // glyphIndex, vertOriginY
+            0x00, 0x09, 0x03, 0x20, // glyphIndex, vertOriginY
+        ];
+        let table = ok!(VerticalOrigin::read(&mut Cursor::new(data)));
+        assert_eq!(table.get(0), 880);
+        assert_eq!(table.get(5), 900);
+        assert_eq!(table.get(9), 800);
+    }
+}
@@ -2,6 +2,9 @@ use std::cell::RefCell;
 use std::io::{Cursor, Result};
 use std::rc::Rc;
 
+use opentype::truetype::tables::names::NameID;
+use opentype::truetype::GlyphID;
+
 use crate::formats::opentype::cache::{Cache, Reference};
 use crate::formats::opentype::{
     axes, characters, features, metrics, names, palettes, tables, timestamps,
@@ -25,6 +28,27 @@ impl<T: crate::Read> crate::font::Case for Font<T> {
         characters::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn characters_of(&mut self, glyph_id: GlyphID) -> Result<Vec<char>> {
+        characters::characters_of(&mut self.cache.borrow_mut(), glyph_id)
+    }
+
+    #[inline]
+    fn codes(&mut self) -> Result<Vec<(u32, GlyphID)>> {
+        characters::codes(&mut self.cache.borrow_mut())
+    }
+
+    #[inline]
+    fn coverage(&mut self) -> Result<crate::Coverage> {
+        let characters = characters::read(&mut self.cache.borrow_mut())?;
+        Ok(crate::Coverage::new(&characters))
+    }
+
+    #[inline]
+    fn encodings(&mut self) -> Result<crate::Encodings> {
+        characters::encodings(&mut self.cache.borrow_mut())
+    }
+
     #[inline]
     fn features(&mut self) -> Result<crate::Features> {
         features::read(&mut self.cache.borrow_mut())
@@ -35,16 +59,48 @@ impl<T: crate::Read> crate::font::Case for Font<T> {
         metrics::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn line_metrics(&mut self, policy: crate::LinePolicy) -> Result<crate::LineMetrics> {
+        metrics::read_line(&mut self.cache.borrow_mut(), policy)
+    }
+
     #[inline]
     fn names(&mut self) -> Result<crate::Names> {
         names::read(&mut self.cache.borrow_mut())
     }
 
     #[inline]
-    fn palettes(&mut self) -> Result<crate::Palettes> {
+    fn palettes(&mut self) -> Result<Option<crate::Palettes>> {
         palettes::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn sequences(&mut self) -> Result<crate::Sequences> {
+        characters::sequences(&mut self.cache.borrow_mut())
+    }
+
+    #[inline]
+    fn select_encoding(&mut self, platform_id: u16, encoding_id: u16) -> Result<()> {
+        characters::select(&mut self.cache.borrow_mut(), platform_id, encoding_id)
+    }
+
+    #[inline]
+    fn set_name(&mut self, name_id: NameID, language: &str, value: &str) -> Result<()> {
+        names::set(&mut self.cache.borrow_mut(), name_id, language, value)
+    }
+
+    #[inline]
+    fn remove_name(&mut self, name_id: NameID, language: &str) -> Result<()> {
+        names::remove(&mut self.cache.borrow_mut(), name_id, language)
+    }
+
+    fn supported_languages(&mut self) -> Result<Vec<crate::languages::Support>> {
+        let mut cache = self.cache.borrow_mut();
+        let characters = characters::read(&mut cache)?;
+        let features = features::read(&mut cache)?;
+        Ok(crate::languages::supported(&characters, &features))
+    }
+
     #[inline]
     fn tables(&mut self) -> Result<crate::Tables> {
         tables::read(&mut self.cache.borrow_mut())
@@ -55,10 +111,69 @@ impl<T: crate::Read> crate::font::Case for Font<T> {
         timestamps::read(&mut self.cache.borrow_mut())
     }
 
+    #[inline]
+    fn vertical_metrics(&mut self) -> Result<crate::VerticalMetrics> {
+        metrics::read_vertical(&mut self.cache.borrow_mut())
+    }
+
     #[inline]
     fn glyph(&mut self, _: char) -> Result<Option<crate::Glyph>> {
         error!("working with glyphs is not supported yet")
     }
+
+    #[inline]
+    fn glyph_variant(&mut self, _: char, _: char) -> Result<Option<crate::Glyph>> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn hinted_glyph(&mut self, _: char, _: u16) -> Result<Option<crate::Glyph>> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn bitmap_glyph(&mut self, _: char, _: u16) -> Result<Option<crate::glyph::Bitmap>> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn svg_glyph(&mut self, _: GlyphID) -> Result<Option<crate::glyph::Document>> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn color_glyph(&mut self, _: char) -> Result<Option<crate::ColorGlyph>> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn glyph_by_id(&mut self, _: GlyphID) -> Result<crate::Glyph> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn shallow_glyph(&mut self, _: GlyphID) -> Result<crate::Glyph> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn components(&mut self, _: GlyphID) -> Result<Vec<crate::glyph::Component>> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn measure(&mut self, _: &str, _: crate::Number) -> Result<crate::Layout> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    #[inline]
+    fn outline(&mut self, _: &str, _: crate::Number, _: crate::Number) -> Result<crate::Glyph> {
+        error!("working with glyphs is not supported yet")
+    }
+
+    fn save<U: crate::Read + crate::Write>(&mut self, _: &mut U) -> Result<()> {
+        error!("writing web fonts is not supported yet")
+    }
 }
 
 pub fn read<T>(tape: Reference<Cursor<Vec<u8>>>, backend: webtype::Font) -> Result<Vec<Font<T>>>
@@ -0,0 +1,32 @@
+/// A bitmap glyph.
+#[derive(Clone, Debug)]
+pub struct Bitmap {
+    /// The format of the data.
+    pub format: BitmapFormat,
+    /// The data.
+    pub data: Vec<u8>,
+    /// The pixels per em of the strike.
+    pub ppem: u16,
+    /// The width and height in pixels.
+    pub size: (u16, u16),
+    /// The horizontal and vertical distances from the origin to the top-left corner in pixels
+    /// with the y axis pointing up.
+    pub bearing: (i16, i16),
+    /// The advance width in pixels.
+    pub advance_width: u16,
+}
+
+/// A format of bitmap data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitmapFormat {
+    /// A PNG image.
+    Png,
+    /// A JPEG image.
+    Jpeg,
+    /// A TIFF image.
+    Tiff,
+    /// Uncompressed gray levels with a bit depth and rows padded to whole bytes.
+    Gray(u8),
+    /// Uncompressed blue, green, red, and premultiplied alpha.
+    Bgra,
+}
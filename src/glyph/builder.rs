@@ -1,4 +1,4 @@
-use crate::glyph::{Contour, Glyph, Segment};
+use crate::glyph::{Contour, Glyph, Hints, Segment};
 use crate::{Number, Offset};
 
 pub struct Builder {
@@ -41,8 +41,8 @@ impl Builder {
         );
         let previous_offset = self.offset;
         let previous_scale = self.scale;
-        self.offset += offset;
-        multiply!(self.scale, scale, self.scale);
+        self.offset += self.transform(offset);
+        multiply!(self.scale, previous_scale, scale);
         let result = build(self);
         self.scale = previous_scale;
         self.offset = previous_offset;
@@ -123,11 +123,22 @@ impl Builder {
         self.glyph.bounding_box = (min_x.into(), min_y.into(), max_x.into(), max_y.into());
     }
 
+    #[inline]
+    pub fn set_hints(&mut self, hints: Hints) {
+        self.glyph.hints = Some(hints);
+    }
+
     #[inline]
     pub fn set_horizontal_metrics(&mut self, (advance_width, left_side_bearing): (Number, Number)) {
         self.glyph.advance_width = advance_width;
         self.glyph.side_bearings.0 = left_side_bearing;
     }
+
+    #[inline]
+    pub fn set_vertical_metrics(&mut self, (advance_height, top_side_bearing): (Number, Number)) {
+        self.glyph.advance_height = advance_height;
+        self.glyph.vertical_side_bearings.0 = top_side_bearing;
+    }
 }
 
 impl Default for Builder {
@@ -148,6 +159,9 @@ impl From<Builder> for Glyph {
         let Builder { mut glyph, .. } = builder;
         let width = glyph.bounding_box.2 - glyph.bounding_box.0;
         glyph.side_bearings.1 = glyph.advance_width - (glyph.side_bearings.0 + width);
+        let height = glyph.bounding_box.3 - glyph.bounding_box.1;
+        glyph.vertical_side_bearings.1 =
+            glyph.advance_height - (glyph.vertical_side_bearings.0 + height);
         glyph
     }
 }
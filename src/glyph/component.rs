@@ -0,0 +1,21 @@
+use crate::glyph::GlyphID;
+use crate::{Number, Offset};
+
+/// A component of a composite glyph.
+#[derive(Clone, Copy, Debug)]
+pub struct Component {
+    /// The glyph identifier.
+    pub glyph_id: GlyphID,
+    /// The offset applied after the transformation.
+    pub offset: Offset,
+    /// The transformation as a 2-by-2 matrix in row-major order.
+    pub transform: (Number, Number, Number, Number),
+    /// The indices of the matched points of the composite and of the component.
+    pub points: Option<(u16, u16)>,
+    /// The indicator of the metrics of the component being used for the composite.
+    pub use_metrics: bool,
+    /// The indicator of the offset being rounded to the grid when hinting.
+    pub round_to_grid: bool,
+    /// The indicator of the offset being transformed along with the component.
+    pub scaled_offset: bool,
+}
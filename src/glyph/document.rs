@@ -0,0 +1,10 @@
+use crate::glyph::GlyphID;
+
+/// An SVG document.
+#[derive(Clone, Debug)]
+pub struct Document {
+    /// The first and last glyph identifiers covered by the document.
+    pub glyph_ids: (GlyphID, GlyphID),
+    /// The content.
+    pub content: String,
+}
@@ -0,0 +1,67 @@
+use crate::{Number, Offset};
+
+/// Hints of a PostScript glyph.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hints {
+    /// The horizontal stems as bottom edges and heights.
+    pub horizontal_stems: Vec<(Number, Number)>,
+    /// The vertical stems as left edges and widths.
+    pub vertical_stems: Vec<(Number, Number)>,
+    /// The hint masks in the order of the segments they apply from.
+    pub masks: Vec<Mask>,
+    /// The counter masks.
+    pub counter_masks: Vec<Vec<bool>>,
+    /// The alignment zones as bottom and top edges with the baseline overshoot zone first.
+    pub blue_zones: Vec<(Number, Number)>,
+    /// The additional bottom alignment zones.
+    pub other_blue_zones: Vec<(Number, Number)>,
+    /// The size of a unit in pixels below which overshoots are suppressed.
+    pub blue_scale: Number,
+    /// The overshoot distance enforced above the blue scale.
+    pub blue_shift: Number,
+    /// The distance by which alignment zones are extended.
+    pub blue_fuzz: Number,
+}
+
+/// A hint mask.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mask {
+    /// The index of the contour.
+    pub contour: usize,
+    /// The index of the first segment within the contour.
+    pub segment: usize,
+    /// The activity of the horizontal stems followed by the vertical ones.
+    pub stems: Vec<bool>,
+}
+
+impl Hints {
+    /// Scale the hints.
+    pub fn scale(&mut self, factor: Number) {
+        let stems = self.horizontal_stems.iter_mut();
+        let stems = stems.chain(self.vertical_stems.iter_mut());
+        let zones = self.blue_zones.iter_mut();
+        let zones = zones.chain(self.other_blue_zones.iter_mut());
+        for (first, second) in stems.chain(zones) {
+            *first *= factor;
+            *second *= factor;
+        }
+        self.blue_scale /= factor;
+        self.blue_shift *= factor;
+        self.blue_fuzz *= factor;
+    }
+
+    /// Translate the hints.
+    pub fn translate(&mut self, offset: Offset) {
+        for (left, _) in self.vertical_stems.iter_mut() {
+            *left += offset.0;
+        }
+        for (bottom, _) in self.horizontal_stems.iter_mut() {
+            *bottom += offset.1;
+        }
+        let zones = self.blue_zones.iter_mut();
+        for (bottom, top) in zones.chain(self.other_blue_zones.iter_mut()) {
+            *bottom += offset.1;
+            *top += offset.1;
+        }
+    }
+}
@@ -1,8 +1,18 @@
 //! Glyphs.
 
+mod bitmap;
 mod builder;
+mod component;
+mod document;
+mod hints;
 
+pub use bitmap::{Bitmap, BitmapFormat};
 pub(crate) use builder::Builder;
+pub use component::Component;
+pub use document::Document;
+pub use hints::{Hints, Mask};
+
+pub use opentype::truetype::GlyphID;
 
 use crate::{Number, Offset};
 
@@ -15,8 +25,14 @@ pub struct Glyph {
     pub bounding_box: (Number, Number, Number, Number),
     /// The left and right side bearings.
     pub side_bearings: (Number, Number),
+    /// The advance height.
+    pub advance_height: Number,
+    /// The top and bottom side bearings.
+    pub vertical_side_bearings: (Number, Number),
     /// The contours.
     pub contours: Vec<Contour>,
+    /// The hints.
+    pub hints: Option<Hints>,
 }
 
 /// A contour.
@@ -53,6 +69,95 @@ impl Glyph {
     pub fn width(&self) -> Number {
         self.bounding_box.2 - self.bounding_box.0
     }
+
+    /// Translate the glyph.
+    pub fn translate(&mut self, offset: Offset) {
+        self.bounding_box.0 += offset.0;
+        self.bounding_box.1 += offset.1;
+        self.bounding_box.2 += offset.0;
+        self.bounding_box.3 += offset.1;
+        if let Some(contour) = self.contours.first_mut() {
+            contour.offset += offset;
+        }
+        for contour in self.contours.iter_mut() {
+            contour.position += offset;
+        }
+        if let Some(hints) = self.hints.as_mut() {
+            hints.translate(offset);
+        }
+    }
+
+    /// Return the outline as SVG path data with the y axis pointing down.
+    pub fn svg_path(&self) -> String {
+        use std::fmt::Write;
+
+        let mut path = String::new();
+        let mut offset = Offset::default();
+        for contour in self.iter() {
+            offset += contour.offset;
+            let _ = write!(path, "M{} {}", offset.0, -offset.1);
+            for segment in contour.iter() {
+                match *segment {
+                    Segment::Linear(a) => {
+                        offset += a;
+                        let _ = write!(path, "L{} {}", offset.0, -offset.1);
+                    }
+                    Segment::Quadratic(a, b) => {
+                        let a = offset + a;
+                        offset = a + b;
+                        let _ = write!(path, "Q{} {} {} {}", a.0, -a.1, offset.0, -offset.1);
+                    }
+                    Segment::Cubic(a, b, c) => {
+                        let a = offset + a;
+                        let b = a + b;
+                        offset = b + c;
+                        let _ = write!(
+                            path,
+                            "C{} {} {} {} {} {}",
+                            a.0, -a.1, b.0, -b.1, offset.0, -offset.1,
+                        );
+                    }
+                }
+            }
+            path.push('Z');
+        }
+        path
+    }
+
+    /// Scale the glyph.
+    pub fn scale(&mut self, factor: Number) {
+        self.advance_width *= factor;
+        self.bounding_box.0 *= factor;
+        self.bounding_box.1 *= factor;
+        self.bounding_box.2 *= factor;
+        self.bounding_box.3 *= factor;
+        self.side_bearings.0 *= factor;
+        self.side_bearings.1 *= factor;
+        self.advance_height *= factor;
+        self.vertical_side_bearings.0 *= factor;
+        self.vertical_side_bearings.1 *= factor;
+        for contour in self.contours.iter_mut() {
+            contour.offset *= factor;
+            contour.position *= factor;
+            for segment in contour.segments.iter_mut() {
+                match segment {
+                    Segment::Linear(a) => *a *= factor,
+                    Segment::Quadratic(a, b) => {
+                        *a *= factor;
+                        *b *= factor;
+                    }
+                    Segment::Cubic(a, b, c) => {
+                        *a *= factor;
+                        *b *= factor;
+                        *c *= factor;
+                    }
+                }
+            }
+        }
+        if let Some(hints) = self.hints.as_mut() {
+            hints.scale(factor);
+        }
+    }
 }
 
 dereference! { Glyph::contours => [Contour] }
@@ -66,7 +171,10 @@ impl Default for Glyph {
             advance_width: Number::NAN,
             bounding_box: (Number::NAN, Number::NAN, Number::NAN, Number::NAN),
             side_bearings: (Number::NAN, Number::NAN),
+            advance_height: Number::NAN,
+            vertical_side_bearings: (Number::NAN, Number::NAN),
             contours: Default::default(),
+            hints: None,
         }
     }
 }
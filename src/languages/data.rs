@@ -0,0 +1,290 @@
+// Adapted from the exemplar characters of the Unicode Common Locale Data Repository.
+
+use super::Language;
+
+macro_rules! languages(
+    ($(($code:literal, $name:literal, $base:literal, $auxiliary:literal $(,)?),)*) => (
+        pub static LANGUAGES: &[Language] = &[
+            $(Language { code: $code, name: $name, base: $base, auxiliary: $auxiliary },)*
+        ];
+    );
+);
+
+languages! {
+    (
+        "ar",
+        "Arabic",
+        "ً ٌ ٍ َ ُ ِ ّ ْ ء آ أ ؤ إ ئ ا ب ة ت ث ج ح خ د ذ ر ز س ش ص ض ط ظ ع غ ف ق ك ل م ن ه و ى ي",
+        "پ چ ژ ڜ ڢ ڤ ڥ ٯ ڧ ڨ ک گ ی",
+    ),
+    (
+        "az",
+        "Azerbaijani",
+        "a b c ç d e ə f g ğ h x ı i j k q l m n o ö p r s ş t u ü v y z",
+        "w",
+    ),
+    (
+        "bg",
+        "Bulgarian",
+        "а б в г д е ж з и й к л м н о п р с т у ф х ц ч ш щ ъ ь ю я",
+        "ѐ ё ѝ ы э",
+    ),
+    (
+        "ca",
+        "Catalan",
+        "a à b c ç d e é è f g h i í ï j k l ŀl m n o ó ò p q r s t u ú ü v w x y z",
+        "á ă â å ä ã ā æ ĕ ê ë ē ì ĭ î ī ñ º ŏ ô ö ø ō œ ù ŭ û ū ÿ",
+    ),
+    (
+        "cs",
+        "Czech",
+        "a á b c č d ď e é ě f g h ch i í j k l m n ň o ó p q r ř s š t ť u ú ů v w x y ý z ž",
+        "à ă â å ä ã ā æ ç è ĕ ê ë ē ì ĭ î ï ī ľ ł ñ ò ŏ ô ö ø ō œ ŕ ù ŭ û ü ū ÿ",
+    ),
+    (
+        "cy",
+        "Welsh",
+        "a á à â ä b c ch d dd e é è ê ë f ff g ng h i í ì î ï l ll m n o ó ò ô ö p ph r rh s t th \
+         u ú ù û ü w ẃ ẁ ŵ ẅ y ý ỳ ŷ ÿ",
+        "ă å ã ā æ ç ĕ ē ĭ ī j k ñ ŏ ø ō œ q ŭ ū v x z",
+    ),
+    (
+        "da",
+        "Danish",
+        "a b c d e f g h i j k l m n o p q r s t u v w x y z æ ø å",
+        "á à â ä ç é è ê ë í î ï ñ ó ô ö ú ù û ü ÿ",
+    ),
+    (
+        "de",
+        "German",
+        "a ä b c d e f g h i j k l m n o ö p q r s ß t u ü v w x y z",
+        "á à ă â å ã ā æ ç é è ĕ ê ë ē ğ í ì ĭ î ï ī ı ñ ó ò ŏ ô ø ō œ ş ú ù ŭ û ū ÿ",
+    ),
+    (
+        "el",
+        "Greek",
+        "α ά β γ δ ε έ ζ η ή θ ι ί ϊ ΐ κ λ μ ν ξ ο ό π ρ σ ς τ υ ύ ϋ ΰ φ χ ψ ω ώ",
+        "",
+    ),
+    (
+        "en",
+        "English",
+        "a b c d e f g h i j k l m n o p q r s t u v w x y z",
+        "á à ă â å ä ã ā æ ç é è ĕ ê ë ē í ì ĭ î ï ī ñ ó ò ŏ ô ö ø ō œ ú ù ŭ û ü ū ÿ",
+    ),
+    (
+        "eo",
+        "Esperanto",
+        "a b c ĉ d e f g ĝ h ĥ i j ĵ k l m n o p r s ŝ t u ŭ v z",
+        "q w x y",
+    ),
+    (
+        "es",
+        "Spanish",
+        "a á b c d e é f g h i í j k l m n ñ o ó p q r s t u ú ü v w x y z",
+        "à ă â å ä ã ā æ ç è ĕ ê ë ē ì ĭ î ï ī ò ŏ ô ö ø ō œ ù ŭ û ū ÿ",
+    ),
+    (
+        "et",
+        "Estonian",
+        "a b c d e f g h i j k l m n o p q r s š z ž t u v w õ ä ö ü x y",
+        "á à â å ā æ ç é è ê ë ē í ì î ï ī ñ ó ò ŏ ô ø ō œ ú ù û ū",
+    ),
+    (
+        "fa",
+        "Persian",
+        "ً ٍ ٌ ّ ٔ آ ا ء أ ؤ ئ ب پ ت ث ج چ ح خ د ذ ر ز ژ س ش ص ض ط ظ ع غ ف ق ک گ ل م ن و ه ة ی",
+        "ِ ُ َ ٓ ٕ ٰ ك ى ي",
+    ),
+    (
+        "fi",
+        "Finnish",
+        "a b c d e f g h i j k l m n o p q r s t u v w x y z å ä ö",
+        "á à â ã č ç đ é è ê ë ǧ ǥ ȟ í î ï ǩ ń ñ ŋ ô õ œ ř š ŧ ú ü ʒ ǯ æ ø",
+    ),
+    (
+        "fr",
+        "French",
+        "a à â æ b c ç d e é è ê ë f g h i î ï j k l m n o ô œ p q r s t u ù û ü v w x y ÿ z",
+        "á å ä ã ā ć ē í ì ī ñ ó ò ö õ ø ō ú ū",
+    ),
+    (
+        "ga",
+        "Irish",
+        "a á b c d e é f g h i í l m n o ó p r s t u ú",
+        "ḃ ċ ḋ ḟ ġ j k ṁ ṗ q ṡ ṫ v w x y z",
+    ),
+    (
+        "ha",
+        "Hausa",
+        "a b ɓ c d ɗ e f g h i j k ƙ l m n o r s t u w y ƴ z ʼ",
+        "á à â é è ê í ì î ó ò ô p q ú ù û v x",
+    ),
+    (
+        "haw",
+        "Hawaiian",
+        "a ā e ē i ī o ō u ū h k l m n p w ʻ",
+        "b c d f g j q r s t v x y z",
+    ),
+    (
+        "hr",
+        "Croatian",
+        "a b c č ć d dž đ e f g h i j k l lj m n nj o p r s š t u v z ž",
+        "q w x y",
+    ),
+    (
+        "hu",
+        "Hungarian",
+        "a á b c cs d dz dzs e é f g gy h i í j k l ly m n ny o ó ö ő p q r s sz t ty u ú ü ű \
+         v w x y z zs",
+        "à ă â å ä ã ā æ ç è ĕ ê ë ē ì ĭ î ï ī ñ ò ŏ ô ø ō œ ù ŭ û ū",
+    ),
+    (
+        "id",
+        "Indonesian",
+        "a b c d e f g h i j k l m n o p q r s t u v w x y z",
+        "å",
+    ),
+    (
+        "ig",
+        "Igbo",
+        "a b ch d e f g gb gh gw h i ị j k kp kw l m n ṅ nw ny o ọ p r s sh t u ụ v w y z",
+        "á à é è í ì ị́ ị̀ ḿ ǹ ń ó ò ọ́ ọ̀ ú ù ụ́ ụ̀",
+    ),
+    (
+        "is",
+        "Icelandic",
+        "a á b d ð e é f g h i í j k l m n o ó p r s t u ú v x y ý þ æ ö",
+        "c q w z",
+    ),
+    (
+        "it",
+        "Italian",
+        "a à b c d e é è f g h i ì j k l m n o ó ò p q r s t u ù v w x y z",
+        "í î ï ú",
+    ),
+    (
+        "lt",
+        "Lithuanian",
+        "a ą b c č d e ę ė f g h i į y j k l m n o p r s š t u ų ū v z ž",
+        "à á ã ą̃ ą́ ą̀ è é ẽ ę̃ ę́ ę̀ ė̃ ė́ ė̀ ì í ĩ į̃ į̇ į̇́ į̇̀ ì̇ í̇ ĩ̇ ỹ ý ỳ ñ ò ó õ q ù ú ũ ų̃ ų́ ų̀ \
+         ū̃ ū́ ū̀ w x",
+    ),
+    (
+        "lv",
+        "Latvian",
+        "a ā b c č d e ē f g ģ h i ī j k ķ l ļ m n ņ o p r s š t u ū v z ž",
+        "q w x y",
+    ),
+    (
+        "mt",
+        "Maltese",
+        "a à b ċ d e è f ġ g għ h ħ i ì ie j k l m n o ò p q r s t u ù v w x ż z",
+        "c y",
+    ),
+    (
+        "nl",
+        "Dutch",
+        "a á ä b c d e é ë f g h i í ï ij j k l m n o ó ö p q r s t u ú ü v w x y z",
+        "à â å ã æ ç è ê î ñ ô ø œ ù û",
+    ),
+    (
+        "no",
+        "Norwegian",
+        "a à b c d e é f g h i j k l m n o ó ò ô p q r s t u v w x y z æ ø å",
+        "á ǎ ã č ç đ è ê í ń ñ ŋ š ŧ ü ž ä ö",
+    ),
+    (
+        "pl",
+        "Polish",
+        "a ą b c ć d e ę f g h i j k l ł m n ń o ó p r s ś t u w y z ź ż",
+        "q v x",
+    ),
+    (
+        "pt",
+        "Portuguese",
+        "a á à â ã b c ç d e é ê f g h i í j k l m n o ó ô õ p q r s t u ú v w x y z",
+        "ă å ä ā æ è ĕ ë ē ì ĭ î ï ī ñ ò ŏ ö ø ō œ ù ŭ û ü ū ÿ",
+    ),
+    (
+        "ro",
+        "Romanian",
+        "a ă â b c d e f g h i î j k l m n o p q r s ș t ț u v w x y z",
+        "á à å ä ç é è ê ë ñ ö ş ţ ü",
+    ),
+    (
+        "ru",
+        "Russian",
+        "а б в г д е ё ж з и й к л м н о п р с т у ф х ц ч ш щ ъ ы ь э ю я",
+        "",
+    ),
+    (
+        "sk",
+        "Slovak",
+        "a á ä b c č d ď dz dž e é f g h ch i í j k l ĺ ľ m n ň o ó ô p q r ŕ s š t ť u ú v w x \
+         y ý z ž",
+        "à ă â å ā æ ç è ĕ ê ë ē ì ĭ î ï ī ñ ò ŏ ö ø ō œ ř ù ŭ û ü ū ÿ",
+    ),
+    (
+        "sl",
+        "Slovenian",
+        "a b c č d e f g h i j k l m n o p r s š t u v z ž",
+        "á à ă â å ä ā æ ç ć đ é è ĕ ê ë ē í ì ĭ î ï ī ñ ó ò ŏ ô ö ø ō œ q ú ù ŭ û ü ū w x y ÿ",
+    ),
+    (
+        "sq",
+        "Albanian",
+        "a b c ç d dh e ë f g gj h i j k l ll m n nj o p q r rr s sh t th u v x xh y z zh",
+        "w",
+    ),
+    (
+        "sr",
+        "Serbian",
+        "а б в г д ђ е ж з и ј к л љ м н њ о п р с т ћ у ф х ц ч џ ш",
+        "",
+    ),
+    (
+        "sv",
+        "Swedish",
+        "a b c d e f g h i j k l m n o p q r s t u v w x y z å ä ö",
+        "á à â ã ā ç é è ê ë í î ï ī ñ ó ô õ ø œ ú ù û ü ū ÿ æ",
+    ),
+    (
+        "sw",
+        "Swahili",
+        "a b ch d e f g h i j k l m n o p r s t u v w y z",
+        "c q x",
+    ),
+    (
+        "th",
+        "Thai",
+        "ฯ ๆ ๎ ์ ็ ่ ้ ๊ ๋ ก ข ฃ ค ฅ ฆ ง จ ฉ ช ซ ฌ ญ ฎ ฏ ฐ ฑ ฒ ณ ด ต ถ ท ธ น บ ป ผ ฝ พ ฟ ภ ม ย ร ฤ \
+         ล ฦ ว ศ ษ ส ห ฬ อ ฮ ฺ ะ ั า ำ ิ ี ึ ื ุ ู เ แ โ ใ ไ ๅ",
+        "",
+    ),
+    (
+        "tr",
+        "Turkish",
+        "a b c ç d e f g ğ h ı i j k l m n o ö p r s ş t u ü v y z",
+        "á à ă â å ä ã ā æ é è ĕ ê ë ē í ì ĭ î ï ī ñ ó ò ŏ ô ø ō œ q ß ú ù ŭ û ū w x ÿ",
+    ),
+    (
+        "uk",
+        "Ukrainian",
+        "а б в г ґ д е є ж з и і ї й к л м н о п р с т у ф х ц ч ш щ ь ю я ʼ",
+        "ё ъ ы э",
+    ),
+    (
+        "vi",
+        "Vietnamese",
+        "a à ả ã á ạ ă ằ ẳ ẵ ắ ặ â ầ ẩ ẫ ấ ậ b c d đ e è ẻ ẽ é ẹ ê ề ể ễ ế ệ g h i ì ỉ ĩ í ị k l \
+         m n o ò ỏ õ ó ọ ô ồ ổ ỗ ố ộ ơ ờ ở ỡ ớ ợ p q r s t u ù ủ ũ ú ụ ư ừ ử ữ ứ ự v x y ỳ ỷ ỹ ý ỵ",
+        "f j w z",
+    ),
+    (
+        "yo",
+        "Yoruba",
+        "a á à b d e é è ẹ ẹ́ ẹ̀ f g gb h i í ì j k l m n o ó ò ọ ọ́ ọ̀ p r s ṣ t u ú ù w y",
+        "c q v x z",
+    ),
+}
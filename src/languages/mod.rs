@@ -0,0 +1,106 @@
+//! Language support.
+
+mod data;
+
+use crate::characters::Characters;
+use crate::features::{Directory, Feature};
+
+/// A language with its exemplar characters.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Language {
+    /// The BCP 47 code.
+    pub code: &'static str,
+    /// The English name.
+    pub name: &'static str,
+    /// The space-separated characters and sequences required to write the language.
+    pub base: &'static str,
+    /// The space-separated characters and sequences found in loanwords and names.
+    pub auxiliary: &'static str,
+}
+
+/// A supported language.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Support {
+    /// The language.
+    pub language: &'static Language,
+    /// The flag indicating that the auxiliary characters are also supported.
+    pub auxiliary: bool,
+}
+
+impl Language {
+    /// Return all known languages.
+    #[inline]
+    pub fn all() -> &'static [Language] {
+        data::LANGUAGES
+    }
+
+    /// Return the language with a code.
+    pub fn get(code: &str) -> Option<&'static Language> {
+        data::LANGUAGES
+            .iter()
+            .find(|language| language.code == code)
+    }
+}
+
+/// Return the languages whose base characters are supported.
+///
+/// Sequences with combining marks are deemed supported when their characters are present and
+/// there is either a glyph-composition or mark-positioning feature.
+pub fn supported(characters: &Characters, features: &Directory) -> Vec<Support> {
+    let marks = features.features.iter().any(|(feature, _)| {
+        matches!(
+            feature,
+            Feature::GlyphCompositionDecomposition | Feature::MarkPositioning
+        )
+    });
+    let check = |language: &Language, exemplars: &str| {
+        // Turkic languages pair dotted and dotless i with İ and I, respectively.
+        let turkic = matches!(language.code, "az" | "tr");
+        exemplars
+            .split_whitespace()
+            .all(|sequence| contains(characters, marks, turkic, sequence))
+    };
+    data::LANGUAGES
+        .iter()
+        .filter(|language| check(language, language.base))
+        .map(|language| Support {
+            language,
+            auxiliary: check(language, language.auxiliary),
+        })
+        .collect()
+}
+
+fn contains(characters: &Characters, marks: bool, turkic: bool, sequence: &str) -> bool {
+    for (index, character) in sequence.chars().enumerate() {
+        if !characters.contains(character) {
+            return false;
+        }
+        if index > 0 && !marks && combining(character) {
+            return false;
+        }
+        if let Some(other) = uppercase(character, turkic) {
+            if !characters.contains(other) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn combining(character: char) -> bool {
+    matches!(
+        character as u32,
+        0x0300..=0x036F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    )
+}
+
+fn uppercase(character: char, turkic: bool) -> Option<char> {
+    if turkic && character == 'i' {
+        return Some('\u{130}');
+    }
+    let mut values = character.to_uppercase();
+    match (values.next(), values.next()) {
+        (Some(value), None) => Some(value),
+        _ => None,
+    }
+}
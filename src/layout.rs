@@ -0,0 +1,105 @@
+//! Text layout.
+
+use crate::glyph::{Contour, Glyph, GlyphID, Segment};
+use crate::{Number, Offset};
+
+/// A layout of a line of text.
+#[derive(Clone, Debug)]
+pub struct Layout {
+    /// The advance width.
+    pub advance_width: Number,
+    /// The left, bottom, right, and top edges of the ink.
+    pub bounding_box: (Number, Number, Number, Number),
+    /// The line height.
+    pub line_height: Number,
+    /// The glyphs.
+    pub glyphs: Vec<Placement>,
+}
+
+/// A glyph placed on a line.
+#[derive(Clone, Debug)]
+pub struct Placement {
+    /// The character.
+    pub character: char,
+    /// The glyph identifier.
+    pub glyph_id: GlyphID,
+    /// The position of the origin of the glyph relative to the origin of the line.
+    pub offset: Offset,
+    /// The glyph.
+    pub glyph: Glyph,
+}
+
+impl Layout {
+    /// Return the height of the ink.
+    #[inline]
+    pub fn height(&self) -> Number {
+        self.bounding_box.3 - self.bounding_box.1
+    }
+
+    /// Return the width of the ink.
+    #[inline]
+    pub fn width(&self) -> Number {
+        self.bounding_box.2 - self.bounding_box.0
+    }
+
+    /// Combine the glyphs into a single outline.
+    pub fn outline(&self) -> Glyph {
+        let mut contours = vec![];
+        let mut position = Offset::default();
+        for placement in self.glyphs.iter() {
+            let mut first = true;
+            let mut local = Offset::default();
+            for contour in placement.glyph.iter() {
+                local += contour.offset;
+                let mut contour = Contour {
+                    offset: contour.offset,
+                    position: contour.position + placement.offset,
+                    segments: contour.segments.clone(),
+                };
+                if first {
+                    contour.offset += placement.offset - position;
+                    first = false;
+                }
+                local += travel(&contour);
+                contours.push(contour);
+            }
+            if !first {
+                position = placement.offset + local;
+            }
+        }
+        Glyph {
+            advance_width: self.advance_width,
+            bounding_box: self.bounding_box,
+            side_bearings: (
+                self.bounding_box.0,
+                self.advance_width - self.bounding_box.2,
+            ),
+            contours,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for Layout {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            advance_width: 0.0,
+            bounding_box: (Number::NAN, Number::NAN, Number::NAN, Number::NAN),
+            line_height: 0.0,
+            glyphs: Default::default(),
+        }
+    }
+}
+
+fn travel(contour: &Contour) -> Offset {
+    let mut offset = Offset::default();
+    for segment in contour.iter() {
+        match *segment {
+            Segment::Linear(a) => offset += a,
+            Segment::Quadratic(a, b) => offset += a + b,
+            Segment::Cubic(a, b, c) => offset += a + b + c,
+        }
+    }
+    offset
+}
@@ -28,11 +28,15 @@ pub extern crate opentype;
 #[cfg(feature = "webtype")]
 pub extern crate webtype;
 
-#[macro_use(dereference, error, raise)]
+#[macro_use(dereference, error, raise, table)]
 extern crate typeface;
 
+pub mod color;
+pub mod coverage;
 pub mod formats;
 pub mod glyph;
+pub mod languages;
+pub mod layout;
 
 mod file;
 mod font;
@@ -42,16 +46,19 @@ mod timestamps;
 
 pub use typeface::tape::{Read, Write};
 
+pub use self::color::ColorGlyph;
+pub use self::coverage::Coverage;
 pub use self::file::File;
 pub use self::font::{Case, Font};
 pub use self::formats::opentype::axes::{self, Axes};
-pub use self::formats::opentype::characters::{self, Characters};
+pub use self::formats::opentype::characters::{self, Characters, Encodings, Sequences};
 pub use self::formats::opentype::features::{self, Directory as Features};
 pub use self::formats::opentype::names::Names;
-pub use self::formats::opentype::palettes::Palettes;
+pub use self::formats::opentype::palettes::{Palette, Palettes};
 pub use self::formats::opentype::tables::Tables;
 pub use self::glyph::Glyph;
-pub use self::metrics::Metrics;
+pub use self::layout::Layout;
+pub use self::metrics::{LineMetrics, LinePolicy, Metrics, VerticalMetrics};
 pub use self::offset::Offset;
 pub use self::timestamps::Timestamps;
 
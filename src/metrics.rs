@@ -21,4 +21,73 @@ pub struct Metrics {
     pub clipping_descender: Number,
     /// The typographical line gap.
     pub line_gap: Number,
+    /// The position of the top of the underline relative to the baseline.
+    pub underline_position: Number,
+    /// The thickness of the underline.
+    pub underline_thickness: Number,
+    /// The position of the top of the strikeout relative to the baseline.
+    pub strikeout_position: Number,
+    /// The thickness of the strikeout.
+    pub strikeout_thickness: Number,
+    /// The horizontal and vertical sizes of subscripts.
+    pub subscript_size: (Number, Number),
+    /// The horizontal and vertical offsets of subscripts relative to the baseline.
+    pub subscript_offset: (Number, Number),
+    /// The horizontal and vertical sizes of superscripts.
+    pub superscript_size: (Number, Number),
+    /// The horizontal and vertical offsets of superscripts relative to the baseline.
+    pub superscript_offset: (Number, Number),
+    /// The italic angle in degrees counterclockwise from the vertical.
+    pub italic_angle: Number,
+    /// The rise and run of the caret slope.
+    pub caret_slope: (Number, Number),
+    /// The horizontal shift of the caret for slanted glyphs.
+    pub caret_offset: Number,
+}
+
+/// Line-spacing metrics.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LineMetrics {
+    /// The ascender relative to the baseline.
+    pub ascender: Number,
+    /// The descender relative to the baseline.
+    pub descender: Number,
+    /// The line gap.
+    pub line_gap: Number,
+}
+
+/// A policy for choosing line-spacing metrics.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LinePolicy {
+    /// The typographical metrics if requested by the font and otherwise the horizontal header
+    /// falling back to the typographical and then Windows metrics, as in web browsers.
+    Browser,
+    /// The horizontal header falling back to the typographical metrics, as on macOS.
+    Macintosh,
+    /// The typographical metrics if requested by the font and otherwise the Windows metrics with
+    /// the external leading of GDI, as on Windows.
+    Windows,
+}
+
+impl LineMetrics {
+    /// Return the distance between consecutive baselines.
+    #[inline]
+    pub fn height(&self) -> Number {
+        self.ascender - self.descender + self.line_gap
+    }
+}
+
+/// Vertical metrics.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VerticalMetrics {
+    /// The granularity of the coordinate grid.
+    pub granularity: Number,
+    /// The typographical ascender relative to the vertical baseline.
+    pub ascender: Number,
+    /// The typographical descender relative to the vertical baseline.
+    pub descender: Number,
+    /// The typographical line gap.
+    pub line_gap: Number,
+    /// The flag indicating that the values are derived from horizontal metrics.
+    pub synthesized: bool,
 }
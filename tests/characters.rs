@@ -1,9 +1,10 @@
 #[macro_use]
 mod support;
 
-use font::characters::Character;
+use font::characters::{Character, Sequence};
+use font::File;
 
-use crate::support::{setup, Fixture};
+use crate::support::{graft, setup, table, trace, Fixture};
 
 #[test]
 fn adobe_blank() {
@@ -86,3 +87,160 @@ fn adobe_blank() {
         ],
     );
 }
+
+#[test]
+fn sequences() {
+    let mut file = setup(Fixture::SourceSerif);
+    assert!(ok!(file[0].sequences()).is_empty());
+    let glyph = ok!(ok!(file[0].glyph_variant('a', '\u{FE00}')));
+    assert_eq!(
+        glyph.advance_width,
+        ok!(ok!(file[0].glyph('a'))).advance_width
+    );
+    assert!(ok!(file[0].glyph_variant('\u{E000}', '\u{FE00}')).is_none());
+}
+
+#[test]
+fn sequences_grafted() {
+    let codes = ok!(setup(Fixture::SourceSerif)[0].codes());
+    let find = |code| ok!(codes.iter().find(|(other, _)| *other == code)).1;
+    let (a, b) = (find(0x61), find(0x62));
+    #[rustfmt::skip]
+    let mut variants = vec![
+        0x00, 0x0E, 0x00, 0x00, 0x00, 0x31, 0x00, 0x00, 0x00, 0x02, // format, length, count
+        0x00, 0xFE, 0x00, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, // U+FE00
+        0x00, 0xFE, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x28, // U+FE01
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x61, 0x00, // default: U+0061
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x61, // non-default: U+0061
+    ];
+    variants.extend(b.to_be_bytes());
+    let mut file = graft(
+        Fixture::SourceSerif,
+        &[(
+            b"cmap",
+            append(table(Fixture::SourceSerif, b"cmap"), variants),
+        )],
+    );
+    assert_eq!(
+        ok!(file[0].sequences()),
+        [
+            Sequence {
+                character: 'a',
+                selector: '\u{FE00}',
+                glyph_id: a,
+                default: true,
+            },
+            Sequence {
+                character: 'a',
+                selector: '\u{FE01}',
+                glyph_id: b,
+                default: false,
+            },
+        ],
+    );
+    let glyph = |file: &mut File<_>, character, selector| {
+        trace(&ok!(ok!(file[0].glyph_variant(character, selector))))
+    };
+    let expected = trace(&ok!(ok!(file[0].glyph('a'))));
+    assert_eq!(glyph(&mut file, 'a', '\u{FE00}'), expected);
+    let expected = trace(&ok!(ok!(file[0].glyph('b'))));
+    assert_eq!(glyph(&mut file, 'a', '\u{FE01}'), expected);
+    let expected = trace(&ok!(ok!(file[0].glyph('c'))));
+    assert_eq!(glyph(&mut file, 'c', '\u{FE01}'), expected);
+    assert!(ok!(file[0].glyph_variant('\u{E000}', '\u{FE01}')).is_none());
+}
+
+#[test]
+fn adobe_blank_full_repertoire() {
+    let mut file = setup(Fixture::AdobeBlank);
+    let values = ok!(file[0].characters());
+    assert_eq!(values.len(), 19);
+    assert_eq!(values[0], Character::Range(('\u{0}', '\u{D7FF}')));
+    assert_eq!(values[3], Character::Range(('\u{10000}', '\u{1FFFD}')));
+    assert!(ok!(file[0].glyph('\u{20000}')).is_some());
+}
+
+#[test]
+fn encodings() {
+    let mut file = setup(Fixture::AdobeBlank);
+    let values = ok!(file[0].encodings())
+        .into_iter()
+        .map(|encoding| {
+            (
+                encoding.platform_id,
+                encoding.encoding_id,
+                encoding.format,
+                encoding.selected,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            (0, 3, 4, false),
+            (0, 4, 12, true),
+            (1, 0, 6, false),
+            (3, 1, 4, false),
+            (3, 10, 12, false),
+        ],
+    );
+    ok!(file[0].select_encoding(1, 0));
+    assert!(ok!(file[0].encodings())[2].selected);
+    assert!(ok!(file[0].glyph('\u{20000}')).is_none());
+    assert!(file[0].select_encoding(3, 0).is_err());
+}
+
+#[test]
+fn characters_of() {
+    let mut file = setup(Fixture::Numans);
+    assert_eq!(ok!(file[0].characters_of(119)), ['\u{B5}', '\u{3BC}']);
+    assert!(ok!(file[0].characters_of(0)).is_empty());
+}
+
+#[test]
+fn operations() {
+    let one = ok!(setup(Fixture::OpenSans)[0].characters());
+    let other = ok!(setup(Fixture::SourceSerif)[0].characters());
+    let union = one.union(&other);
+    let intersection = one.intersection(&other);
+    assert_eq!(
+        union.count() + intersection.count(),
+        one.count() + other.count(),
+    );
+    assert_eq!(
+        one.difference(&other).count(),
+        one.count() - intersection.count(),
+    );
+    assert!(one.covers("Zażółć gęślą jaźń"));
+    assert!(one.contains('Ж'));
+    assert!(!other.contains('Ж'));
+    assert!(one.difference(&other).contains('Ж'));
+}
+
+#[test]
+fn codes() {
+    let mut file = setup(Fixture::OpenSans);
+    let values = ok!(file[0].codes());
+    assert!(values.contains(&(0x41, 36)));
+    assert_eq!(values.len(), ok!(file[0].characters()).count());
+}
+
+// Add a subtable for the Unicode platform after the existing ones.
+fn append(mut data: Vec<u8>, subtable: Vec<u8>) -> Vec<u8> {
+    let count = u16::from_be_bytes([data[2], data[3]]) as usize;
+    let index = (0..count)
+        .take_while(|index| data[4 + 8 * index..6 + 8 * index] == [0, 0])
+        .count();
+    for index in 0..count {
+        let range = 8 + 8 * index..12 + 8 * index;
+        let offset = u32::from_be_bytes(ok!(data[range.clone()].try_into())) + 8;
+        data[range].copy_from_slice(&offset.to_be_bytes());
+    }
+    let offset = data.len() as u32 + 8;
+    data[2..4].copy_from_slice(&(count as u16 + 1).to_be_bytes());
+    let mut record = vec![0x00, 0x00, 0x00, 0x05];
+    record.extend(offset.to_be_bytes());
+    data.splice(4 + 8 * index..4 + 8 * index, record);
+    data.extend(subtable);
+    data
+}
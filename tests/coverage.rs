@@ -0,0 +1,60 @@
+#[macro_use]
+mod support;
+
+use font::Coverage;
+
+use crate::support::{setup, Fixture};
+
+#[test]
+fn open_sans() {
+    let mut file = setup(Fixture::OpenSans);
+    let coverage = ok!(file[0].coverage());
+    let values = coverage
+        .scripts
+        .iter()
+        .map(|entry| (entry.name, entry.count, entry.total))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            ("Common", 160, 8252),
+            ("Cyrillic", 273, 443),
+            ("Greek", 75, 518),
+            ("Inherited", 8, 657),
+            ("Latin", 367, 1475),
+        ],
+    );
+    let values = coverage
+        .blocks
+        .iter()
+        .take(3)
+        .map(|entry| (entry.name, entry.count, entry.percentage()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [
+            ("Basic Latin", 95, 74.21875),
+            ("Latin-1 Supplement", 96, 75.0),
+            ("Latin Extended-A", 128, 100.0),
+        ],
+    );
+    assert_eq!(coverage.blocks.len(), 21);
+}
+
+#[test]
+fn malformed() {
+    use font::characters::Character::{Range, Scalar};
+
+    let coverage = Coverage::new(&[
+        Range(('z', 'a')),
+        Range(('a', 'c')),
+        Scalar('b'),
+        Range(('b', 'd')),
+    ]);
+    let values = coverage
+        .scripts
+        .iter()
+        .map(|entry| (entry.name, entry.count))
+        .collect::<Vec<_>>();
+    assert_eq!(values, [("Latin", 4)]);
+}
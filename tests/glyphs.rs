@@ -64,6 +64,35 @@ mod monte_carlo {
             (224.2497 , 387.49524),
         ]);
     }
+
+    #[test]
+    fn shear() {
+        let font = &mut setup(Fixture::MonteCarlo)[0];
+        let components = ok!(font.components(18));
+        assert_eq!(components[1].glyph_id, 382);
+        assert_eq!(
+            components[1].transform,
+            (0.8499756, 0.1786499, 0.0, 0.8499756)
+        );
+        let glyph = ok!(font.glyph_by_id(18));
+        assert_eq!(glyph.len(), 2);
+        #[rustfmt::skip]
+        assert_eq!(&trace(&glyph)[91..], &vec![
+            ( 840.7286 , 764.2406 ),
+            ( 837.15   , 763.3906 ),
+            ( 831.31116, 765.94055),
+            ( 832.6294 , 770.1904 ),
+            ( 959.8272 , 857.7379 ),
+            ( 982.3704 , 867.9376 ),
+            ( 988.9483 , 862.83777),
+            (1024.4099 , 772.74036),
+            (1024.724  , 770.1904 ),
+            (1019.9165 , 765.51556),
+            (1013.63104, 765.94055),
+            ( 964.53217, 835.63855),
+            ( 840.7287 , 764.2406 ),
+        ]);
+    }
 }
 
 mod numans {
@@ -117,9 +146,141 @@ mod numans {
     }
 }
 
-mod source_serif {
+mod qahiri {
     use crate::support::{setup, trace, Fixture};
 
+    #[test]
+    fn comma_hinted() {
+        let font = &mut setup(Fixture::Qahiri)[0];
+        let glyph = ok!(ok!(font.hinted_glyph('\u{060C}', 16)));
+        assert_eq!(glyph.len(), 1);
+        assert_eq!(glyph.advance_width, 3.0);
+        assert_eq!(glyph.bounding_box, (-0.015625, 0.0, 1.875, 3.953125));
+        assert_eq!(glyph.side_bearings, (-0.015625, 1.125));
+        #[rustfmt::skip]
+        assert_eq!(&trace(&glyph), &vec![
+            (  1.46875, 3.953125),
+            ( 0.203125,  2.34375),
+            (  0.96875,      0.0),
+            (    1.875, 1.234375),
+            (1.4140625,   1.9375),
+            (1.1796875, 2.953125),
+            (  1.46875, 3.953125),
+        ]);
+        let values = [(9, 1.0), (12, 2.0), (24, 4.0), (37, 6.0)];
+        for (ppem, advance_width) in values {
+            let glyph = ok!(ok!(font.hinted_glyph('\u{060C}', ppem)));
+            assert_eq!(glyph.advance_width, advance_width);
+        }
+    }
+}
+
+mod source_serif {
+    use crate::support::{graft, setup, trace, Fixture};
+
+    #[test]
+    fn accent() {
+        #[rustfmt::skip]
+        let data = vec![
+            0x01, 0x00, 0x04, 0x01, // major, minor, hdrSize, offSize
+            0x00, 0x01, 0x01, 0x01, 0x05, 0x54, 0x65, 0x73, 0x74, // Name INDEX
+            0x00, 0x01, 0x01, 0x01, 0x18, // Top DICT INDEX
+            0x1D, 0x00, 0x00, 0x00, 0x2D, 0x0F, // charset
+            0x1D, 0x00, 0x00, 0x00, 0x37, 0x11, // CharStrings
+            0x1D, 0x00, 0x00, 0x00, 0x02, 0x1D, 0x00, 0x00, 0x00, 0x5A, 0x12, // Private
+            0x00, 0x00, // String INDEX
+            0x00, 0x00, // Global Subr INDEX
+            // Charset1
+            0x01, // format
+            0x00, 0x22, 0x00, // A
+            0x00, 0x7D, 0x00, // acute
+            0x00, 0xAE, 0x00, // Aacute
+            // CharStrings INDEX
+            0x00, 0x04, 0x01, 0x01, 0x02, 0x0E, 0x16, 0x1C,
+            0x0E, // endchar
+            0xEF, 0x8B, 0x15, // 100 0 rmoveto
+            0xF7, 0x5C, 0x8B, 0x05, 0x8B, 0xF7, 0xC0, 0x05, // 200 0 rlineto 0 300 rlineto
+            0x0E, // endchar
+            0xBD, 0xF8, 0x24, 0x15, // 50 400 rmoveto
+            0x9F, 0x8B, 0x05, // 20 0 rlineto
+            0x0E, // endchar
+            0x95, 0x9F, 0xCC, 0xF7, 0x56, 0x0E, // 10 20 65 194 endchar
+            // Private DICT
+            0x8B, 0x14, // defaultWidthX
+        ];
+        let mut file = graft(Fixture::SourceSerif, &[(b"CFF ", data)]);
+        let font = &mut file[0];
+        let glyph = ok!(font.glyph_by_id(3));
+        assert_eq!(glyph.len(), 2);
+        #[rustfmt::skip]
+        assert_eq!(&trace(&glyph), &vec![
+            (100.0,   0.0),
+            (300.0,   0.0),
+            (300.0, 300.0),
+
+            ( 60.0, 420.0),
+            ( 80.0, 420.0),
+        ]);
+        let components = ok!(font.components(3));
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].glyph_id, 1);
+        assert_eq!(components[0].offset, (0.0, 0.0).into());
+        assert_eq!(components[1].glyph_id, 2);
+        assert_eq!(components[1].offset, (10.0, 20.0).into());
+        assert_eq!(components[1].transform, (1.0, 0.0, 0.0, 1.0));
+        assert!(ok!(font.components(1)).is_empty());
+        assert_eq!(ok!(font.shallow_glyph(3)).len(), 0);
+        assert_eq!(ok!(font.shallow_glyph(1)).len(), 1);
+    }
+
+    #[test]
+    fn bitmap() {
+        let font = &mut setup(Fixture::SourceSerif)[0];
+        assert!(ok!(font.bitmap_glyph('a', 20)).is_none());
+    }
+
+    #[test]
+    fn hints() {
+        let font = &mut setup(Fixture::SourceSerif)[0];
+        let glyph = ok!(ok!(font.glyph('o')));
+        let hints = ok!(glyph.hints.as_ref());
+        assert_eq!(hints.horizontal_stems, [(-15.0, 45.0), (445.0, 46.0)]);
+        assert_eq!(hints.vertical_stems, [(45.0, 95.0), (409.0, 95.0)]);
+        assert!(hints.masks.is_empty());
+        assert_eq!(hints.blue_zones[..2], [(-20.0, 0.0), (473.0, 491.0)]);
+        assert_eq!(hints.other_blue_zones, [(-249.0, -239.0)]);
+        assert_eq!(hints.blue_shift, 7.0);
+
+        let glyph = ok!(ok!(font.glyph('a')));
+        let hints = ok!(glyph.hints.as_ref());
+        let masks = hints
+            .masks
+            .iter()
+            .map(|mask| (mask.contour, mask.segment, mask.stems.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            masks,
+            [
+                (0, 0, vec![true, false, true, true, true]),
+                (1, 0, vec![false, true, true, true, true]),
+                (1, 14, vec![true, false, true, true, true]),
+                (1, 16, vec![false, true, true, true, true]),
+            ],
+        );
+    }
+
+    #[test]
+    fn hinted() {
+        let font = &mut setup(Fixture::SourceSerif)[0];
+        assert!(font.hinted_glyph('a', 12).is_err());
+    }
+
+    #[test]
+    fn svg() {
+        let font = &mut setup(Fixture::SourceSerif)[0];
+        assert!(ok!(font.svg_glyph(1)).is_none());
+    }
+
     #[test]
     fn from_a_to_z() {
         let font = &mut setup(Fixture::SourceSerif)[0];
@@ -155,11 +316,218 @@ mod source_serif {
         let glyph = ok!(ok!(font.glyph('r')));
         assert_eq!(glyph.bounding_box, (34.0, 0.0, 412.0, 491.0));
         assert_eq!(glyph.side_bearings, (34.0, 11.0));
+        assert_eq!(glyph.advance_height, 1000.0);
+        assert_eq!(glyph.vertical_side_bearings, (239.0, 270.0));
     }
 }
 
 mod open_sans {
-    use crate::support::{setup, trace, Fixture};
+    use font::glyph::BitmapFormat;
+
+    use crate::support::{graft, setup, table, trace, Fixture};
+
+    #[test]
+    fn bitmap() {
+        #[rustfmt::skip]
+        let locations = vec![
+            0x00, 0x02, 0x00, 0x00, // majorVersion, minorVersion
+            0x00, 0x00, 0x00, 0x01, // numSizes
+            0x00, 0x00, 0x00, 0x38, // indexSubtableListOffset
+            0x00, 0x00, 0x00, 0x1A, // indexSubtableListSize
+            0x00, 0x00, 0x00, 0x01, // numberOfIndexSubtables
+            0x00, 0x00, 0x00, 0x00, // colorRef
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // hori
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // vert
+            0x00, 0x44, 0x00, 0x46, // startGlyphIndex, endGlyphIndex
+            0x0C, 0x0C, 0x01, 0x01, // ppemX, ppemY, bitDepth, flags
+            // IndexSubtableList
+            0x00, 0x44, 0x00, 0x46, 0x00, 0x00, 0x00, 0x08, // firstGlyphIndex, lastGlyphIndex, indexSubtableOffset
+            // IndexSubtable4
+            0x00, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x04, // indexFormat, imageFormat, imageDataOffset
+            0x00, 0x00, 0x00, 0x02, // numGlyphs
+            0x00, 0x44, 0x00, 0x00, // glyphID, sbitOffset
+            0x00, 0x46, 0x00, 0x07, // glyphID, sbitOffset
+            0x00, 0x00, 0x00, 0x0E, // glyphID, sbitOffset
+        ];
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x02, 0x00, 0x00, // majorVersion, minorVersion
+            0x02, 0x08, 0x01, 0x02, 0x09, // smallMetrics
+            0b1111_0000, 0b0000_1111, // imageData
+            0x02, 0x08, 0x00, 0x02, 0x08, // smallMetrics
+            0b1010_1010, 0b0101_0101, // imageData
+        ];
+        let mut file = graft(Fixture::OpenSans, &[(b"EBLC", locations), (b"EBDT", data)]);
+        let font = &mut file[0];
+        let bitmap = ok!(ok!(font.bitmap_glyph('a', 12)));
+        assert_eq!(bitmap.format, BitmapFormat::Gray(1));
+        assert_eq!(bitmap.data, [0b1111_0000, 0b0000_1111]);
+        assert_eq!((bitmap.ppem, bitmap.size), (12, (8, 2)));
+        assert_eq!((bitmap.bearing, bitmap.advance_width), ((1, 2), 9));
+        let bitmap = ok!(ok!(font.bitmap_glyph('c', 12)));
+        assert_eq!(bitmap.data, [0b1010_1010, 0b0101_0101]);
+        assert!(ok!(font.bitmap_glyph('b', 12)).is_none());
+
+        #[rustfmt::skip]
+        let locations = vec![
+            0x00, 0x03, 0x00, 0x00, // majorVersion, minorVersion
+            0x00, 0x00, 0x00, 0x01, // numSizes
+            0x00, 0x00, 0x00, 0x38, // indexSubtableListOffset
+            0x00, 0x00, 0x00, 0x18, // indexSubtableListSize
+            0x00, 0x00, 0x00, 0x01, // numberOfIndexSubtables
+            0x00, 0x00, 0x00, 0x00, // colorRef
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // hori
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // vert
+            0x00, 0x44, 0x00, 0x44, // startGlyphIndex, endGlyphIndex
+            0x10, 0x10, 0x20, 0x01, // ppemX, ppemY, bitDepth, flags
+            // IndexSubtableList
+            0x00, 0x44, 0x00, 0x44, 0x00, 0x00, 0x00, 0x08, // firstGlyphIndex, lastGlyphIndex, indexSubtableOffset
+            // IndexSubtable1
+            0x00, 0x01, 0x00, 0x11, 0x00, 0x00, 0x00, 0x04, // indexFormat, imageFormat, imageDataOffset
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, // sbitOffsets
+        ];
+        #[rustfmt::skip]
+        let data = vec![
+            0x00, 0x03, 0x00, 0x00, // majorVersion, minorVersion
+            0x0E, 0x0D, 0x00, 0x0C, 0x0F, // smallMetrics
+            0x00, 0x00, 0x00, 0x08, // dataLen
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, // data
+        ];
+        let mut malformed = data.clone();
+        malformed[9..13].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xF0]);
+        let mut file = graft(
+            Fixture::OpenSans,
+            &[(b"CBLC", locations.clone()), (b"CBDT", data)],
+        );
+        let font = &mut file[0];
+        let bitmap = ok!(ok!(font.bitmap_glyph('a', 20)));
+        assert_eq!(bitmap.format, BitmapFormat::Png);
+        assert_eq!(bitmap.data, b"\x89PNG\r\n\x1A\n");
+        assert_eq!((bitmap.ppem, bitmap.size), (16, (13, 14)));
+        assert_eq!((bitmap.bearing, bitmap.advance_width), ((0, 12), 15));
+
+        let mut file = graft(
+            Fixture::OpenSans,
+            &[(b"CBLC", locations), (b"CBDT", malformed)],
+        );
+        assert!(file[0].bitmap_glyph('a', 20).is_err());
+
+        let profile = table(Fixture::OpenSans, b"maxp");
+        let glyph_count = u16::from_be_bytes([profile[4], profile[5]]) as usize;
+        #[rustfmt::skip]
+        let images = [
+            // dupe of c
+            vec![0x00, 0x00, 0x00, 0x00, b'd', b'u', b'p', b'e', 0x00, 0x46],
+            // TIFF with the width and height in a short and a long
+            vec![
+                0x00, 0x01, 0xFF, 0xFE, b't', b'i', b'f', b'f',
+                b'I', b'I', 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00,
+                0x02, 0x00,
+                0x00, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00,
+                0x01, 0x01, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00,
+            ],
+            // JPEG with an application segment before the frame
+            vec![
+                0x00, 0x02, 0xFF, 0xFD, b'j', b'p', b'g', b' ',
+                0xFF, 0xD8,
+                0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00,
+                0xFF, 0xC0, 0x00, 0x0B, 0x08, 0x00, 0x07, 0x00, 0x05, 0x01, 0x01, 0x11, 0x00,
+            ],
+        ];
+        #[rustfmt::skip]
+        let mut standard = vec![
+            0x00, 0x01, 0x00, 0x01, // version, flags
+            0x00, 0x00, 0x00, 0x01, // numStrikes
+            0x00, 0x00, 0x00, 0x0C, // strikeOffsets
+            0x00, 0x14, 0x00, 0x48, // ppem, ppi
+        ];
+        let mut offset = 4 + 4 * (glyph_count + 1);
+        for glyph_id in 0..=glyph_count {
+            standard.extend((offset as u32).to_be_bytes());
+            if let Some(image) = glyph_id
+                .checked_sub(0x44)
+                .and_then(|index| images.get(index))
+            {
+                offset += image.len();
+            }
+        }
+        standard.extend(images.concat());
+        let mut file = graft(Fixture::OpenSans, &[(b"sbix", standard)]);
+        let font = &mut file[0];
+        let advance_width =
+            |glyph: font::Glyph| (glyph.advance_width * 20.0 / 2048.0).round() as u16;
+        let bitmap = ok!(ok!(font.bitmap_glyph('a', 20)));
+        assert_eq!(bitmap.format, BitmapFormat::Jpeg);
+        assert_eq!(bitmap.data, images[2][8..]);
+        assert_eq!(
+            (bitmap.ppem, bitmap.size, bitmap.bearing),
+            (20, (5, 7), (2, 4))
+        );
+        assert_eq!(
+            bitmap.advance_width,
+            advance_width(ok!(ok!(font.glyph('a'))))
+        );
+        let bitmap = ok!(ok!(font.bitmap_glyph('b', 20)));
+        assert_eq!(bitmap.format, BitmapFormat::Tiff);
+        assert_eq!((bitmap.size, bitmap.bearing), ((3, 4), (1, 2)));
+        let bitmap = ok!(ok!(font.bitmap_glyph('c', 20)));
+        assert_eq!(bitmap.format, BitmapFormat::Jpeg);
+        assert_eq!(
+            bitmap.advance_width,
+            advance_width(ok!(ok!(font.glyph('c'))))
+        );
+        assert_ne!(
+            advance_width(ok!(ok!(font.glyph('a')))),
+            advance_width(ok!(ok!(font.glyph('c')))),
+        );
+    }
+
+    #[test]
+    fn svg() {
+        let expected = concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg">"#,
+            r##"<path id="glyph68" d="M0 0L100 0L100 -100Z" fill="#c00"/>"##,
+            r##"<path id="glyph69" d="M0 0L50 -100L100 0Z" fill="#0c0"/>"##,
+            r#"</svg>"#,
+        );
+        #[rustfmt::skip]
+        let document = [
+            0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xB3, 0x29, 0x2E, 0x4B,
+            0x57, 0xA8, 0xC8, 0xCD, 0xC9, 0x2B, 0xB6, 0x55, 0xCA, 0x28, 0x29, 0x29, 0xB0, 0xD2,
+            0xD7, 0x2F, 0x2F, 0x2F, 0xD7, 0x2B, 0x37, 0xD6, 0xCB, 0x2F, 0x4A, 0xD7, 0x37, 0x32,
+            0x30, 0x30, 0xD0, 0x07, 0xAA, 0x50, 0xB2, 0xB3, 0x29, 0x48, 0x2C, 0xC9, 0x50, 0xC8,
+            0x4C, 0xB1, 0x55, 0x4A, 0xCF, 0xA9, 0x2C, 0xC8, 0x30, 0xB3, 0x50, 0x52, 0x00, 0xB2,
+            0x7D, 0x0D, 0x14, 0x0C, 0x7C, 0x0C, 0x0D, 0x60, 0xA4, 0x2E, 0x90, 0x88, 0x52, 0x52,
+            0x48, 0xCB, 0xCC, 0xC9, 0xB1, 0x55, 0x52, 0x4E, 0x36, 0x30, 0x50, 0xD2, 0xC7, 0xD0,
+            0x68, 0x89, 0xD0, 0x68, 0x0A, 0xD1, 0x01, 0x31, 0x00, 0xA1, 0xCF, 0x20, 0x19, 0xAC,
+            0x0F, 0x64, 0xAF, 0x1D, 0x00, 0xA0, 0x52, 0x07, 0xB4, 0x9F, 0x00, 0x00, 0x00,
+        ];
+        #[rustfmt::skip]
+        let mut documents = vec![
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, // version, svgDocumentListOffset
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x00, 0x02, // numEntries
+            0x00, 0x44, 0x00, 0x45, 0x00, 0x00, 0x00, 0x1A, 0x00, 0x00, 0x00, 0x7D, // record
+            0x00, 0x46, 0x00, 0x46, 0x00, 0x00, 0x00, 0x97, 0x00, 0x00, 0x00, 0x9F, // record
+        ];
+        documents.extend(document);
+        documents.extend(expected.as_bytes());
+        let mut malformed = documents.clone();
+        malformed[32..36].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xF0]);
+        let mut file = graft(Fixture::OpenSans, &[(b"SVG ", documents)]);
+        let font = &mut file[0];
+        for glyph_id in [68, 69, 70] {
+            let document = ok!(ok!(font.svg_glyph(glyph_id)));
+            assert_eq!(document.content, expected);
+        }
+        assert_eq!(ok!(ok!(font.svg_glyph(69))).glyph_ids, (68, 69));
+        assert!(ok!(font.svg_glyph(71)).is_none());
+
+        let mut file = graft(Fixture::OpenSans, &[(b"SVG ", malformed)]);
+        let font = &mut file[0];
+        assert!(ok!(font.svg_glyph(68)).is_some());
+        assert!(font.svg_glyph(70).is_err());
+    }
 
     #[test]
     fn a_ring() {
@@ -217,12 +585,98 @@ mod open_sans {
         ]);
     }
 
+    #[test]
+    fn a_ring_components() {
+        let font = &mut setup(Fixture::OpenSans)[0];
+        let components = ok!(font.components(167));
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].glyph_id, 68);
+        assert_eq!(components[0].offset, (0.0, 0.0).into());
+        assert!(components[0].use_metrics);
+        assert!(components[0].round_to_grid);
+        assert!(!components[0].scaled_offset);
+        assert_eq!(components[1].glyph_id, 336);
+        assert_eq!(components[1].offset, (-38.0, 0.0).into());
+        assert_eq!(components[1].transform, (1.0, 0.0, 0.0, 1.0));
+        assert!(components[1].points.is_none());
+        assert!(!components[1].use_metrics);
+        assert!(ok!(font.components(68)).is_empty());
+        let glyph = ok!(font.shallow_glyph(167));
+        assert_eq!(glyph.len(), 0);
+        assert_eq!(glyph.advance_width, 1157.0);
+        assert_eq!(glyph.side_bearings, (98.0, 37.0));
+    }
+
+    #[test]
+    fn a_hinted_after_others() {
+        let font = &mut setup(Fixture::OpenSans)[0];
+        let expected = trace(&ok!(ok!(font.hinted_glyph('a', 12))));
+        for character in ['o', 'å', 'z'] {
+            ok!(font.hinted_glyph(character, 12));
+            ok!(font.hinted_glyph(character, 20));
+        }
+        assert_eq!(trace(&ok!(ok!(font.hinted_glyph('a', 12)))), expected);
+    }
+
+    #[test]
+    fn a_hinted() {
+        let font = &mut setup(Fixture::OpenSans)[0];
+        let glyph = ok!(ok!(font.hinted_glyph('a', 12)));
+        assert_eq!(glyph.len(), 2);
+        assert_eq!(glyph.advance_width, 7.0);
+        assert_eq!(glyph.bounding_box, (0.578125, 0.0, 6.5625, 6.0));
+        #[rustfmt::skip]
+        assert_eq!(&trace(&glyph), &vec![
+            (     3.75,       6.0),
+            ( 4.703125,  5.734375),
+            ( 5.359375,  4.984375),
+            ( 5.421875,  4.984375),
+            (   5.8125,       6.0),
+            (   6.5625,       6.0),
+            ( 5.203125,       0.0),
+            ( 4.421875,       0.0),
+            ( 4.578125,  1.203125),
+            (  4.53125,  1.203125),
+            (   2.3125,       0.0),
+            (1.0390625, 0.5234375),
+            ( 0.578125,  1.953125),
+            (0.9921875, 3.9921875),
+            ( 2.140625,   5.46875),
+            (     3.75,       6.0),
+
+            (    2.625,    0.8125),
+            ( 3.765625,   1.28125),
+            (4.6796875, 2.5078125),
+            (  5.03125,   4.03125),
+            ( 4.703125,  4.859375),
+            (  3.84375,  5.171875),
+            (2.7109375,  4.734375),
+            (1.8828125,  3.546875),
+            ( 1.578125,   1.96875),
+            ( 1.859375, 1.1015625),
+            (    2.625,    0.8125),
+        ]);
+    }
+
+    #[test]
+    fn a_ring_hinted() {
+        let font = &mut setup(Fixture::OpenSans)[0];
+        let glyph = ok!(ok!(font.hinted_glyph('å', 16)));
+        assert!(glyph.hints.is_none());
+        assert_eq!(glyph.len(), 4);
+        assert_eq!(glyph.advance_width, 9.0);
+        assert_eq!(glyph.bounding_box, (0.765625, 0.0, 8.75, 13.0));
+        assert_eq!(glyph.side_bearings, (0.765625, 0.25));
+    }
+
     #[test]
     fn copyright() {
         let font = &mut setup(Fixture::OpenSans)[0];
         let glyph = ok!(ok!(font.glyph('©')));
         assert_eq!(glyph.bounding_box, (139.0, -20.0, 1642.0, 1483.0));
         assert_eq!(glyph.side_bearings, (139.0, 62.0));
+        assert_eq!(glyph.advance_height, 2059.0);
+        assert_eq!(glyph.vertical_side_bearings, (84.0, 472.0));
     }
 
     #[test]
@@ -0,0 +1,80 @@
+#[macro_use]
+mod support;
+
+use font::features::Directory;
+use font::languages::{self, Language};
+use font::{Characters, Font};
+
+use crate::support::{setup, Fixture};
+
+#[test]
+fn crimson_text() {
+    let mut file = setup(Fixture::CrimsonText);
+    let values = extract(&mut file[0]);
+    assert_eq!(values.len(), 34);
+    assert!(values.contains(&("vi", true)));
+    assert!(values.contains(&("yo", true)));
+    assert!(values.contains(&("ig", false)));
+    assert!(!values.iter().any(|(code, _)| *code == "ru"));
+}
+
+#[test]
+fn noto_serif_thai() {
+    let mut file = setup(Fixture::NotoSerifThai);
+    let values = extract(&mut file[0]);
+    assert!(values.contains(&("th", true)));
+    assert!(!values.contains(&("vi", false)));
+}
+
+#[test]
+fn open_sans() {
+    let mut file = setup(Fixture::OpenSans);
+    let values = extract(&mut file[0]);
+    assert!(values.contains(&("pl", true)));
+    assert!(values.contains(&("ru", true)));
+    assert!(values.contains(&("uk", true)));
+    assert!(!values.iter().any(|(code, _)| *code == "yo"));
+}
+
+#[test]
+fn get() {
+    let language = ok!(Language::get("pl"));
+    assert_eq!(language.name, "Polish");
+    assert!(language.base.split_whitespace().any(|value| value == "ł"));
+    assert!(Language::get("xx").is_none());
+}
+
+#[test]
+fn case() {
+    let features = Directory::default();
+    let check = |code: &str, extra: &str, skip: &str| {
+        let language = ok!(Language::get(code));
+        let characters = language
+            .base
+            .split_whitespace()
+            .flat_map(str::chars)
+            .flat_map(|character| character.to_uppercase().chain([character]))
+            .chain(extra.chars())
+            .filter(|character| !skip.contains(*character))
+            .collect::<Characters>();
+        languages::supported(&characters, &features)
+            .iter()
+            .any(|support| support.language.code == code)
+    };
+    assert!(check("pl", "", ""));
+    assert!(!check("pl", "", "Ł"));
+    assert!(check("ru", "", ""));
+    assert!(!check("ru", "", "Ё"));
+    assert!(!check("tr", "", ""));
+    assert!(check("tr", "\u{130}", ""));
+    assert!(!check("tr", "\u{130}", "ı"));
+    assert!(!check("az", "", ""));
+    assert!(check("az", "\u{130}", ""));
+}
+
+fn extract(font: &mut Font<std::fs::File>) -> Vec<(&'static str, bool)> {
+    ok!(font.supported_languages())
+        .into_iter()
+        .map(|support| (support.language.code, support.auxiliary))
+        .collect()
+}
@@ -0,0 +1,67 @@
+#[macro_use]
+mod support;
+
+use crate::support::{setup, trace, Fixture};
+
+#[test]
+fn open_sans() {
+    let mut file = setup(Fixture::OpenSans);
+    let layout = ok!(file[0].measure("AV", 2048.0));
+    assert_eq!(layout.advance_width, 2177.0);
+    assert_eq!(layout.bounding_box, (-117.0, 0.0, 2366.0, 1462.0));
+    assert_eq!(layout.line_height, 2191.0);
+    let values = layout
+        .glyphs
+        .iter()
+        .map(|placement| (placement.character, placement.glyph_id, placement.offset.0))
+        .collect::<Vec<_>>();
+    assert_eq!(values, [('A', 36, 0.0), ('V', 57, 1055.0)]);
+
+    let layout = ok!(file[0].measure("A V", 1024.0));
+    assert_eq!(layout.advance_width, 1395.5);
+    assert_eq!(layout.line_height, 1095.5);
+}
+
+#[test]
+fn source_serif() {
+    let mut file = setup(Fixture::SourceSerif);
+    let layout = ok!(file[0].measure("AV", 1000.0));
+    assert_eq!(layout.advance_width, 1209.0);
+    assert_eq!(layout.bounding_box, (5.0, -10.0, 1200.0, 669.0));
+    assert_eq!(layout.line_height, 1000.0);
+    let values = layout
+        .glyphs
+        .iter()
+        .map(|placement| (placement.character, placement.glyph_id, placement.offset.0))
+        .collect::<Vec<_>>();
+    assert_eq!(values, [('A', 2, 0.0), ('V', 23, 535.0)]);
+
+    let mut points = vec![];
+    for placement in layout.glyphs.iter() {
+        let (x, y) = placement.offset.into();
+        points.extend(
+            trace(&placement.glyph)
+                .into_iter()
+                .map(|(a, b)| (a + x, b + y)),
+        );
+    }
+    let glyph = layout.outline();
+    assert_eq!(glyph.advance_width, 1209.0);
+    assert_eq!(glyph.len(), 3);
+    assert_eq!(trace(&glyph), points);
+}
+
+#[test]
+fn outline() {
+    let mut file = setup(Fixture::SourceSerif);
+    let glyph = ok!(file[0].outline("AV", 1000.0, 0.0));
+    assert_eq!(glyph.advance_width, 1209.0);
+    assert_eq!(glyph.bounding_box, (5.0, -10.0, 1200.0, 669.0));
+    let path = glyph.svg_path();
+    assert!(path.starts_with("M"));
+    assert_eq!(path.matches('Z').count(), 3);
+
+    let glyph = ok!(file[0].outline("AV", 1000.0, 100.0));
+    assert_eq!(glyph.advance_width, 1309.0);
+    assert_eq!(glyph.bounding_box, (5.0, -10.0, 1300.0, 669.0));
+}
@@ -38,6 +38,17 @@ fn open_sans() {
     assert_eq!(values.descender, -492.0);
     assert_eq!(values.clipping_descender, -600.0);
     assert_eq!(values.line_gap, 132.0);
+    assert_eq!(values.underline_position, -154.0);
+    assert_eq!(values.underline_thickness, 102.0);
+    assert_eq!(values.strikeout_position, 512.0);
+    assert_eq!(values.strikeout_thickness, 102.0);
+    assert_eq!(values.subscript_size, (1434.0, 1331.0));
+    assert_eq!(values.subscript_offset, (0.0, -287.0));
+    assert_eq!(values.superscript_size, (1434.0, 1331.0));
+    assert_eq!(values.superscript_offset, (0.0, 977.0));
+    assert_eq!(values.italic_angle, -12.0);
+    assert_eq!(values.caret_slope, (2048.0, 435.0));
+    assert_eq!(values.caret_offset, 0.0);
 }
 
 #[test]
@@ -54,3 +65,106 @@ fn source_serif() {
     assert_eq!(values.clipping_descender, -335.0);
     assert_eq!(values.line_gap, 0.0);
 }
+
+#[test]
+fn source_serif_vertical() {
+    let mut file = setup(Fixture::SourceSerif);
+    let values = ok!(file[0].vertical_metrics());
+    assert_eq!(values.granularity, 1000.0);
+    assert_eq!(values.ascender, 500.0);
+    assert_eq!(values.descender, -500.0);
+    assert_eq!(values.line_gap, 0.0);
+    assert!(values.synthesized);
+}
+
+#[test]
+fn open_sans_line() {
+    use font::LinePolicy::*;
+
+    let metrics = |data: Vec<u8>| {
+        let mut file = ok!(font::File::read(std::io::Cursor::new(data)));
+        [Browser, Macintosh, Windows].map(|policy| {
+            let values = ok!(file[0].line_metrics(policy));
+            (values.ascender, values.descender, values.line_gap)
+        })
+    };
+
+    let data = ok!(std::fs::read(crate::support::path(Fixture::OpenSans)));
+    assert_eq!(
+        metrics(data.clone()),
+        [
+            (2189.0, -600.0, 0.0),
+            (2189.0, -600.0, 0.0),
+            (2189.0, -600.0, 0.0),
+        ],
+    );
+
+    let mut other = data.clone();
+    patch(&mut other, b"hhea", 4, &[2100, -600, 300]);
+    assert_eq!(
+        metrics(other.clone()),
+        [
+            (2100.0, -600.0, 300.0),
+            (2100.0, -600.0, 300.0),
+            (2189.0, -600.0, 211.0),
+        ],
+    );
+
+    patch(&mut other, b"OS/2", 62, &[0x0081]);
+    assert_eq!(
+        metrics(other),
+        [
+            (1567.0, -492.0, 132.0),
+            (2100.0, -600.0, 300.0),
+            (1567.0, -492.0, 132.0),
+        ],
+    );
+
+    let mut other = data;
+    patch(&mut other, b"hhea", 4, &[0, 0, 0]);
+    assert_eq!(
+        metrics(other),
+        [
+            (1567.0, -492.0, 132.0),
+            (1567.0, -492.0, 132.0),
+            (2189.0, -600.0, 0.0),
+        ],
+    );
+}
+
+#[test]
+fn open_sans_without_postscript() {
+    use std::io::Cursor;
+
+    let mut data = ok!(std::fs::read(crate::support::path(Fixture::OpenSans)));
+    let position = ok!(record(&data, b"post"));
+    data[position..(position + 4)].copy_from_slice(b"posu");
+    let mut file = ok!(font::File::read(Cursor::new(data)));
+    let values = ok!(file[0].metrics());
+    assert_eq!(values.ascender, 1567.0);
+    assert!(values.underline_position.is_nan());
+    assert!(values.underline_thickness.is_nan());
+    assert_eq!(values.italic_angle, 0.0);
+    assert_eq!(values.caret_slope, (2048.0, 435.0));
+    let layout = ok!(file[0].measure("Hi", 16.0));
+    assert!(layout.advance_width > 0.0);
+}
+
+// Overwrite values in a table of a font file.
+fn patch(data: &mut [u8], tag: &[u8; 4], offset: usize, values: &[i16]) {
+    let position = ok!(record(data, tag)) + 8;
+    let position = u32::from_be_bytes(ok!(data[position..(position + 4)].try_into()));
+    let position = position as usize + offset;
+    for (index, value) in values.iter().enumerate() {
+        let position = position + 2 * index;
+        data[position..(position + 2)].copy_from_slice(&value.to_be_bytes());
+    }
+}
+
+// The position of a table record in the table directory of a font file.
+fn record(data: &[u8], tag: &[u8; 4]) -> Option<usize> {
+    let count = u16::from_be_bytes([data[4], data[5]]) as usize;
+    (0..count)
+        .map(|index| 12 + 16 * index)
+        .find(|position| &data[*position..(*position + 4)] == tag)
+}
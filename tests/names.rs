@@ -69,6 +69,75 @@ mod source_serif {
 
     use crate::support::{setup, Fixture};
 
+    #[test]
+    fn accessors() {
+        let font = &mut setup(Fixture::SourceSerif)[0];
+        let names = ok!(font.names());
+        assert_eq!(ok!(names.family()), "Source Serif Pro");
+        assert_eq!(ok!(names.subfamily()), "Regular");
+        assert_eq!(ok!(names.typographic_family()), "Source Serif Pro");
+        assert_eq!(ok!(names.typographic_subfamily()), "Regular");
+        assert_eq!(ok!(names.wws_family()), "Source Serif Pro");
+        assert_eq!(ok!(names.wws_subfamily()), "Regular");
+        assert_eq!(ok!(names.full_name()), "Source Serif Pro");
+        assert_eq!(ok!(names.postscript_name()), "SourceSerifPro-Regular");
+        assert_eq!(
+            ok!(names.version()),
+            "Version 1.017;PS 1.0;hotconv 1.0.79;makeotf.lib2.5.61930",
+        );
+        assert_eq!(ok!(names.designer()), "Frank Grießhammer");
+        assert!(ok!(names.license()).contains("SIL Open Font License, Version 1.1"));
+    }
+
+    #[test]
+    fn edit() {
+        use std::io::Cursor;
+
+        use font::formats::opentype::read;
+        use font::opentype::truetype::tables::names::{NameID, PlatformID};
+        use font::Case;
+
+        let family = NameID::FontFamilyName;
+        let mut font = setup(Fixture::SourceSerif).fonts.remove(0);
+        ok!(font.set_name(family, "en", "Source Serif Ⅱ"));
+        ok!(font.set_name(family, "ja", "ソース セリフ"));
+        ok!(font.set_name(family, "tlh", "Klingon"));
+        ok!(font.set_name(NameID::DesignerName, "en-US", "Someone"));
+        ok!(font.remove_name(NameID::LicenseURL, "en"));
+
+        let mut cursor = Cursor::new(vec![]);
+        ok!(font.save(&mut cursor));
+        let mut font = ok!(ok!(read(Cursor::new(cursor.into_inner()))).pop());
+        let names = ok!(font.names());
+        assert_eq!(ok!(names.family()), "Source Serif Ⅱ");
+        assert_eq!(ok!(names.get_in(family, "ja-JP")), "ソース セリフ");
+        assert_eq!(ok!(names.get_in(family, "tlh")), "Klingon");
+        assert_eq!(ok!(names.designer()), "Someone");
+        assert_eq!(names.license_url(), None);
+        assert_eq!(names.languages(family), ["en", "ja", "tlh"]);
+
+        let table = names.borrow();
+        let records = table.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        assert!(!records
+            .iter()
+            .any(
+                |(platform_id, _, _, name_id)| *platform_id == PlatformID::Macintosh
+                    && *name_id == family
+            ));
+        let keys = records
+            .iter()
+            .map(|(platform_id, encoding_id, language_id, name_id)| {
+                (
+                    u16::from(*platform_id),
+                    *encoding_id,
+                    u16::from(*language_id),
+                    u16::from(*name_id),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
     #[test]
     fn read() {
         let font = &mut setup(Fixture::SourceSerif)[0];
@@ -145,3 +214,21 @@ fn write(fixture: Fixture) -> Cursor<Vec<u8>> {
 
     Cursor::new(cursor.into_inner())
 }
+
+mod qahiri {
+    use font::opentype::truetype::tables::names::NameID;
+
+    use crate::support::{setup, Fixture};
+
+    #[test]
+    fn get_in() {
+        let font = &mut setup(Fixture::Qahiri)[0];
+        let names = ok!(font.names());
+        let name_id = NameID::Other(256);
+        assert_eq!(names.languages(name_id), ["ar", "en"]);
+        assert_eq!(ok!(names.get_in(name_id, "ar-EG")), "حروف مهملة (بلا نقاط)");
+        assert_eq!(ok!(names.get_in(name_id, "ar")), "حروف مهملة (بلا نقاط)");
+        assert_eq!(ok!(names.get_in(name_id, "fr-CA")), ok!(names.get(name_id)));
+        assert_eq!(ok!(names.typographic_family_in("ar")), "Qahiri");
+    }
+}
@@ -39,3 +39,114 @@ fn noto_color_emoji() {
         ],
     );
 }
+
+#[test]
+fn source_serif() {
+    let font = &mut setup(Fixture::SourceSerif)[0];
+    assert!(ok!(font.color_glyph('a')).is_none());
+    assert!(ok!(font.palettes()).is_none());
+}
+
+#[test]
+fn open_sans() {
+    use crate::support::{graft, trace};
+
+    #[rustfmt::skip]
+    let colors = vec![
+        0x00, 0x00, // version
+        0x00, 0x01, // numBaseGlyphRecords
+        0x00, 0x00, 0x00, 0x0E, // baseGlyphRecordsOffset
+        0x00, 0x00, 0x00, 0x14, // layerRecordsOffset
+        0x00, 0x02, // numLayerRecords
+        0x00, 0x44, 0x00, 0x00, 0x00, 0x02, // a
+        0x00, 0x52, 0x00, 0x00, // o
+        0x00, 0x44, 0xFF, 0xFF, // a
+    ];
+    #[rustfmt::skip]
+    let palettes = vec![
+        0x00, 0x00, // version
+        0x00, 0x01, // numPaletteEntries
+        0x00, 0x01, // numPalettes
+        0x00, 0x01, // numColorRecords
+        0x00, 0x00, 0x00, 0x0E, // colorRecordsArrayOffset
+        0x00, 0x00, // colorRecordIndices
+        0x20, 0x40, 0x80, 0xFF, // blue, green, red, alpha
+    ];
+    let mut file = graft(Fixture::OpenSans, &[(b"COLR", colors), (b"CPAL", palettes)]);
+    let font = &mut file[0];
+    assert!(ok!(font.color_glyph('o')).is_none());
+    let glyph = ok!(ok!(font.color_glyph('a')));
+    let layers = glyph
+        .layers
+        .iter()
+        .map(|layer| (layer.glyph_id, layer.palette_index))
+        .collect::<Vec<_>>();
+    assert_eq!(layers, [(82, Some(0)), (68, None)]);
+    assert_eq!(
+        trace(&glyph.layers[0].glyph),
+        trace(&ok!(font.glyph_by_id(82))),
+    );
+    assert_eq!(
+        trace(&glyph.layers[1].glyph),
+        trace(&ok!(ok!(font.glyph('a')))),
+    );
+
+    let table = ok!(ok!(font.palettes()));
+    let table = table.borrow();
+    let colors = ok!(table.iter().next()).collect::<Vec<_>>();
+    assert_eq!(colors.len(), 1);
+    assert_eq!(
+        (
+            colors[0].red,
+            colors[0].green,
+            colors[0].blue,
+            colors[0].alpha
+        ),
+        (0x80, 0x40, 0x20, 0xFF),
+    );
+}
+
+#[test]
+fn open_sans_labels() {
+    use std::io::Cursor;
+
+    use font::opentype::truetype::tables::names::NameID;
+
+    use crate::support::graft;
+
+    #[rustfmt::skip]
+    let palettes = vec![
+        0x00, 0x01, // version
+        0x00, 0x02, // numPaletteEntries
+        0x00, 0x02, // numPalettes
+        0x00, 0x03, // numColorRecords
+        0x00, 0x00, 0x00, 0x20, // colorRecordsArrayOffset
+        0x00, 0x00, 0x00, 0x01, // colorRecordIndices
+        0x00, 0x00, 0x00, 0x2C, // paletteTypesArrayOffset
+        0x00, 0x00, 0x00, 0x34, // paletteLabelsArrayOffset
+        0x00, 0x00, 0x00, 0x38, // paletteEntryLabelsArrayOffset
+        0x00, 0x00, 0x00, 0x00, // padding
+        0x00, 0x00, 0xFF, 0xFF, // blue, green, red, alpha
+        0xFF, 0x00, 0x00, 0x80, // blue, green, red, alpha
+        0x00, 0xFF, 0x00, 0xFF, // blue, green, red, alpha
+        0x00, 0x00, 0x00, 0x01, // paletteTypes[0]
+        0x00, 0x00, 0x00, 0x02, // paletteTypes[1]
+        0x01, 0x00, 0xFF, 0xFF, // paletteLabels
+        0xFF, 0xFF, 0x01, 0x01, // paletteEntryLabels
+    ];
+    let mut file = graft(Fixture::OpenSans, &[(b"CPAL", palettes)]);
+    let mut font = file.fonts.remove(0);
+    ok!(font.set_name(NameID::Other(256), "en", "Light"));
+    ok!(font.set_name(NameID::Other(257), "en", "Outline"));
+    let mut cursor = Cursor::new(vec![]);
+    ok!(font.save(&mut cursor));
+
+    let mut file = ok!(font::File::read(Cursor::new(cursor.into_inner())));
+    let palettes = ok!(ok!(file[0].palettes()));
+    let labels = palettes
+        .iter()
+        .map(|palette| palette.label)
+        .collect::<Vec<_>>();
+    assert_eq!(labels, [Some("Light".into()), None]);
+    assert_eq!(palettes.entry_labels(), [None, Some("Outline".into())]);
+}
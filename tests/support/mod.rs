@@ -74,3 +74,66 @@ pub fn trace(glyph: &Glyph) -> Vec<(f32, f32)> {
     }
     points
 }
+
+pub fn graft(fixture: Fixture, tables: &[(&[u8; 4], Vec<u8>)]) -> File<std::io::Cursor<Vec<u8>>> {
+    ok!(File::read(std::io::Cursor::new(extend(fixture, tables))))
+}
+
+// Rebuild a font file with additional or replaced tables.
+pub fn extend(fixture: Fixture, tables: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let data = ok!(std::fs::read(path(fixture)));
+    let mut records = read(&data);
+    records.retain(|(tag, _)| tables.iter().all(|(other, _)| tag != *other));
+    records.extend(tables.iter().map(|(tag, table)| (**tag, table.clone())));
+    records.sort_by_key(|(tag, _)| *tag);
+
+    let count = records.len() as u16;
+    let power = 1 << (15 - count.leading_zeros());
+    let mut output = data[..4].to_vec();
+    for value in [
+        count,
+        16 * power,
+        power.trailing_zeros() as u16,
+        16 * (count - power),
+    ] {
+        output.extend(value.to_be_bytes());
+    }
+    let mut offset = 12 + 16 * records.len();
+    for (tag, table) in records.iter() {
+        let checksum = table.chunks(4).fold(0u32, |sum, chunk| {
+            let mut value = [0; 4];
+            value[..chunk.len()].copy_from_slice(chunk);
+            sum.wrapping_add(u32::from_be_bytes(value))
+        });
+        output.extend(tag);
+        output.extend(checksum.to_be_bytes());
+        output.extend((offset as u32).to_be_bytes());
+        output.extend((table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in records.iter() {
+        output.extend(table);
+        output.resize(output.len().next_multiple_of(4), 0);
+    }
+    output
+}
+
+pub fn table(fixture: Fixture, tag: &[u8; 4]) -> Vec<u8> {
+    let data = ok!(std::fs::read(path(fixture)));
+    ok!(read(&data).into_iter().find(|(other, _)| other == tag)).1
+}
+
+fn read(data: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+    let count = u16::from_be_bytes([data[4], data[5]]) as usize;
+    (0..count)
+        .map(|index| {
+            let record = &data[(12 + 16 * index)..(28 + 16 * index)];
+            let offset = u32::from_be_bytes(ok!(record[8..12].try_into())) as usize;
+            let size = u32::from_be_bytes(ok!(record[12..16].try_into())) as usize;
+            (
+                ok!(record[..4].try_into()),
+                data[offset..(offset + size)].to_vec(),
+            )
+        })
+        .collect()
+}